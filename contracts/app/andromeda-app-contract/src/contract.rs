@@ -187,6 +187,9 @@ pub fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response,
         ExecuteMsg::ClaimOwnership { name, new_owner } => {
             execute::claim_ownership(ctx, name, new_owner)
         }
+        ExecuteMsg::AcceptComponentOwnership { name } => {
+            execute::accept_component_ownership(ctx, name)
+        }
         ExecuteMsg::ProxyMessage { msg, name } => execute::message(ctx, name, msg),
         ExecuteMsg::UpdateAddress { name, addr } => execute::update_address(ctx, name, addr),
         ExecuteMsg::AssignAppToComponents {} => execute::assign_app_to_components(ctx),
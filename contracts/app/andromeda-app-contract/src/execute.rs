@@ -1,8 +1,10 @@
 use crate::state::{
     add_app_component, generate_assign_app_message, generate_ownership_message,
-    load_component_addresses, ADO_ADDRESSES,
+    generate_update_owner_message, load_component_addresses, ADO_ADDRESSES,
 };
 use andromeda_app::app::{AppComponent, ComponentType};
+use andromeda_std::ado_base::ownership::PendingOwnerResponse;
+use andromeda_std::ado_base::AndromedaQuery;
 use andromeda_std::ado_contract::ADOContract;
 use andromeda_std::common::context::ExecuteContext;
 use andromeda_std::error::ContractError;
@@ -113,6 +115,46 @@ pub fn claim_ownership(
         .add_attribute("method", "claim_ownership"))
 }
 
+/// Accepts ownership of the app's components on behalf of their pending owner, the sender
+///
+/// Only components still owned by the app whose unexpired ownership proposal names the sender are transferred
+pub fn accept_component_ownership(
+    ctx: ExecuteContext,
+    name_opt: Option<String>,
+) -> Result<Response, ContractError> {
+    let addresses = match name_opt {
+        Some(name) => vec![ADO_ADDRESSES.load(ctx.deps.storage, &name)?],
+        None => load_component_addresses(ctx.deps.storage, None)?,
+    };
+
+    let mut msgs: Vec<SubMsg> = vec![];
+    for address in addresses {
+        let curr_owner = AOSQuerier::ado_owner_getter(&ctx.deps.querier, &address)?;
+        if curr_owner != ctx.env.contract.address {
+            continue;
+        }
+        let proposal: PendingOwnerResponse = ctx
+            .deps
+            .querier
+            .query_wasm_smart(&address, &AndromedaQuery::PendingOwner {})?;
+        let is_pending_owner = proposal.pending_owner.as_deref() == Some(ctx.info.sender.as_str());
+        let is_expired = proposal
+            .expiration
+            .map_or(false, |expiration| expiration.is_expired(&ctx.env.block));
+        if is_pending_owner && !is_expired {
+            msgs.push(generate_update_owner_message(
+                address,
+                ctx.info.sender.as_str(),
+            )?);
+        }
+    }
+    ensure!(!msgs.is_empty(), ContractError::NoPendingOwner {});
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_attribute("method", "accept_component_ownership"))
+}
+
 pub fn message(ctx: ExecuteContext, name: String, msg: Binary) -> Result<Response, ContractError> {
     let ExecuteContext { info, deps, .. } = ctx;
     //Temporary until message sender attached to Andromeda Comms
@@ -32,6 +32,12 @@ pub fn mock_claim_ownership_msg(component_name: Option<String>) -> ExecuteMsg {
     }
 }
 
+pub fn mock_accept_component_ownership_msg(component_name: Option<String>) -> ExecuteMsg {
+    ExecuteMsg::AcceptComponentOwnership {
+        name: component_name,
+    }
+}
+
 pub fn mock_get_components_msg() -> QueryMsg {
     QueryMsg::GetComponents {}
 }
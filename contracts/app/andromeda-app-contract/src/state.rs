@@ -76,7 +76,26 @@ pub fn load_component_descriptors(
     Ok(descriptors)
 }
 
+/// Proposes the given owner for the ADO at `addr`, the owner must accept ownership before it is transferred
 pub fn generate_ownership_message(addr: Addr, owner: &str) -> Result<SubMsg, ContractError> {
+    let msg = to_binary(&AndromedaMsg::ProposeOwner {
+        address: AndrAddr::from_string(owner),
+        expiration: None,
+    })?;
+    Ok(SubMsg {
+        id: ReplyId::ClaimOwnership.repr(),
+        reply_on: ReplyOn::Error,
+        msg: CosmosMsg::Wasm(WasmMsg::Execute {
+            msg,
+            funds: Vec::<Coin>::new(),
+            contract_addr: addr.to_string(),
+        }),
+        gas_limit: None,
+    })
+}
+
+/// Transfers ownership of the ADO at `addr` to `owner` immediately, used once `owner` has accepted through the app
+pub fn generate_update_owner_message(addr: Addr, owner: &str) -> Result<SubMsg, ContractError> {
     let msg = to_binary(&AndromedaMsg::UpdateOwner {
         address: owner.to_string(),
     })?;
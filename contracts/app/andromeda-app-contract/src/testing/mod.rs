@@ -4,7 +4,9 @@ use super::{contract::*, state::ADO_ADDRESSES};
 use andromeda_app::app::{AppComponent, ComponentType, ExecuteMsg, InstantiateMsg};
 use andromeda_std::amp::AndrAddr;
 use andromeda_std::os::vfs::{convert_component_name, ExecuteMsg as VFSExecuteMsg};
-use andromeda_std::testing::mock_querier::{mock_dependencies_custom, MOCK_KERNEL_CONTRACT};
+use andromeda_std::testing::mock_querier::{
+    mock_dependencies_custom, MOCK_KERNEL_CONTRACT, MOCK_YANKED_ADODB_KEY,
+};
 
 use andromeda_std::{ado_base::AndromedaMsg, error::ContractError};
 
@@ -201,6 +203,38 @@ fn test_add_app_component_duplicate_name() {
     assert_eq!(ContractError::NameAlreadyTaken {}, err);
 }
 
+#[test]
+fn test_add_app_component_yanked_version() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+    let inst_msg = InstantiateMsg {
+        app_components: vec![],
+        name: String::from("Some App"),
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+        chain_info: None,
+    };
+
+    instantiate(deps.as_mut(), env.clone(), info.clone(), inst_msg).unwrap();
+
+    let msg = ExecuteMsg::AddAppComponent {
+        component: AppComponent {
+            name: "token".to_string(),
+            ado_type: MOCK_YANKED_ADODB_KEY.to_string(),
+            component_type: ComponentType::New(to_binary(&true).unwrap()),
+        },
+    };
+
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(
+        ContractError::ADOVersionYanked {
+            version: format!("{MOCK_YANKED_ADODB_KEY}@0.1.0")
+        },
+        err
+    );
+}
+
 #[test]
 fn test_add_app_component() {
     let mut deps = mock_dependencies_custom(&[]);
@@ -332,6 +366,27 @@ fn test_claim_ownership_empty() {
     assert_eq!(0, res.messages.len());
 }
 
+#[test]
+fn test_accept_component_ownership_empty() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+    let inst_msg = InstantiateMsg {
+        app_components: vec![],
+        name: String::from("Some App"),
+        owner: None,
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        chain_info: None,
+    };
+
+    instantiate(deps.as_mut(), env.clone(), info.clone(), inst_msg).unwrap();
+
+    let msg = ExecuteMsg::AcceptComponentOwnership { name: None };
+
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err);
+}
+
 //TODO: Fix this test
 // #[test]
 // fn test_claim_ownership_all() {
@@ -412,8 +467,9 @@ fn test_claim_ownership() {
         id: 101,
         msg: CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: "tokenaddress".to_string(),
-            msg: to_binary(&AndromedaMsg::UpdateOwner {
-                address: "creator".to_string(),
+            msg: to_binary(&AndromedaMsg::ProposeOwner {
+                address: AndrAddr::from_string("creator"),
+                expiration: None,
             })
             .unwrap(),
             funds: vec![],
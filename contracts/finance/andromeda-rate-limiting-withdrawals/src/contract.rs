@@ -100,6 +100,10 @@ pub fn execute(
 
     if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(msg, ExecuteMsg::UpdateOwner { .. })
+        && !matches!(msg, ExecuteMsg::ProposeOwner { .. })
+        && !matches!(msg, ExecuteMsg::AcceptOwnership {})
+        && !matches!(msg, ExecuteMsg::CancelOwnershipProposal {})
+        && !matches!(msg, ExecuteMsg::RenounceOwnership {})
     {
         contract.module_hook::<Response>(
             &deps.as_ref(),
@@ -115,6 +115,10 @@ pub fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response,
 
     if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(msg, ExecuteMsg::UpdateOwner { .. })
+        && !matches!(msg, ExecuteMsg::ProposeOwner { .. })
+        && !matches!(msg, ExecuteMsg::AcceptOwnership {})
+        && !matches!(msg, ExecuteMsg::CancelOwnershipProposal {})
+        && !matches!(msg, ExecuteMsg::RenounceOwnership {})
     {
         contract.module_hook::<Response>(
             &ctx.deps.as_ref(),
@@ -81,6 +81,10 @@ pub fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response,
 
     if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(msg, ExecuteMsg::UpdateOwner { .. })
+        && !matches!(msg, ExecuteMsg::ProposeOwner { .. })
+        && !matches!(msg, ExecuteMsg::AcceptOwnership {})
+        && !matches!(msg, ExecuteMsg::CancelOwnershipProposal {})
+        && !matches!(msg, ExecuteMsg::RenounceOwnership {})
     {
         contract.module_hook::<Response>(
             &ctx.deps.as_ref(),
@@ -486,6 +490,15 @@ fn execute_claim(
     // Calculate the funds to be received after tax
     let after_tax_payment = purchase_token(deps.as_ref(), &info, token_auction_state.clone())?;
 
+    // Any percentage based fee is charged to the winner on the winning bid
+    let fee_msg = ADOContract::default().pay_fee_for_amount(
+        deps.storage,
+        &deps.querier,
+        "Claim".to_string(),
+        token_auction_state.high_bidder_addr.clone(),
+        Some(token_auction_state.high_bidder_amount),
+    )?;
+
     Ok(Response::new()
         .add_submessages(after_tax_payment.1)
         // Send funds to the original owner.
@@ -507,7 +520,8 @@ fn execute_claim(
         .add_attribute("token_contract", token_auction_state.token_address)
         .add_attribute("recipient", &token_auction_state.high_bidder_addr)
         .add_attribute("winning_bid_amount", token_auction_state.high_bidder_amount)
-        .add_attribute("auction_id", token_auction_state.auction_id))
+        .add_attribute("auction_id", token_auction_state.auction_id)
+        .add_submessage(fee_msg))
 }
 
 fn purchase_token(
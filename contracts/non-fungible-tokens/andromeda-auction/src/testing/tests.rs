@@ -14,6 +14,7 @@ use andromeda_non_fungible_tokens::{
 };
 use andromeda_std::{
     ado_base::modules::Module,
+    ado_contract::ADOContract,
     common::{encode_binary, expiration::MILLISECONDS_TO_NANOSECONDS_RATIO},
     error::ContractError,
     testing::mock_querier::MOCK_KERNEL_CONTRACT,
@@ -944,7 +945,18 @@ fn execute_claim() {
             .add_attribute("token_contract", MOCK_TOKEN_ADDR)
             .add_attribute("recipient", "sender")
             .add_attribute("winning_bid_amount", Uint128::from(100u128))
-            .add_attribute("auction_id", "1"),
+            .add_attribute("auction_id", "1")
+            .add_submessage(
+                ADOContract::default()
+                    .pay_fee_for_amount(
+                        deps.as_ref().storage,
+                        &deps.as_ref().querier,
+                        "Claim".to_string(),
+                        Addr::unchecked("sender"),
+                        Some(Uint128::from(100u128)),
+                    )
+                    .unwrap()
+            ),
         res
     );
 }
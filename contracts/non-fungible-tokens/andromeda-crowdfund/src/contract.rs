@@ -107,6 +107,10 @@ pub fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response,
 
     if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(msg, ExecuteMsg::UpdateOwner { .. })
+        && !matches!(msg, ExecuteMsg::ProposeOwner { .. })
+        && !matches!(msg, ExecuteMsg::AcceptOwnership {})
+        && !matches!(msg, ExecuteMsg::CancelOwnershipProposal {})
+        && !matches!(msg, ExecuteMsg::RenounceOwnership {})
     {
         contract.module_hook::<Response>(
             &ctx.deps.as_ref(),
@@ -336,7 +340,17 @@ fn execute_purchase_by_token_id(
     STATE.save(deps.storage, &state)?;
     PURCHASES.save(deps.storage, &sender, &purchases)?;
 
+    // Any percentage based fee is charged on the token price
+    let fee_msg = ADOContract::default().pay_fee_for_amount(
+        deps.storage,
+        &deps.querier,
+        "PurchaseByTokenId".to_string(),
+        info.sender,
+        Some(state.price.amount),
+    )?;
+
     Ok(Response::new()
+        .add_submessage(fee_msg)
         .add_attribute("action", "purchase")
         .add_attribute("token_id", token_id))
 }
@@ -386,6 +400,15 @@ fn execute_purchase(
     PURCHASES.save(deps.storage, &sender, &purchases)?;
     STATE.save(deps.storage, &state)?;
 
+    // Any percentage based fee is charged on the price of the purchased tokens, excluding taxes
+    let fee_msg = ADOContract::default().pay_fee_for_amount(
+        deps.storage,
+        &deps.querier,
+        "Purchase".to_string(),
+        info.sender.clone(),
+        Some(state.price.amount * Uint128::from(number_of_tokens_purchased as u128)),
+    )?;
+
     // Refund user if they sent more. This can happen near the end of the sale when they weren't
     // able to get the amount that they wanted.
     let mut funds = info.funds;
@@ -402,6 +425,7 @@ fn execute_purchase(
     };
 
     Ok(resp
+        .add_submessage(fee_msg)
         .add_attribute("action", "purchase")
         .add_attribute(
             "number_of_tokens_wanted",
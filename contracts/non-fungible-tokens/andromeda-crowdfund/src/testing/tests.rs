@@ -16,6 +16,7 @@ use andromeda_non_fungible_tokens::{
 };
 use andromeda_std::{
     ado_base::modules::Module,
+    ado_contract::ADOContract,
     amp::{addresses::AndrAddr, recipient::Recipient},
     common::encode_binary,
     error::ContractError,
@@ -23,12 +24,24 @@ use andromeda_std::{
 use cosmwasm_std::{
     coin, coins, from_binary,
     testing::{mock_env, mock_info},
-    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Response, StdError, SubMsg, Uint128, WasmMsg,
+    Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Response, StdError, SubMsg, Uint128, WasmMsg,
 };
 use cw_utils::Expiration;
 
 use super::mock_querier::MOCK_KERNEL_CONTRACT;
 
+fn purchase_fee_msg(deps: Deps, action: &str, payee: &str, amount: u128) -> SubMsg {
+    ADOContract::default()
+        .pay_fee_for_amount(
+            deps.storage,
+            &deps.querier,
+            action.to_string(),
+            Addr::unchecked(payee),
+            Some(Uint128::from(amount)),
+        )
+        .unwrap()
+}
+
 const ADDRESS_LIST: &str = "addresslist";
 const RATES: &str = "rates";
 
@@ -803,6 +816,12 @@ fn test_purchase_by_token_id() {
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
     assert_eq!(
         Response::new()
+            .add_submessage(purchase_fee_msg(
+                deps.as_ref(),
+                "PurchaseByTokenId",
+                "sender",
+                100
+            ))
             .add_attribute("action", "purchase")
             .add_attribute("token_id", MOCK_TOKENS_FOR_SALE[0]),
         res
@@ -897,6 +916,7 @@ fn test_multiple_purchases() {
 
     assert_eq!(
         Response::new()
+            .add_submessage(purchase_fee_msg(deps.as_ref(), "Purchase", "sender", 200))
             .add_attribute("action", "purchase")
             .add_attribute("number_of_tokens_wanted", "2")
             .add_attribute("number_of_tokens_purchased", "2"),
@@ -933,6 +953,7 @@ fn test_multiple_purchases() {
                 // Refund sent back as they only were able to mint one.
                 amount: coins(150, "uusd")
             })
+            .add_submessage(purchase_fee_msg(deps.as_ref(), "Purchase", "sender", 100))
             .add_attribute("action", "purchase")
             .add_attribute("number_of_tokens_wanted", "1")
             .add_attribute("number_of_tokens_purchased", "1"),
@@ -977,6 +998,7 @@ fn test_multiple_purchases() {
                 // Refund sent back as they only were able to mint one.
                 amount: coins(150, "uusd")
             })
+            .add_submessage(purchase_fee_msg(deps.as_ref(), "Purchase", "user2", 100))
             .add_attribute("action", "purchase")
             .add_attribute("number_of_tokens_wanted", "2")
             .add_attribute("number_of_tokens_purchased", "1"),
@@ -1052,6 +1074,7 @@ fn test_purchase_more_than_allowed_per_wallet() {
                 to_address: "sender".to_string(),
                 amount: coins(150, "uusd")
             })
+            .add_submessage(purchase_fee_msg(deps.as_ref(), "Purchase", "sender", 300))
             .add_attribute("action", "purchase")
             // Number got truncated to 3 which is the max possible.
             .add_attribute("number_of_tokens_wanted", "3")
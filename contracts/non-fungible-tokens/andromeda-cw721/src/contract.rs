@@ -105,6 +105,7 @@ fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response, Cont
     ensure!(
         is_context_permissioned(
             ctx.deps.storage,
+            &ctx.deps.querier,
             &ctx.info,
             &ctx.env,
             &ctx.amp_ctx,
@@ -192,6 +193,7 @@ fn execute_mint(
         ctx.contains_sender(minter.as_str())
             | is_context_permissioned_strict(
                 ctx.deps.storage,
+                &ctx.deps.querier,
                 &ctx.info,
                 &ctx.env,
                 &ctx.amp_ctx,
@@ -245,6 +247,7 @@ fn execute_batch_mint(
         ctx.contains_sender(minter.as_str())
             | is_context_permissioned_strict(
                 ctx.deps.storage,
+                &ctx.deps.querier,
                 &ctx.info,
                 &ctx.env,
                 &ctx.amp_ctx,
@@ -72,6 +72,10 @@ pub fn execute(
 
     if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(msg, ExecuteMsg::UpdateOwner { .. })
+        && !matches!(msg, ExecuteMsg::ProposeOwner { .. })
+        && !matches!(msg, ExecuteMsg::AcceptOwnership {})
+        && !matches!(msg, ExecuteMsg::CancelOwnershipProposal {})
+        && !matches!(msg, ExecuteMsg::RenounceOwnership {})
     {
         contract.module_hook::<Response>(
             &deps.as_ref(),
@@ -287,6 +291,15 @@ fn execute_buy(
     // Calculate the funds to be received after tax
     let after_tax_payment = purchase_token(&mut deps, &info, token_sale_state.clone())?;
 
+    // Any percentage based fee is charged on the sale price
+    let fee_msg = ADOContract::default().pay_fee_for_amount(
+        deps.storage,
+        &deps.querier,
+        "Buy".to_string(),
+        info.sender.clone(),
+        Some(token_sale_state.price),
+    )?;
+
     Ok(Response::new()
         .add_submessages(after_tax_payment.1)
         // Send funds to the original owner.
@@ -307,7 +320,8 @@ fn execute_buy(
         .add_attribute("token_id", token_id)
         .add_attribute("token_contract", token_sale_state.token_address)
         .add_attribute("recipient", info.sender.to_string())
-        .add_attribute("sale_id", token_sale_state.sale_id))
+        .add_attribute("sale_id", token_sale_state.sale_id)
+        .add_submessage(fee_msg))
 }
 
 fn execute_cancel(
@@ -2,8 +2,8 @@ use andromeda_non_fungible_tokens::marketplace::{
     Cw721HookMsg, ExecuteMsg, InstantiateMsg, Status,
 };
 use andromeda_std::{
-    ado_base::modules::Module, amp::addresses::AndrAddr, common::encode_binary,
-    error::ContractError,
+    ado_base::modules::Module, ado_contract::ADOContract, amp::addresses::AndrAddr,
+    common::encode_binary, error::ContractError,
 };
 use cosmwasm_std::{
     coin, coins,
@@ -390,6 +390,15 @@ fn execute_buy_with_tax_and_royalty_works() {
             .unwrap(),
             funds: vec![],
         })),
+        ADOContract::default()
+            .pay_fee_for_amount(
+                deps.as_ref().storage,
+                &deps.as_ref().querier,
+                "Buy".to_string(),
+                info.sender,
+                Some(Uint128::from(100u128)),
+            )
+            .unwrap(),
     ];
     assert_eq!(res.messages, expected)
 }
@@ -1,13 +1,15 @@
 use crate::state::{
-    read_code_id, read_latest_code_id, store_code_id, ACTION_FEES, ADO_TYPE, CODE_ID,
-    LATEST_VERSION, PUBLISHER,
+    read_code_id, read_latest_code_id, read_version_status, resolve_version, store_code_id,
+    ACTION_FEES, ADO_TYPE, CODE_ID, LATEST_VERSION, PUBLISHER, VERSION_METADATA, VERSION_STATUS,
 };
 use andromeda_std::ado_base::InstantiateMsg as BaseInstantiateMsg;
 use andromeda_std::ado_contract::ADOContract;
 use andromeda_std::common::encode_binary;
 use andromeda_std::error::{from_semver, ContractError};
 use andromeda_std::os::adodb::{
-    ADOMetadata, ADOVersion, ActionFee, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ADOMetadata, ADOVersion, ADOVersionInfoResponse, ADOVersionMetadata, ADOVersionResponse,
+    ADOVersionStatus, ActionFee, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ResolvedVersionResponse, VerifyADOResponse,
 };
 use cosmwasm_std::{
     attr, ensure, entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
@@ -69,6 +71,7 @@ pub fn execute(
             action_fees,
             version,
             publisher,
+            metadata,
         } => publish(
             deps,
             env,
@@ -78,6 +81,7 @@ pub fn execute(
             version,
             action_fees,
             publisher,
+            metadata,
         ),
         ExecuteMsg::UpdateActionFees {
             action_fees,
@@ -92,6 +96,26 @@ pub fn execute(
             ado_type,
             publisher,
         } => execute_update_publisher(deps, info, &ADOVersion::from_string(ado_type), publisher),
+        ExecuteMsg::Deprecate {
+            ado_type,
+            version,
+            reason,
+        } => execute_update_version_status(
+            deps,
+            info,
+            &ADOVersion::from_type(ado_type).with_version(version),
+            ADOVersionStatus::Deprecated { reason },
+        ),
+        ExecuteMsg::Yank {
+            ado_type,
+            version,
+            reason,
+        } => execute_update_version_status(
+            deps,
+            info,
+            &ADOVersion::from_type(ado_type).with_version(version),
+            ADOVersionStatus::Yanked { reason },
+        ),
     }
 }
 
@@ -101,6 +125,7 @@ pub fn update_action_fees(
     fees: Vec<ActionFee>,
 ) -> Result<(), ContractError> {
     for action_fee in fees {
+        action_fee.validate()?;
         ACTION_FEES.save(
             storage,
             &(ado_version.clone().into_string(), action_fee.clone().action),
@@ -121,6 +146,7 @@ pub fn publish(
     version: String,
     action_fees: Option<Vec<ActionFee>>,
     publisher: Option<String>,
+    metadata: Option<ADOVersionMetadata>,
 ) -> Result<Response, ContractError> {
     ensure!(
         ADOContract::default().is_owner_or_operator(deps.storage, info.sender.as_str())?,
@@ -145,11 +171,13 @@ pub fn publish(
         version.validate(),
         ContractError::InvalidADOVersion { msg: None }
     );
+    if let Some(metadata) = &metadata {
+        metadata.validate()?;
+    }
 
-    // Ensure version is not already published
-    let curr_code_id = read_code_id(deps.storage, &version);
+    // Ensure version is not already published, including versions that have since been yanked
     ensure!(
-        curr_code_id.is_err(),
+        !CODE_ID.has(deps.storage, version.as_str()),
         ContractError::InvalidADOVersion {
             msg: Some(String::from("Version already published"))
         }
@@ -166,6 +194,10 @@ pub fn publish(
         update_action_fees(deps.storage, &version, fees)?;
     }
 
+    if let Some(metadata) = metadata {
+        VERSION_METADATA.save(deps.storage, version.as_str(), &metadata)?;
+    }
+
     Ok(Response::default().add_attributes(vec![
         attr("action", "publish_ado"),
         attr("ado_type", version.into_string()),
@@ -262,6 +294,49 @@ fn execute_update_publisher(
     ]))
 }
 
+fn execute_update_version_status(
+    deps: DepsMut,
+    info: MessageInfo,
+    ado_version: &ADOVersion,
+    status: ADOVersionStatus,
+) -> Result<Response, ContractError> {
+    ensure!(
+        CODE_ID.has(deps.storage, ado_version.as_str()),
+        ContractError::InvalidADOVersion {
+            msg: Some("ADO version does not exist".to_string())
+        }
+    );
+    let publisher = PUBLISHER.may_load(deps.storage, ado_version.as_str())?;
+    ensure!(
+        publisher == Some(info.sender.to_string())
+            || ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        !read_version_status(deps.storage, ado_version)?.is_yanked(),
+        ContractError::InvalidADOVersion {
+            msg: Some("ADO version has already been yanked".to_string())
+        }
+    );
+
+    VERSION_STATUS.save(deps.storage, ado_version.as_str(), &status)?;
+
+    let (action, reason) = match status {
+        ADOVersionStatus::Yanked { reason } => ("yank_ado", reason),
+        ADOVersionStatus::Deprecated { reason } => ("deprecate_ado", reason),
+        ADOVersionStatus::Active => ("activate_ado", None),
+    };
+    let mut res = Response::default().add_attributes(vec![
+        attr("action", action),
+        attr("ado_type", ado_version.clone().into_string()),
+    ]);
+    if let Some(reason) = reason {
+        res = res.add_attribute("reason", reason);
+    }
+
+    Ok(res)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // New version
@@ -300,6 +375,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::CodeId { key } => encode_binary(&query_code_id(deps, key)?),
+        QueryMsg::ResolveVersion { key } => encode_binary(&query_resolve_version(deps, key)?),
         QueryMsg::ADOType { code_id } => encode_binary(&query_ado_type(deps, code_id)?),
         QueryMsg::AllADOTypes { start_after, limit } => {
             encode_binary(&query_all_ado_types(deps.storage, start_after, limit)?)
@@ -314,7 +390,19 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             start_after,
             limit,
         )?),
+        QueryMsg::ADOVersionsWithStatus {
+            ado_type,
+            start_after,
+            limit,
+        } => encode_binary(&query_ado_versions_with_status(
+            deps.storage,
+            &ado_type,
+            start_after,
+            limit,
+        )?),
         QueryMsg::ADOMetadata { ado_type } => encode_binary(&query_ado_metadata(deps, ado_type)?),
+        QueryMsg::ADOVersionInfo { key } => encode_binary(&query_ado_version_info(deps, key)?),
+        QueryMsg::VerifyADO { address } => encode_binary(&query_verify_ado(deps, address)?),
         QueryMsg::ActionFee { ado_type, action } => {
             encode_binary(&query_action_fee(deps, ado_type, action)?)
         }
@@ -329,6 +417,24 @@ fn query_code_id(deps: Deps, key: String) -> Result<u64, ContractError> {
     Ok(code_id)
 }
 
+fn query_resolve_version(
+    deps: Deps,
+    key: String,
+) -> Result<ResolvedVersionResponse, ContractError> {
+    let ado_version = ADOVersion::from_string(key);
+    let (version, code_id) = resolve_version(deps.storage, &ado_version)?;
+    let status = read_version_status(
+        deps.storage,
+        &ADOVersion::from_type(ado_version.get_type()).with_version(version.as_str()),
+    )?;
+    Ok(ResolvedVersionResponse {
+        ado_type: ado_version.get_type(),
+        version,
+        code_id,
+        status,
+    })
+}
+
 fn query_ado_type(deps: Deps, code_id: u64) -> Result<Option<String>, ContractError> {
     let ado_version = ADO_TYPE.may_load(deps.storage, code_id)?;
     Ok(ado_version)
@@ -379,6 +485,21 @@ pub fn query_ado_versions(
     Ok(versions)
 }
 
+pub fn query_ado_versions_with_status(
+    storage: &dyn Storage,
+    ado_type: &str,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ADOVersionResponse>, ContractError> {
+    query_ado_versions(storage, ado_type, start_after, limit)?
+        .into_iter()
+        .map(|version| {
+            let status = read_version_status(storage, &ADOVersion::from_string(version.as_str()))?;
+            Ok(ADOVersionResponse { version, status })
+        })
+        .collect()
+}
+
 fn query_ado_metadata(deps: Deps, ado_type: String) -> Result<ADOMetadata, ContractError> {
     let ado_version = ADOVersion::from_string(ado_type);
     let publisher = PUBLISHER.load(deps.storage, ado_version.as_str())?;
@@ -390,6 +511,46 @@ fn query_ado_metadata(deps: Deps, ado_type: String) -> Result<ADOMetadata, Contr
     })
 }
 
+fn query_ado_version_info(
+    deps: Deps,
+    key: String,
+) -> Result<ADOVersionInfoResponse, ContractError> {
+    let ado_type = ADOVersion::from_string(key.as_str()).get_type();
+    let (version, code_id) = resolve_version(deps.storage, &ADOVersion::from_string(key))?;
+    let ado_version = ADOVersion::from_type(ado_type.as_str()).with_version(version.as_str());
+
+    Ok(ADOVersionInfoResponse {
+        ado_type,
+        version,
+        code_id,
+        publisher: PUBLISHER.load(deps.storage, ado_version.as_str())?,
+        status: read_version_status(deps.storage, &ado_version)?,
+        metadata: VERSION_METADATA
+            .may_load(deps.storage, ado_version.as_str())?
+            .unwrap_or_default(),
+    })
+}
+
+fn query_verify_ado(deps: Deps, address: String) -> Result<VerifyADOResponse, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let code_id = deps.querier.query_wasm_contract_info(&address)?.code_id;
+    let ado_version =
+        ADO_TYPE
+            .may_load(deps.storage, code_id)?
+            .ok_or(ContractError::UnpublishedADO {
+                address: address.to_string(),
+            })?;
+    let ado_version = ADOVersion::from_string(ado_version);
+
+    Ok(VerifyADOResponse {
+        code_id,
+        ado_type: ado_version.get_type(),
+        version: ado_version.get_version(),
+        publisher: PUBLISHER.load(deps.storage, ado_version.as_str())?,
+        status: read_version_status(deps.storage, &ado_version)?,
+    })
+}
+
 fn query_action_fee(
     deps: Deps,
     ado_type: String,
@@ -33,6 +33,7 @@ pub fn mock_publish(
         version: version.into(),
         publisher,
         action_fees,
+        metadata: None,
     }
 }
 
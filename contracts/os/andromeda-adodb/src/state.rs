@@ -1,9 +1,10 @@
 use andromeda_std::{
     error::ContractError,
-    os::adodb::{ADOVersion, ActionFee},
+    os::adodb::{ADOVersion, ADOVersionMetadata, ADOVersionStatus, ActionFee},
 };
-use cosmwasm_std::{ensure, StdResult, Storage};
-use cw_storage_plus::Map;
+use cosmwasm_std::{ensure, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use semver::{Version, VersionReq};
 
 /// Stores a mapping from an ADO type/version to its code ID
 pub const CODE_ID: Map<&str, u64> = Map::new("code_id");
@@ -15,6 +16,10 @@ pub const ADO_TYPE: Map<u64, String> = Map::new("ado_type");
 pub const PUBLISHER: Map<&str, String> = Map::new("publisher");
 /// Stores a mapping from an (ADO,Action) to its action fees
 pub const ACTION_FEES: Map<&(String, String), ActionFee> = Map::new("action_fees");
+/// Stores the metadata provided when publishing an ADO version
+pub const VERSION_METADATA: Map<&str, ADOVersionMetadata> = Map::new("version_metadata");
+/// Stores the status of a published ADO version, versions without an entry are active
+pub const VERSION_STATUS: Map<&str, ADOVersionStatus> = Map::new("version_status");
 
 pub fn store_code_id(
     storage: &mut dyn Storage,
@@ -53,17 +58,99 @@ pub fn store_code_id(
     Ok(())
 }
 
+/// Reads the code ID of an ADO version, refusing yanked versions
 pub fn read_code_id(storage: &dyn Storage, ado_version: &ADOVersion) -> StdResult<u64> {
-    if ado_version.get_version() == "latest" {
-        let (_version, code_id) = read_latest_code_id(storage, ado_version.get_type())?;
-        Ok(code_id)
-    } else {
-        CODE_ID.load(storage, ado_version.as_str())
+    let (version, code_id) = resolve_version(storage, ado_version)?;
+    let resolved = ADOVersion::from_type(ado_version.get_type()).with_version(version.as_str());
+    if read_version_status(storage, &resolved)?.is_yanked() {
+        return Err(StdError::generic_err(format!(
+            "ADO version {} has been yanked",
+            resolved.as_str()
+        )));
     }
+    Ok(code_id)
 }
 
+/// Resolves an ADO version to a published version and its code ID
+///
+/// The version may be `latest`, an exact version or a semver requirement such as `^1.2` or `~2.0`,
+/// in which case the newest published version matching the requirement is used. Yanked versions are
+/// never resolved from `latest` or a requirement, an exact yanked version is still resolved so that
+/// callers can check its status and refuse it.
+pub fn resolve_version(
+    storage: &dyn Storage,
+    ado_version: &ADOVersion,
+) -> StdResult<(String, u64)> {
+    let version = ado_version.get_version();
+    let ado_type = ado_version.get_type();
+    if version == "latest" {
+        return read_latest_code_id(storage, ado_type);
+    }
+    // Exact versions are looked up directly, as are any versions that are not valid requirements
+    let requirement = match VersionReq::parse(&version) {
+        Ok(requirement) if Version::parse(&version).is_err() => requirement,
+        _ => return Ok((version, CODE_ID.load(storage, ado_version.as_str())?)),
+    };
+
+    newest_version(storage, &ado_type, |published| {
+        requirement.matches(published)
+    })?
+    .ok_or_else(|| StdError::not_found(format!("ADO version matching {}", ado_version.as_str())))
+}
+
+/// Finds the newest published version of an ADO type that has not been yanked and satisfies `filter`
+fn newest_version(
+    storage: &dyn Storage,
+    ado_type: &str,
+    filter: impl Fn(&Version) -> bool,
+) -> StdResult<Option<(String, u64)>> {
+    // All versions have @ as starting point, we can add A which has higher ascii than @ to get the end of the range
+    let start = format!("{ado_type}@");
+    let end = format!("{ado_type}A");
+    let mut newest: Option<(Version, u64)> = None;
+    for item in CODE_ID.range(
+        storage,
+        Some(Bound::inclusive(start.as_str())),
+        Some(Bound::exclusive(end.as_str())),
+        Order::Ascending,
+    ) {
+        let (key, code_id) = item?;
+        let ado_version = ADOVersion::from_string(key);
+        let Ok(published) = Version::parse(&ado_version.get_version()) else {
+            continue;
+        };
+        if filter(&published)
+            && newest
+                .as_ref()
+                .map_or(true, |(newest, _)| published > *newest)
+            && !read_version_status(storage, &ado_version)?.is_yanked()
+        {
+            newest = Some((published, code_id));
+        }
+    }
+
+    Ok(newest.map(|(version, code_id)| (version.to_string(), code_id)))
+}
+
+/// Reads the status of a published version, versions without a stored status are active
+pub fn read_version_status(
+    storage: &dyn Storage,
+    ado_version: &ADOVersion,
+) -> StdResult<ADOVersionStatus> {
+    Ok(VERSION_STATUS
+        .may_load(storage, ado_version.as_str())?
+        .unwrap_or_default())
+}
+
+/// Reads the latest published version of an ADO type and its code ID, skipping yanked versions
 pub fn read_latest_code_id(storage: &dyn Storage, ado_type: String) -> StdResult<(String, u64)> {
-    LATEST_VERSION.load(storage, &ado_type)
+    let (version, code_id) = LATEST_VERSION.load(storage, &ado_type)?;
+    let latest = ADOVersion::from_type(ado_type.as_str()).with_version(version.as_str());
+    if !read_version_status(storage, &latest)?.is_yanked() {
+        return Ok((version, code_id));
+    }
+    newest_version(storage, &ado_type, |_| true)?
+        .ok_or_else(|| StdError::not_found(format!("ADO version matching {ado_type}@latest")))
 }
 
 // pub fn read_all_ado_types(storage: &dyn Storage) -> StdResult<Vec<String>> {
@@ -1,12 +1,18 @@
 #[cfg(test)]
-use andromeda_std::testing::mock_querier::{mock_dependencies_custom, MOCK_KERNEL_CONTRACT};
+use andromeda_std::testing::mock_querier::{
+    mock_dependencies_custom, INVALID_CONTRACT, MOCK_KERNEL_CONTRACT,
+};
 use cosmwasm_std::{from_binary, Uint128};
 
 use crate::contract::{execute, instantiate, query};
 use crate::state::{ACTION_FEES, CODE_ID, LATEST_VERSION, PUBLISHER};
 
 use andromeda_std::error::ContractError;
-use andromeda_std::os::adodb::{ADOVersion, ActionFee, ExecuteMsg, InstantiateMsg, QueryMsg};
+use andromeda_std::os::adodb::{
+    ADOAudit, ADOMetadata, ADOVersion, ADOVersionInfoResponse, ADOVersionMetadata,
+    ADOVersionResponse, ADOVersionStatus, ActionFee, ExecuteMsg, InstantiateMsg, QueryMsg,
+    ResolvedVersionResponse, VerifyADOResponse,
+};
 
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
@@ -48,12 +54,14 @@ fn test_publish() {
             amount: Uint128::from(1u128),
             asset: "somecw20token".to_string(),
             receiver: None,
+            percentage: None,
         },
         ActionFee {
             action: "action2".to_string(),
             amount: Uint128::from(2u128),
             asset: "uusd".to_string(),
             receiver: None,
+            percentage: None,
         },
     ];
 
@@ -65,6 +73,7 @@ fn test_publish() {
         code_id,
         action_fees: Some(action_fees.clone()),
         publisher: Some(owner.clone()),
+        metadata: None,
     };
 
     let resp = execute(deps.as_mut(), env.clone(), info, msg.clone());
@@ -129,12 +138,14 @@ fn test_update_action_fees() {
             amount: Uint128::from(1u128),
             asset: "somecw20token".to_string(),
             receiver: None,
+            percentage: None,
         },
         ActionFee {
             action: "action2".to_string(),
             amount: Uint128::from(2u128),
             asset: "uusd".to_string(),
             receiver: None,
+            percentage: None,
         },
     ];
 
@@ -314,6 +325,7 @@ fn test_get_code_id() {
         code_id,
         action_fees: None,
         publisher: Some(owner),
+        metadata: None,
     };
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
@@ -339,6 +351,441 @@ fn test_get_code_id() {
     assert_eq!(value, code_id);
 }
 
+#[test]
+fn test_resolve_version_range() {
+    let owner = String::from("owner");
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info(owner.as_str(), &[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&owner, &[]),
+        InstantiateMsg {
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            owner: None,
+        },
+    )
+    .unwrap();
+
+    let ados = vec![
+        ("splitter@1.1.0", 1),
+        ("splitter@1.2.0", 2),
+        ("splitter@1.10.1", 3),
+        ("splitter@2.0.3", 4),
+        ("splitter@2.1.0", 5),
+        ("splitters@1.5.0", 6),
+    ];
+    for (ado, code_id) in ados {
+        let ado_version = ADOVersion::from_string(ado);
+        let msg = ExecuteMsg::Publish {
+            ado_type: ado_version.get_type(),
+            version: ado_version.get_version(),
+            code_id,
+            action_fees: None,
+            publisher: Some(owner.clone()),
+            metadata: None,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    }
+
+    let cases = vec![
+        ("splitter@^1.2", "1.10.1", 3),
+        ("splitter@~2.0", "2.0.3", 4),
+        ("splitter@1.2.0", "1.2.0", 2),
+        ("splitter@>=1.1.0, <1.2.0", "1.1.0", 1),
+        ("splitter@latest", "2.1.0", 5),
+        ("splitter", "2.1.0", 5),
+    ];
+    for (key, version, code_id) in cases {
+        let query_msg = QueryMsg::ResolveVersion {
+            key: key.to_string(),
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let value: ResolvedVersionResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            value,
+            ResolvedVersionResponse {
+                ado_type: "splitter".to_string(),
+                version: version.to_string(),
+                code_id,
+                status: ADOVersionStatus::Active,
+            },
+            "{key}"
+        );
+
+        let query_msg = QueryMsg::CodeId {
+            key: key.to_string(),
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let value: u64 = from_binary(&res).unwrap();
+        assert_eq!(value, code_id, "{key}");
+    }
+
+    let query_msg = QueryMsg::CodeId {
+        key: "splitter@^3".to_string(),
+    };
+    assert!(query(deps.as_ref(), env, query_msg).is_err());
+}
+
+#[test]
+fn test_deprecate_and_yank() {
+    let owner = String::from("owner");
+    let publisher = String::from("publisher");
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&owner, &[]),
+        InstantiateMsg {
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            owner: None,
+        },
+    )
+    .unwrap();
+
+    for (version, code_id) in [("1.0.0", 1), ("1.1.0", 2), ("1.2.0", 3)] {
+        let msg = ExecuteMsg::Publish {
+            ado_type: "splitter".to_string(),
+            version: version.to_string(),
+            code_id,
+            action_fees: None,
+            publisher: Some(publisher.clone()),
+            metadata: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), msg).unwrap();
+    }
+
+    // Only the publisher or the owner may change the status of a version
+    let msg = ExecuteMsg::Yank {
+        ado_type: "splitter".to_string(),
+        version: "1.2.0".to_string(),
+        reason: Some("Incorrect splitting".to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("attacker", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(&publisher, &[]), msg).unwrap();
+    assert_eq!(res.attributes[0].value, "yank_ado");
+
+    let msg = ExecuteMsg::Deprecate {
+        ado_type: "splitter".to_string(),
+        version: "1.1.0".to_string(),
+        reason: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), msg).unwrap();
+
+    // Yanked versions cannot be deprecated
+    let msg = ExecuteMsg::Deprecate {
+        ado_type: "splitter".to_string(),
+        version: "1.2.0".to_string(),
+        reason: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidADOVersion {
+            msg: Some("ADO version has already been yanked".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::Yank {
+        ado_type: "splitter".to_string(),
+        version: "2.0.0".to_string(),
+        reason: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidADOVersion {
+            msg: Some("ADO version does not exist".to_string())
+        }
+    );
+
+    // The latest version and ranges skip yanked versions
+    for key in ["splitter", "splitter@latest", "splitter@^1"] {
+        let query_msg = QueryMsg::ResolveVersion {
+            key: key.to_string(),
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let value: ResolvedVersionResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            value,
+            ResolvedVersionResponse {
+                ado_type: "splitter".to_string(),
+                version: "1.1.0".to_string(),
+                code_id: 2,
+                status: ADOVersionStatus::Deprecated { reason: None },
+            },
+            "{key}"
+        );
+    }
+
+    // Yanked versions requested exactly are resolved along with their status so that callers can refuse them
+    let query_msg = QueryMsg::ResolveVersion {
+        key: "splitter@1.2.0".to_string(),
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let value: ResolvedVersionResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        ResolvedVersionResponse {
+            ado_type: "splitter".to_string(),
+            version: "1.2.0".to_string(),
+            code_id: 3,
+            status: ADOVersionStatus::Yanked {
+                reason: Some("Incorrect splitting".to_string())
+            },
+        }
+    );
+
+    // The code ID of a yanked version is not provided
+    let query_msg = QueryMsg::CodeId {
+        key: "splitter@1.2.0".to_string(),
+    };
+    query(deps.as_ref(), env.clone(), query_msg).unwrap_err();
+
+    // The latest version reported in the metadata skips yanked versions
+    let query_msg = QueryMsg::ADOMetadata {
+        ado_type: "splitter@1.1.0".to_string(),
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let value: ADOMetadata = from_binary(&res).unwrap();
+    assert_eq!(value.latest_version, "1.1.0");
+
+    // The status of a yanked version can still be checked
+    let query_msg = QueryMsg::ADOVersionInfo {
+        key: "splitter@1.2.0".to_string(),
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let value: ADOVersionInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value.status,
+        ADOVersionStatus::Yanked {
+            reason: Some("Incorrect splitting".to_string())
+        }
+    );
+
+    // Yanked versions cannot be published again
+    let msg = ExecuteMsg::Publish {
+        ado_type: "splitter".to_string(),
+        version: "1.2.0".to_string(),
+        code_id: 4,
+        action_fees: None,
+        publisher: None,
+        metadata: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidADOVersion {
+            msg: Some("Version must be newer than the current version".to_string())
+        }
+    );
+
+    let query_msg = QueryMsg::ADOVersions {
+        ado_type: "splitter".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let value: Vec<String> = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        vec!["splitter@1.2.0", "splitter@1.1.0", "splitter@1.0.0"]
+    );
+
+    let query_msg = QueryMsg::ADOVersionsWithStatus {
+        ado_type: "splitter".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env, query_msg).unwrap();
+    let value: Vec<ADOVersionResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        vec![
+            ADOVersionResponse {
+                version: "splitter@1.2.0".to_string(),
+                status: ADOVersionStatus::Yanked {
+                    reason: Some("Incorrect splitting".to_string())
+                },
+            },
+            ADOVersionResponse {
+                version: "splitter@1.1.0".to_string(),
+                status: ADOVersionStatus::Deprecated { reason: None },
+            },
+            ADOVersionResponse {
+                version: "splitter@1.0.0".to_string(),
+                status: ADOVersionStatus::Active,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_ado_version_info() {
+    let owner = String::from("owner");
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info(owner.as_str(), &[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&owner, &[]),
+        InstantiateMsg {
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            owner: None,
+        },
+    )
+    .unwrap();
+
+    let metadata = ADOVersionMetadata {
+        description: Some("Splits funds between recipients".to_string()),
+        source: Some("https://github.com/andromedaprotocol/andromeda-core".to_string()),
+        schema_hash: Some("a".repeat(64)),
+        audits: vec![ADOAudit {
+            auditor: "auditor".to_string(),
+            report: "https://example.com/report.pdf".to_string(),
+        }],
+    };
+
+    let invalid_metadata = ADOVersionMetadata {
+        schema_hash: Some("not a hash".to_string()),
+        ..metadata.clone()
+    };
+    let msg = ExecuteMsg::Publish {
+        ado_type: "splitter".to_string(),
+        version: "1.0.0".to_string(),
+        code_id: 1,
+        action_fees: None,
+        publisher: None,
+        metadata: Some(invalid_metadata),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidADOVersion {
+            msg: Some("Schema hash must be a hex encoded SHA-256 checksum".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::Publish {
+        ado_type: "splitter".to_string(),
+        version: "1.0.0".to_string(),
+        code_id: 1,
+        action_fees: None,
+        publisher: None,
+        metadata: Some(metadata.clone()),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::Publish {
+        ado_type: "splitter".to_string(),
+        version: "1.1.0".to_string(),
+        code_id: 2,
+        action_fees: None,
+        publisher: None,
+        metadata: None,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let query_msg = QueryMsg::ADOVersionInfo {
+        key: "splitter@~1.0".to_string(),
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let value: ADOVersionInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        ADOVersionInfoResponse {
+            ado_type: "splitter".to_string(),
+            version: "1.0.0".to_string(),
+            code_id: 1,
+            publisher: owner.clone(),
+            status: ADOVersionStatus::Active,
+            metadata,
+        }
+    );
+
+    let query_msg = QueryMsg::ADOVersionInfo {
+        key: "splitter".to_string(),
+    };
+    let res = query(deps.as_ref(), env, query_msg).unwrap();
+    let value: ADOVersionInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(value.version, "1.1.0");
+    assert_eq!(value.code_id, 2);
+    assert_eq!(value.metadata, ADOVersionMetadata::default());
+}
+
+#[test]
+fn test_verify_ado() {
+    let owner = String::from("owner");
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info(owner.as_str(), &[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&owner, &[]),
+        InstantiateMsg {
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            owner: None,
+        },
+    )
+    .unwrap();
+
+    // The mock querier reports code ID 1 for all contracts other than `INVALID_CONTRACT`
+    let msg = ExecuteMsg::Publish {
+        ado_type: "splitter".to_string(),
+        version: "1.0.0".to_string(),
+        code_id: 1,
+        action_fees: None,
+        publisher: Some("publisher".to_string()),
+        metadata: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::Yank {
+        ado_type: "splitter".to_string(),
+        version: "1.0.0".to_string(),
+        reason: None,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let query_msg = QueryMsg::VerifyADO {
+        address: "splitter_contract".to_string(),
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let value: VerifyADOResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        VerifyADOResponse {
+            code_id: 1,
+            ado_type: "splitter".to_string(),
+            version: "1.0.0".to_string(),
+            publisher: "publisher".to_string(),
+            status: ADOVersionStatus::Yanked { reason: None },
+        }
+    );
+
+    let query_msg = QueryMsg::VerifyADO {
+        address: INVALID_CONTRACT.to_string(),
+    };
+    let err = query(deps.as_ref(), env, query_msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnpublishedADO {
+            address: INVALID_CONTRACT.to_string()
+        }
+    );
+}
+
 #[test]
 fn test_all_ado_types() {
     let owner = String::from("owner");
@@ -371,6 +818,7 @@ fn test_all_ado_types() {
             code_id,
             action_fees: None,
             publisher: Some(owner.clone()),
+            metadata: None,
         };
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
         code_id += 1;
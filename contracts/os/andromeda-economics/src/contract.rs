@@ -1,15 +1,23 @@
-use crate::state::BALANCES;
+use crate::state::{
+    receiver_fee_payments, receiver_revenue, record_fee_payment, BALANCES, FEE_EXEMPTIONS,
+    FEE_TIERS, FEE_VOLUME,
+};
 use andromeda_std::ado_base::{AndromedaQuery, InstantiateMsg as BaseInstantiateMsg};
 use andromeda_std::ado_contract::ADOContract;
 use andromeda_std::amp::AndrAddr;
 
 use andromeda_std::error::{from_semver, ContractError};
+use andromeda_std::os::adodb::ActionFee;
 use andromeda_std::os::aos_querier::AOSQuerier;
-use andromeda_std::os::economics::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use andromeda_std::os::economics::{
+    Cw20HookMsg, ExecuteMsg, FeeEstimate, FeePayment, FeeRevenue, FeeTier, FeeTierRequirement,
+    InstantiateMsg, MigrateMsg, QueryMsg, RevenueResponse,
+};
 #[allow(unused_imports)]
 use cosmwasm_std::{
     attr, coin, ensure, entry_point, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg,
-    Deps, DepsMut, Empty, Env, MessageInfo, Response, Storage, SubMsg, Uint128, WasmMsg,
+    Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, Storage, SubMsg, Timestamp, Uint128,
+    WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
@@ -51,7 +59,11 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Deposit { address } => execute_deposit_native(deps, info, address),
-        ExecuteMsg::PayFee { payee, action } => execute_pay_fee(deps, env, info, payee, action),
+        ExecuteMsg::PayFee {
+            payee,
+            action,
+            amount,
+        } => execute_pay_fee(deps, env, info, payee, action, amount),
         ExecuteMsg::Withdraw { amount, asset } => {
             execute_withdraw_native(deps, info, amount, asset)
         }
@@ -59,6 +71,13 @@ pub fn execute(
         ExecuteMsg::WithdrawCW20 { amount, asset } => {
             execute_withdraw_cw20(deps, info, amount, asset)
         }
+        ExecuteMsg::SetFeeTiers { tiers } => execute_set_fee_tiers(deps, info, tiers),
+        ExecuteMsg::AddFeeExemption { address } => {
+            execute_update_fee_exemption(deps, info, address, true)
+        }
+        ExecuteMsg::RemoveFeeExemption { address } => {
+            execute_update_fee_exemption(deps, info, address, false)
+        }
     }
 }
 
@@ -184,6 +203,71 @@ pub(crate) fn spend_balance(
     Ok(remainder)
 }
 
+/// Calculates the fee charged to the payer after any exemption or fee tier discount
+pub(crate) fn estimate_fee(
+    deps: Deps,
+    fee: &ActionFee,
+    payer: &Addr,
+) -> Result<FeeEstimate, ContractError> {
+    if FEE_EXEMPTIONS.has(deps.storage, payer) {
+        return Ok(FeeEstimate {
+            asset: fee.asset.clone(),
+            amount: Uint128::zero(),
+            percentage: None,
+            discount: Decimal::one(),
+            exempt: true,
+        });
+    }
+
+    let discount = fee_discount(deps, payer)?;
+    let remaining = Decimal::one() - discount;
+    Ok(FeeEstimate {
+        asset: fee.asset.clone(),
+        amount: fee.amount * remaining,
+        percentage: fee.percentage.map(|percentage| percentage * remaining),
+        discount,
+        exempt: false,
+    })
+}
+
+/// Gets the largest discount of the fee tiers the payer qualifies for
+fn fee_discount(deps: Deps, payer: &Addr) -> Result<Decimal, ContractError> {
+    let tiers = FEE_TIERS.may_load(deps.storage)?.unwrap_or_default();
+    let mut discount = Decimal::zero();
+    // Only query the payer's delegations if a staking tier requires it
+    let mut staked: Option<Uint128> = None;
+    for tier in tiers {
+        if tier.discount <= discount {
+            continue;
+        }
+        let qualifies = match tier.requirement {
+            FeeTierRequirement::Volume { asset, amount } => {
+                FEE_VOLUME
+                    .may_load(deps.storage, (payer, asset.as_str()))?
+                    .unwrap_or_default()
+                    >= amount
+            }
+            FeeTierRequirement::Staked { amount } => {
+                let payer_staked = match staked {
+                    Some(payer_staked) => payer_staked,
+                    None => deps
+                        .querier
+                        .query_all_delegations(payer)?
+                        .iter()
+                        .map(|delegation| delegation.amount.amount)
+                        .sum(),
+                };
+                staked = Some(payer_staked);
+                payer_staked >= amount
+            }
+        };
+        if qualifies {
+            discount = tier.discount;
+        }
+    }
+    Ok(discount)
+}
+
 /// Charges a fee depending on the sending ADO and the action being performed.
 /// Sender must be an ADO contract else this will error.
 ///
@@ -191,12 +275,15 @@ pub(crate) fn spend_balance(
 /// 1. ADO
 /// 2. App
 /// 3. Payee
+///
+/// The payee's exemptions and fee tiers are applied before charging, any percentage of the fee is charged on `amount`.
 fn execute_pay_fee(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     payee: Addr,
     action: String,
+    amount: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let mut resp = Response::default();
 
@@ -228,12 +315,27 @@ fn execute_pay_fee(
             // No fee
             None => Ok(resp),
             Some(fee) => {
+                let estimate = estimate_fee(deps.as_ref(), &fee, &payee)?;
+                if estimate.exempt {
+                    return Ok(resp.add_attribute("fee_exempt", payee.to_string()));
+                }
+                let fee_amount = estimate.amount
+                    + amount.map_or(Uint128::zero(), |amount| {
+                        amount * estimate.percentage.unwrap_or_default()
+                    });
+                if !estimate.discount.is_zero() {
+                    resp = resp.add_attribute("fee_discount", estimate.discount.to_string());
+                }
+                if fee_amount.is_zero() {
+                    return Ok(resp);
+                }
+
                 let asset_string = fee.asset.to_string();
                 let asset = asset_string.split(':').last().unwrap();
 
                 // Charge ADO first
                 let mut remainder =
-                    spend_balance(deps.storage, &info.sender, asset.to_string(), fee.amount)?;
+                    spend_balance(deps.storage, &info.sender, asset.to_string(), fee_amount)?;
 
                 // Next charge the app
                 if remainder > Uint128::zero() {
@@ -249,8 +351,12 @@ fn execute_pay_fee(
                 }
 
                 // Next charge the payee
+                let mut payee_amount = Uint128::zero();
                 if remainder > Uint128::zero() {
-                    remainder = spend_balance(deps.storage, &payee, asset.to_string(), remainder)?;
+                    let payee_remainder =
+                        spend_balance(deps.storage, &payee, asset.to_string(), remainder)?;
+                    payee_amount = remainder - payee_remainder;
+                    remainder = payee_remainder;
                 }
 
                 // If balance remaining then not enough funds to pay fee
@@ -279,12 +385,35 @@ fn execute_pay_fee(
                 BALANCES.save(
                     deps.storage,
                     (recipient.clone(), asset.to_string()),
-                    &(receiver_balance + fee.amount),
+                    &(receiver_balance + fee_amount),
+                )?;
+
+                // Only the amount paid from the payee's own balance counts towards their volume
+                if !payee_amount.is_zero() {
+                    let volume = FEE_VOLUME
+                        .may_load(deps.storage, (&payee, asset))?
+                        .unwrap_or_default();
+                    FEE_VOLUME.save(deps.storage, (&payee, asset), &(volume + payee_amount))?;
+                }
+
+                let payment_id = record_fee_payment(
+                    deps.storage,
+                    FeePayment {
+                        id: 0,
+                        ado_type,
+                        action,
+                        payee,
+                        receiver: recipient.clone(),
+                        asset: asset.to_string(),
+                        amount: fee_amount,
+                        timestamp: env.block.time,
+                    },
                 )?;
 
                 resp = resp
-                    .add_attribute("paid_fee", format!("{}{}", fee.amount, fee.asset))
-                    .add_attribute("fee_recipient", recipient.to_string());
+                    .add_attribute("paid_fee", format!("{}{}", fee_amount, fee.asset))
+                    .add_attribute("fee_recipient", recipient.to_string())
+                    .add_attribute("fee_payment_id", payment_id.to_string());
                 Ok(resp)
             }
         }
@@ -294,6 +423,56 @@ fn execute_pay_fee(
     }
 }
 
+fn execute_set_fee_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    tiers: Vec<FeeTier>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    for tier in tiers.iter() {
+        ensure!(
+            tier.discount <= Decimal::one(),
+            ContractError::InvalidRate {}
+        );
+    }
+
+    FEE_TIERS.save(deps.storage, &tiers)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "set_fee_tiers"),
+        attr("tiers", tiers.len().to_string()),
+    ]))
+}
+
+fn execute_update_fee_exemption(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: AndrAddr,
+    exempt: bool,
+) -> Result<Response, ContractError> {
+    ensure!(
+        ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    let address = address.get_raw_address(&deps.as_ref())?;
+
+    let action = if exempt {
+        FEE_EXEMPTIONS.save(deps.storage, &address, &true)?;
+        "add_fee_exemption"
+    } else {
+        FEE_EXEMPTIONS.remove(deps.storage, &address);
+        "remove_fee_exemption"
+    };
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", action),
+        attr("address", address.to_string()),
+    ]))
+}
+
 fn execute_withdraw_native(
     deps: DepsMut,
     info: MessageInfo,
@@ -429,12 +608,169 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::Balance { address, asset } => {
-            Ok(to_binary(&query_balance(_deps, address, asset)?)?)
+            Ok(to_binary(&query_balance(deps, address, asset)?)?)
+        }
+        QueryMsg::EstimateFee {
+            ado_type,
+            action,
+            payer,
+        } => Ok(to_binary(&query_estimate_fee(
+            deps, ado_type, action, payer,
+        )?)?),
+        QueryMsg::FeeTiers {} => Ok(to_binary(
+            &FEE_TIERS.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::FeeExempt { address } => {
+            let addr = address.get_raw_address(&deps)?;
+            Ok(to_binary(&FEE_EXEMPTIONS.has(deps.storage, &addr))?)
+        }
+        QueryMsg::FeePayments {
+            receiver,
+            ado_type,
+            action,
+            start_time,
+            end_time,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_fee_payments(
+            deps,
+            receiver,
+            ado_type,
+            action,
+            start_time,
+            end_time,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::Revenue {
+            receiver,
+            start_time,
+            end_time,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_revenue(
+            deps,
+            receiver,
+            start_time,
+            end_time,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::TotalRevenue {
+            receiver,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_total_revenue(
+            deps,
+            receiver,
+            start_after,
+            limit,
+        )?)?),
+    }
+}
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[allow(clippy::too_many_arguments)]
+fn query_fee_payments(
+    deps: Deps,
+    receiver: AndrAddr,
+    ado_type: Option<String>,
+    action: Option<String>,
+    start_time: Option<Timestamp>,
+    end_time: Option<Timestamp>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<FeePayment>, ContractError> {
+    let receiver = receiver.get_raw_address(&deps)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let payments: Vec<FeePayment> = receiver_fee_payments(
+        deps.storage,
+        &receiver,
+        ado_type.as_deref(),
+        action.as_deref(),
+        start_time,
+        end_time,
+        start_after,
+    )?
+    .take(limit)
+    .collect::<Result<_, _>>()?;
+    Ok(payments)
+}
+
+fn query_revenue(
+    deps: Deps,
+    receiver: AndrAddr,
+    start_time: Option<Timestamp>,
+    end_time: Option<Timestamp>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<RevenueResponse, ContractError> {
+    let receiver = receiver.get_raw_address(&deps)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let mut revenue: Vec<FeeRevenue> = vec![];
+    let mut last_payment = None;
+    for payment in receiver_fee_payments(
+        deps.storage,
+        &receiver,
+        None,
+        None,
+        start_time,
+        end_time,
+        start_after,
+    )?
+    .take(limit)
+    {
+        let payment = payment?;
+        last_payment = Some(payment.id);
+        match revenue.iter_mut().find(|revenue| {
+            revenue.ado_type == payment.ado_type
+                && revenue.action == payment.action
+                && revenue.asset == payment.asset
+        }) {
+            Some(revenue) => revenue.amount += payment.amount,
+            None => revenue.push(FeeRevenue {
+                ado_type: payment.ado_type,
+                action: payment.action,
+                asset: payment.asset,
+                amount: payment.amount,
+            }),
         }
     }
+
+    Ok(RevenueResponse {
+        revenue,
+        last_payment,
+    })
+}
+
+fn query_total_revenue(
+    deps: Deps,
+    receiver: AndrAddr,
+    start_after: Option<(String, String, String)>,
+    limit: Option<u32>,
+) -> Result<Vec<FeeRevenue>, ContractError> {
+    let receiver = receiver.get_raw_address(&deps)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    receiver_revenue(deps.storage, &receiver, start_after, limit)
+}
+
+fn query_estimate_fee(
+    deps: Deps,
+    ado_type: String,
+    action: String,
+    payer: AndrAddr,
+) -> Result<Option<FeeEstimate>, ContractError> {
+    let payer = payer.get_raw_address(&deps)?;
+    let adodb_addr = ADOContract::default().get_adodb_address(deps.storage, &deps.querier)?;
+    let fee = AOSQuerier::action_fee_getter(&deps.querier, &adodb_addr, &ado_type, &action)?;
+    fee.map(|fee| estimate_fee(deps, &fee, &payer)).transpose()
 }
 
 fn query_balance(deps: Deps, address: AndrAddr, asset: String) -> Result<Uint128, ContractError> {
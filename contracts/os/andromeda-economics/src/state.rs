@@ -1,5 +1,149 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::Map;
+use andromeda_std::{
+    error::ContractError,
+    os::economics::{FeePayment, FeeRevenue, FeeTier},
+};
+use cosmwasm_std::{ensure, Addr, Empty, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Item, Map};
 
 /// Contains all balances for an address
 pub const BALANCES: Map<(Addr, String), Uint128> = Map::new("balances");
+/// The tiers used to discount fees
+pub const FEE_TIERS: Item<Vec<FeeTier>> = Item::new("fee_tiers");
+/// Addresses that do not pay fees
+pub const FEE_EXEMPTIONS: Map<&Addr, bool> = Map::new("fee_exemptions");
+/// The total amount of fees paid from a payee's own balance per asset, used for volume based fee tiers
+pub const FEE_VOLUME: Map<(&Addr, &str), Uint128> = Map::new("fee_volume");
+/// Ledger of all fee payments by ID
+pub const FEE_PAYMENTS: Map<u64, FeePayment> = Map::new("fee_payments");
+pub const FEE_PAYMENT_COUNT: Item<u64> = Item::new("fee_payment_count");
+/// Fee payments by receiver, payment time in nanoseconds and ID
+pub const RECEIVER_FEE_PAYMENTS: Map<(&Addr, u64, u64), Empty> = Map::new("receiver_fee_payments");
+/// Fee payments by (receiver, ADO type), payment time in nanoseconds and ID
+pub const RECEIVER_TYPE_FEE_PAYMENTS: Map<((&Addr, &str), u64, u64), Empty> =
+    Map::new("receiver_type_fee_payments");
+/// The (receiver, ADO type, action) a fee payment is indexed by
+type ReceiverAction<'a> = (&'a Addr, &'a str, &'a str);
+/// Fee payments by (receiver, ADO type, action), payment time in nanoseconds and ID
+pub const RECEIVER_ACTION_FEE_PAYMENTS: Map<(ReceiverAction, u64, u64), Empty> =
+    Map::new("receiver_action_fee_payments");
+/// The total fees paid to a receiver per (ADO type, action, asset)
+pub const RECEIVER_REVENUE: Map<(&Addr, (&str, &str, &str)), Uint128> =
+    Map::new("receiver_revenue");
+
+/// Adds a payment to the fee ledger, assigning it the next payment ID
+pub fn record_fee_payment(
+    storage: &mut dyn Storage,
+    mut payment: FeePayment,
+) -> Result<u64, ContractError> {
+    let id = FEE_PAYMENT_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    payment.id = id;
+    let time = payment.timestamp.nanos();
+    RECEIVER_FEE_PAYMENTS.save(storage, (&payment.receiver, time, id), &Empty {})?;
+    RECEIVER_TYPE_FEE_PAYMENTS.save(
+        storage,
+        ((&payment.receiver, &payment.ado_type), time, id),
+        &Empty {},
+    )?;
+    RECEIVER_ACTION_FEE_PAYMENTS.save(
+        storage,
+        (
+            (&payment.receiver, &payment.ado_type, &payment.action),
+            time,
+            id,
+        ),
+        &Empty {},
+    )?;
+    RECEIVER_REVENUE.update(
+        storage,
+        (
+            &payment.receiver,
+            (&payment.ado_type, &payment.action, &payment.asset),
+        ),
+        |total| -> Result<_, ContractError> {
+            Ok(total.unwrap_or_default().checked_add(payment.amount)?)
+        },
+    )?;
+    FEE_PAYMENTS.save(storage, id, &payment)?;
+    FEE_PAYMENT_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+/// Iterates the fee payments for a receiver within a time range, ordered by payment time
+///
+/// Payments can be limited to an ADO type, or an ADO type and action, in which case only the matching payments are read.
+/// `start_time` is inclusive and `end_time` is exclusive, `start_after` is the ID of one of the receiver's payments to continue from
+pub fn receiver_fee_payments<'a>(
+    storage: &'a dyn Storage,
+    receiver: &'a Addr,
+    ado_type: Option<&'a str>,
+    action: Option<&'a str>,
+    start_time: Option<Timestamp>,
+    end_time: Option<Timestamp>,
+    start_after: Option<u64>,
+) -> Result<Box<dyn Iterator<Item = Result<FeePayment, ContractError>> + 'a>, ContractError> {
+    let min = match start_after {
+        Some(id) => {
+            let payment = FEE_PAYMENTS.load(storage, id)?;
+            ensure!(
+                payment.receiver == *receiver,
+                ContractError::InvalidQuery {}
+            );
+            Some(Bound::exclusive((payment.timestamp.nanos(), id)))
+        }
+        None => start_time.map(|time| Bound::inclusive((time.nanos(), 0))),
+    };
+    let max = end_time.map(|time| Bound::exclusive((time.nanos(), 0)));
+
+    let keys: Box<dyn Iterator<Item = StdResult<(u64, u64)>>> = match (ado_type, action) {
+        (None, None) => Box::new(RECEIVER_FEE_PAYMENTS.sub_prefix(receiver).keys(
+            storage,
+            min,
+            max,
+            Order::Ascending,
+        )),
+        (Some(ado_type), None) => Box::new(
+            RECEIVER_TYPE_FEE_PAYMENTS
+                .sub_prefix((receiver, ado_type))
+                .keys(storage, min, max, Order::Ascending),
+        ),
+        (Some(ado_type), Some(action)) => Box::new(
+            RECEIVER_ACTION_FEE_PAYMENTS
+                .sub_prefix((receiver, ado_type, action))
+                .keys(storage, min, max, Order::Ascending),
+        ),
+        // Actions are only indexed per ADO type
+        (None, Some(_)) => return Err(ContractError::InvalidQuery {}),
+    };
+
+    Ok(Box::new(keys.map(move |key| {
+        let (_time, id) = key?;
+        Ok(FEE_PAYMENTS.load(storage, id)?)
+    })))
+}
+
+/// Reads the total fees paid to a receiver per (ADO type, action, asset)
+pub fn receiver_revenue(
+    storage: &dyn Storage,
+    receiver: &Addr,
+    start_after: Option<(String, String, String)>,
+    limit: usize,
+) -> Result<Vec<FeeRevenue>, ContractError> {
+    let min = start_after.as_ref().map(|(ado_type, action, asset)| {
+        Bound::exclusive((ado_type.as_str(), action.as_str(), asset.as_str()))
+    });
+
+    RECEIVER_REVENUE
+        .prefix(receiver)
+        .range(storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let ((ado_type, action, asset), amount) = item?;
+            Ok(FeeRevenue {
+                ado_type,
+                action,
+                asset,
+                amount,
+            })
+        })
+        .collect()
+}
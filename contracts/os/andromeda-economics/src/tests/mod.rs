@@ -2,16 +2,21 @@ use andromeda_std::amp::AndrAddr;
 use andromeda_std::error::ContractError;
 #[cfg(test)]
 use andromeda_std::testing::mock_querier::{
-    mock_dependencies_custom, MOCK_ACTION, MOCK_KERNEL_CONTRACT,
+    mock_dependencies_custom, MOCK_ACTION, MOCK_KERNEL_CONTRACT, MOCK_PERCENTAGE_ACTION,
 };
 use andromeda_std::testing::mock_querier::{MOCK_ADO_PUBLISHER, MOCK_APP_CONTRACT};
-use cosmwasm_std::{coin, to_binary, Addr, BankMsg, CosmosMsg, Uint128};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, FullDelegation, Uint128,
+};
 use cw20::Cw20ReceiveMsg;
 
-use crate::contract::{cw20_withdraw_msg, execute, instantiate, spend_balance};
-use crate::state::BALANCES;
+use crate::contract::{cw20_withdraw_msg, execute, instantiate, query, spend_balance};
+use crate::state::{BALANCES, FEE_VOLUME};
 
-use andromeda_std::os::economics::{Cw20HookMsg, ExecuteMsg, InstantiateMsg};
+use andromeda_std::os::economics::{
+    Cw20HookMsg, ExecuteMsg, FeeEstimate, FeePayment, FeeRevenue, FeeTier, FeeTierRequirement,
+    InstantiateMsg, QueryMsg, RevenueResponse,
+};
 
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
@@ -127,6 +132,7 @@ fn test_pay_fee() {
     let msg = ExecuteMsg::PayFee {
         payee: Addr::unchecked(payee),
         action: MOCK_ACTION.to_string(),
+        amount: None,
     };
 
     // Paying fee without funds
@@ -172,6 +178,7 @@ fn test_pay_fee_contract() {
     let msg = ExecuteMsg::PayFee {
         payee: Addr::unchecked(payee),
         action: MOCK_ACTION.to_string(),
+        amount: None,
     };
 
     BALANCES
@@ -208,6 +215,7 @@ fn test_pay_fee_app() {
     let msg = ExecuteMsg::PayFee {
         payee: Addr::unchecked(payee),
         action: MOCK_ACTION.to_string(),
+        amount: None,
     };
 
     BALANCES
@@ -245,6 +253,7 @@ fn test_pay_fee_joint() {
     let msg = ExecuteMsg::PayFee {
         payee: Addr::unchecked(payee),
         action: MOCK_ACTION.to_string(),
+        amount: None,
     };
 
     // Contract balance
@@ -309,6 +318,12 @@ fn test_pay_fee_joint() {
         .unwrap_or_default();
     assert_eq!(balance, Uint128::from(10u128));
 
+    // Only the amount paid by the payee counts towards its fee volume
+    let volume = FEE_VOLUME
+        .load(deps.as_ref().storage, (&Addr::unchecked(payee), "uusd"))
+        .unwrap();
+    assert_eq!(volume, Uint128::from(3u128));
+
     // Check insufficient funds
     // Contract balance
     BALANCES
@@ -331,6 +346,504 @@ fn test_pay_fee_joint() {
     assert_eq!(res, ContractError::InsufficientFunds {});
 }
 
+#[test]
+fn test_pay_fee_percentage() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+    let payee = "payee";
+
+    BALANCES
+        .save(
+            deps.as_mut().storage,
+            (Addr::unchecked(payee), "uusd".to_string()),
+            &Uint128::from(100u128),
+        )
+        .unwrap();
+
+    // 10 uusd plus 5% of 200 uusd
+    let msg = ExecuteMsg::PayFee {
+        payee: Addr::unchecked(payee),
+        action: MOCK_PERCENTAGE_ACTION.to_string(),
+        amount: Some(Uint128::from(200u128)),
+    };
+    execute(deps.as_mut(), env, info, msg).unwrap();
+
+    let balance = BALANCES
+        .load(
+            deps.as_ref().storage,
+            (Addr::unchecked(payee), "uusd".to_string()),
+        )
+        .unwrap();
+    assert_eq!(balance, Uint128::from(80u128));
+
+    let publisher = Addr::unchecked(MOCK_ADO_PUBLISHER);
+    let balance = BALANCES
+        .load(deps.as_ref().storage, (publisher, "uusd".to_string()))
+        .unwrap_or_default();
+    assert_eq!(balance, Uint128::from(20u128));
+
+    let volume = FEE_VOLUME
+        .load(deps.as_ref().storage, (&Addr::unchecked(payee), "uusd"))
+        .unwrap();
+    assert_eq!(volume, Uint128::from(20u128));
+}
+
+#[test]
+fn test_fee_exemption() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let owner = mock_info("owner", &[]);
+    let payee = "payee";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        InstantiateMsg {
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            owner: None,
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::AddFeeExemption {
+        address: AndrAddr::from_string(payee),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), owner.clone(), msg).unwrap();
+
+    let exempt: bool = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::FeeExempt {
+                address: AndrAddr::from_string(payee),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(exempt);
+
+    // Exempt payees are not charged even without funds
+    let msg = ExecuteMsg::PayFee {
+        payee: Addr::unchecked(payee),
+        action: MOCK_ACTION.to_string(),
+        amount: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "fee_exempt" && attr.value == payee));
+
+    let msg_remove = ExecuteMsg::RemoveFeeExemption {
+        address: AndrAddr::from_string(payee),
+    };
+    execute(deps.as_mut(), env.clone(), owner, msg_remove).unwrap();
+
+    let err = execute(deps.as_mut(), env, mock_info("creator", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+}
+
+#[test]
+fn test_fee_tiers() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let owner = mock_info("owner", &[]);
+    let payee = "payee";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        InstantiateMsg {
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            owner: None,
+        },
+    )
+    .unwrap();
+
+    let invalid_tiers = vec![FeeTier {
+        requirement: FeeTierRequirement::Staked {
+            amount: Uint128::from(100u128),
+        },
+        discount: Decimal::percent(101),
+    }];
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::SetFeeTiers {
+            tiers: invalid_tiers,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidRate {});
+
+    let tiers = vec![
+        FeeTier {
+            requirement: FeeTierRequirement::Volume {
+                asset: "uusd".to_string(),
+                amount: Uint128::from(10u128),
+            },
+            discount: Decimal::percent(20),
+        },
+        FeeTier {
+            requirement: FeeTierRequirement::Staked {
+                amount: Uint128::from(1000u128),
+            },
+            discount: Decimal::percent(50),
+        },
+    ];
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner,
+        ExecuteMsg::SetFeeTiers {
+            tiers: tiers.clone(),
+        },
+    )
+    .unwrap();
+    let stored: Vec<FeeTier> =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::FeeTiers {}).unwrap()).unwrap();
+    assert_eq!(stored, tiers);
+
+    let estimate_msg = QueryMsg::EstimateFee {
+        ado_type: "ADOType".to_string(),
+        action: MOCK_PERCENTAGE_ACTION.to_string(),
+        payer: AndrAddr::from_string(payee),
+    };
+    let estimate: Option<FeeEstimate> =
+        from_binary(&query(deps.as_ref(), env.clone(), estimate_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        estimate,
+        Some(FeeEstimate {
+            asset: "uusd".to_string(),
+            amount: Uint128::from(10u128),
+            percentage: Some(Decimal::percent(5)),
+            discount: Decimal::zero(),
+            exempt: false,
+        })
+    );
+
+    // Paying a fee qualifies the payee for the volume tier
+    BALANCES
+        .save(
+            deps.as_mut().storage,
+            (Addr::unchecked(payee), "uusd".to_string()),
+            &Uint128::from(100u128),
+        )
+        .unwrap();
+    let msg = ExecuteMsg::PayFee {
+        payee: Addr::unchecked(payee),
+        action: MOCK_ACTION.to_string(),
+        amount: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let estimate: Option<FeeEstimate> =
+        from_binary(&query(deps.as_ref(), env.clone(), estimate_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        estimate,
+        Some(FeeEstimate {
+            asset: "uusd".to_string(),
+            amount: Uint128::from(8u128),
+            percentage: Some(Decimal::percent(4)),
+            discount: Decimal::percent(20),
+            exempt: false,
+        })
+    );
+
+    // Staking qualifies the payee for the larger discount
+    deps.querier.base.update_staking(
+        "uandr",
+        &[],
+        &[FullDelegation {
+            delegator: Addr::unchecked(payee),
+            validator: "validator".to_string(),
+            amount: coin(1000, "uandr"),
+            can_redelegate: coin(0, "uandr"),
+            accumulated_rewards: vec![],
+        }],
+    );
+    let estimate: Option<FeeEstimate> =
+        from_binary(&query(deps.as_ref(), env.clone(), estimate_msg).unwrap()).unwrap();
+    assert_eq!(estimate.unwrap().discount, Decimal::percent(50));
+
+    let msg = ExecuteMsg::PayFee {
+        payee: Addr::unchecked(payee),
+        action: MOCK_ACTION.to_string(),
+        amount: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+    let balance = BALANCES
+        .load(
+            deps.as_ref().storage,
+            (Addr::unchecked(payee), "uusd".to_string()),
+        )
+        .unwrap();
+    assert_eq!(balance, Uint128::from(85u128));
+}
+
+#[test]
+fn test_fee_ledger() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    let info = mock_info("creator", &[]);
+    let payee = Addr::unchecked("payee");
+    let publisher = Addr::unchecked(MOCK_ADO_PUBLISHER);
+    let start = env.block.time;
+
+    BALANCES
+        .save(
+            deps.as_mut().storage,
+            (payee.clone(), "uusd".to_string()),
+            &Uint128::from(1000u128),
+        )
+        .unwrap();
+
+    // Pay a fee for each action an hour apart
+    for (action, amount) in [
+        (MOCK_ACTION, None),
+        (MOCK_PERCENTAGE_ACTION, Some(Uint128::from(100u128))),
+        (MOCK_ACTION, None),
+    ] {
+        let msg = ExecuteMsg::PayFee {
+            payee: payee.clone(),
+            action: action.to_string(),
+            amount,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        env.block.time = env.block.time.plus_seconds(3600);
+    }
+
+    let payments: Vec<FeePayment> = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::FeePayments {
+                receiver: AndrAddr::from_string(publisher.to_string()),
+                ado_type: None,
+                action: None,
+                start_time: None,
+                end_time: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        payments,
+        vec![
+            FeePayment {
+                id: 1,
+                ado_type: "ADOType".to_string(),
+                action: MOCK_ACTION.to_string(),
+                payee: payee.clone(),
+                receiver: publisher.clone(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(10u128),
+                timestamp: start,
+            },
+            FeePayment {
+                id: 2,
+                ado_type: "ADOType".to_string(),
+                action: MOCK_PERCENTAGE_ACTION.to_string(),
+                payee: payee.clone(),
+                receiver: publisher.clone(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(15u128),
+                timestamp: start.plus_seconds(3600),
+            },
+            FeePayment {
+                id: 3,
+                ado_type: "ADOType".to_string(),
+                action: MOCK_ACTION.to_string(),
+                payee,
+                receiver: publisher.clone(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(10u128),
+                timestamp: start.plus_seconds(7200),
+            },
+        ]
+    );
+
+    // Time range and action filters
+    let filtered: Vec<FeePayment> = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::FeePayments {
+                receiver: AndrAddr::from_string(publisher.to_string()),
+                ado_type: Some("ADOType".to_string()),
+                action: Some(MOCK_ACTION.to_string()),
+                start_time: Some(start.plus_seconds(1)),
+                end_time: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(filtered, vec![payments[2].clone()]);
+
+    // Paginated revenue
+    let revenue: RevenueResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Revenue {
+                receiver: AndrAddr::from_string(publisher.to_string()),
+                start_time: None,
+                end_time: Some(start.plus_seconds(7200)),
+                start_after: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        revenue,
+        RevenueResponse {
+            revenue: vec![FeeRevenue {
+                ado_type: "ADOType".to_string(),
+                action: MOCK_ACTION.to_string(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(10u128),
+            }],
+            last_payment: Some(1),
+        }
+    );
+
+    let revenue: RevenueResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Revenue {
+                receiver: AndrAddr::from_string(publisher.to_string()),
+                start_time: None,
+                end_time: Some(start.plus_seconds(7200)),
+                start_after: revenue.last_payment,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        revenue,
+        RevenueResponse {
+            revenue: vec![FeeRevenue {
+                ado_type: "ADOType".to_string(),
+                action: MOCK_PERCENTAGE_ACTION.to_string(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(15u128),
+            }],
+            last_payment: Some(2),
+        }
+    );
+
+    let revenue: RevenueResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Revenue {
+                receiver: AndrAddr::from_string(publisher.to_string()),
+                start_time: None,
+                end_time: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(revenue.revenue[0].amount, Uint128::from(20u128));
+    assert_eq!(revenue.last_payment, Some(3));
+
+    let total: Vec<FeeRevenue> = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::TotalRevenue {
+                receiver: AndrAddr::from_string(publisher.to_string()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        total,
+        vec![
+            FeeRevenue {
+                ado_type: "ADOType".to_string(),
+                action: MOCK_ACTION.to_string(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(20u128),
+            },
+            FeeRevenue {
+                ado_type: "ADOType".to_string(),
+                action: MOCK_PERCENTAGE_ACTION.to_string(),
+                asset: "uusd".to_string(),
+                amount: Uint128::from(15u128),
+            },
+        ]
+    );
+
+    // Filtering by action requires the ADO type
+    let err = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::FeePayments {
+            receiver: AndrAddr::from_string(publisher.to_string()),
+            ado_type: None,
+            action: Some(MOCK_ACTION.to_string()),
+            start_time: None,
+            end_time: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidQuery {});
+
+    // Payments to another receiver cannot be used to continue the query
+    let err = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::FeePayments {
+            receiver: AndrAddr::from_string("other_receiver"),
+            ado_type: None,
+            action: None,
+            start_time: None,
+            end_time: None,
+            start_after: Some(1),
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidQuery {});
+}
+
 #[test]
 fn test_withdraw() {
     let mut deps = mock_dependencies_custom(&[]);
@@ -13,7 +13,10 @@ use cw2::{get_contract_version, set_contract_version};
 use semver::Version;
 
 use crate::ibc::{IBCLifecycleComplete, SudoMsg};
-use crate::reply::{on_reply_create_ado, on_reply_ibc_hooks_packet_send, ReplyId};
+use crate::reply::{
+    on_reply_amp_batch_msg, on_reply_create_ado, on_reply_ibc_direct_packet_send,
+    on_reply_ibc_hooks_packet_send, on_reply_register_username, ReplyId,
+};
 use crate::state::CURR_CHAIN;
 use crate::{execute, query, sudo};
 
@@ -49,6 +52,14 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // Batch messages record their failure rather than reverting
+    if let Some(ReplyId::AMPBatchMsg) = ReplyId::from_repr(msg.id) {
+        return on_reply_amp_batch_msg(deps, msg);
+    }
+    // Failed username registrations are reported in the packet acknowledgement
+    if let Some(ReplyId::RegisterUsername) = ReplyId::from_repr(msg.id) {
+        return on_reply_register_username(msg);
+    }
     if msg.result.is_err() {
         return Err(ContractError::Std(StdError::generic_err(format!(
             "{}:{}",
@@ -60,6 +71,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
     match ReplyId::from_repr(msg.id) {
         Some(ReplyId::CreateADO) => on_reply_create_ado(deps, env, msg),
         Some(ReplyId::IBCHooksPacketSend) => on_reply_ibc_hooks_packet_send(deps, msg),
+        Some(ReplyId::IBCDirectPacketSend) => on_reply_ibc_direct_packet_send(deps, msg),
         _ => Ok(Response::default()),
     }
 }
@@ -100,14 +112,18 @@ pub fn execute(
             direct_channel_id,
             chain,
             kernel_address,
+            packet_timeout,
         } => execute::assign_channels(
             execute_env,
             ics20_channel_id,
             direct_channel_id,
             chain,
             kernel_address,
+            packet_timeout,
         ),
         ExecuteMsg::Recover {} => execute::recover(execute_env),
+        ExecuteMsg::SetEnv { variable, value } => execute::set_env(execute_env, variable, value),
+        ExecuteMsg::UnsetEnv { variable } => execute::unset_env(execute_env, variable),
         ExecuteMsg::Internal(msg) => execute::internal(execute_env, msg),
     }
 }
@@ -174,5 +190,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
         }
         QueryMsg::ChannelInfo { chain } => encode_binary(&query::channel_info(deps, chain)?),
         QueryMsg::Recoveries { addr } => encode_binary(&query::recoveries(deps, addr)?),
+        QueryMsg::GetEnv { variable } => encode_binary(&query::get_env(deps, variable)?),
+        QueryMsg::PacketStatus { channel, sequence } => {
+            encode_binary(&query::packet_status(deps, channel, sequence)?)
+        }
+        QueryMsg::PacketsByOrigin {
+            origin,
+            start_after,
+            limit,
+        } => encode_binary(&query::packets_by_origin(deps, origin, start_after, limit)?),
     }
 }
@@ -1,33 +1,38 @@
 use andromeda_std::ado_contract::ADOContract;
 use andromeda_std::amp::addresses::AndrAddr;
-use andromeda_std::amp::messages::{AMPMsg, AMPPkt, IBCConfig};
+use andromeda_std::amp::messages::{AMPCtx, AMPMsg, AMPPkt, IBCConfig};
 use andromeda_std::amp::{ADO_DB_KEY, VFS_KEY};
 
 use andromeda_std::common::context::ExecuteContext;
 use andromeda_std::error::ContractError;
+use andromeda_std::os::adodb::ADOVersionStatus;
 use andromeda_std::os::aos_querier::AOSQuerier;
-use andromeda_std::os::kernel::{ChannelInfo, IbcExecuteMsg, InternalMsg};
+use andromeda_std::os::kernel::{
+    AMPMsgOutcome, AMPPktResponse, ChannelInfo, ExecuteMsg, IbcExecuteMsg, InternalMsg,
+};
 
 use andromeda_std::os::vfs::vfs_resolve_symlink;
 use cosmwasm_std::{
-    attr, ensure, to_binary, Addr, BankMsg, Binary, CosmosMsg, DepsMut, Env, IbcMsg, MessageInfo,
-    Response, StdError, SubMsg, WasmMsg,
+    attr, ensure, to_binary, Addr, BankMsg, Binary, CosmosMsg, DepsMut, Env, IbcMsg, IbcTimeout,
+    MessageInfo, Response, StdError, Storage, SubMsg, WasmMsg,
 };
 
-use crate::ibc::{generate_transfer_message, PACKET_LIFETIME};
+use crate::ibc::{generate_ics20_transfer_message, generate_transfer_message, get_packet_timeout};
 use crate::state::{
-    IBCHooksPacketSendState, ADO_OWNER, CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, IBC_FUND_RECOVERY,
-    KERNEL_ADDRESSES, OUTGOING_IBC_HOOKS_PACKETS,
+    assign_packet_id, pop_amp_batch_msg, AMPBatchMsgState, IBCDirectPacketSendState,
+    IBCHooksPacketSendState, ADO_OWNER, AMP_BATCH_COUNT, AMP_BATCH_MSGS, AMP_BATCH_OUTCOMES,
+    CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, ENV_VARIABLES, IBC_FUND_RECOVERY, KERNEL_ADDRESSES,
+    OUTGOING_IBC_DIRECT_PACKETS, OUTGOING_IBC_HOOKS_PACKETS, PACKET_RECIPIENTS,
 };
 use crate::{query, reply::ReplyId};
 
-pub fn send(execute_env: ExecuteContext, message: AMPMsg) -> Result<Response, ContractError> {
-    let res = MsgHandler::new(message).handle(
-        execute_env.deps,
+pub fn send(mut execute_env: ExecuteContext, message: AMPMsg) -> Result<Response, ContractError> {
+    let (res, _) = handle_messages(
+        &mut execute_env.deps,
         execute_env.info,
         execute_env.env,
         execute_env.amp_ctx,
-        0,
+        vec![message],
     )?;
 
     Ok(res)
@@ -44,14 +49,16 @@ pub fn amp_receive(
             || packet.ctx.get_origin() == info.sender,
         ContractError::Unauthorized {}
     );
+    // Packets forwarded by ADOs keep the IDs assigned to the packet the kernel relayed to them
     ensure!(
-        packet.ctx.id == 0,
+        (packet.ctx.id == 0 && packet.ctx.hop_sequence == 0)
+            || PACKET_RECIPIENTS.may_load(deps.storage, packet.ctx.hop_sequence)?
+                == Some(info.sender.clone()),
         ContractError::InvalidPacket {
             error: Some("Packet ID cannot be provided from outside the Kernel".into())
         }
     );
 
-    let mut res = Response::default();
     ensure!(
         !packet.messages.is_empty(),
         ContractError::InvalidPacket {
@@ -59,21 +66,121 @@ pub fn amp_receive(
         }
     );
 
-    for (idx, message) in packet.messages.iter().enumerate() {
+    let parent_packet_id = packet.ctx.id;
+    let (res, outcomes) = handle_messages(deps, info, env, Some(packet.clone()), packet.messages)?;
+
+    Ok(res
+        .add_attribute("action", "handle_amp_packet")
+        .add_attribute("parent_packet_id", parent_packet_id.to_string())
+        .set_data(to_binary(&AMPPktResponse { outcomes })?))
+}
+
+/// Handles each of the given messages in order, returning the outcome of each message.
+///
+/// The failure of a message with `exit_at_error` set reverts every message provided. Any other message is dispatched to the kernel as its own sub message so that its failure only reverts its own state changes.
+/// Failed messages are recorded upon reply, which refunds their funds to their recovery address and returns the updated outcomes as an `AMPPktResponse` in its data.
+fn handle_messages(
+    deps: &mut DepsMut,
+    info: MessageInfo,
+    env: Env,
+    ctx: Option<AMPPkt>,
+    messages: Vec<AMPMsg>,
+) -> Result<(Response, Vec<AMPMsgOutcome>), ContractError> {
+    let mut res = Response::default();
+    let mut outcomes = vec![];
+    let mut batch_msgs = vec![];
+    let batch_id = AMP_BATCH_COUNT.may_load(deps.storage)?.unwrap_or_default();
+
+    for (idx, message) in messages.iter().enumerate() {
         let mut handler = MsgHandler::new(message.clone());
-        let msg_res = handler.handle(
-            deps.branch(),
-            info.clone(),
-            env.clone(),
-            Some(packet.clone()),
-            idx as u64,
-        )?;
-        res.messages.extend_from_slice(&msg_res.messages);
-        res.attributes.extend_from_slice(&msg_res.attributes);
-        res.events.extend_from_slice(&msg_res.events);
+        if message.config.exit_at_error {
+            let msg_res = handler.handle(
+                deps.branch(),
+                info.clone(),
+                env.clone(),
+                ctx.clone(),
+                idx as u64,
+            )?;
+            res = res
+                .add_submessages(msg_res.messages)
+                .add_attributes(msg_res.attributes)
+                .add_events(msg_res.events);
+        } else {
+            let refund_addr = handler.get_recovery_addr(deps, info.clone(), ctx.clone())?;
+            let batch_msg = WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_binary(&ExecuteMsg::Internal(InternalMsg::AMPBatchMsg {
+                    sender: info.sender.clone(),
+                    ctx: ctx.as_ref().map(|pkt| pkt.ctx.clone()),
+                    message: Box::new(message.clone()),
+                    index: idx as u64,
+                }))?,
+                funds: message.funds.clone(),
+            };
+            res = res.add_submessage(SubMsg::reply_on_error(
+                batch_msg,
+                ReplyId::AMPBatchMsg.repr(),
+            ));
+            batch_msgs.push(AMPBatchMsgState {
+                batch_id,
+                index: idx as u64,
+                refund_addr,
+                funds: message.funds.clone(),
+            });
+        }
+        outcomes.push(AMPMsgOutcome::Success);
+    }
+
+    if !batch_msgs.is_empty() {
+        AMP_BATCH_COUNT.save(deps.storage, &(batch_id + 1))?;
+        AMP_BATCH_OUTCOMES.save(deps.storage, batch_id, &outcomes)?;
+        let pending = AMP_BATCH_MSGS.may_load(deps.storage)?.unwrap_or_default();
+        batch_msgs.extend(pending);
+        AMP_BATCH_MSGS.save(deps.storage, &batch_msgs)?;
     }
 
-    Ok(res.add_attribute("action", "handle_amp_packet"))
+    Ok((res, outcomes))
+}
+
+/// Handles a message of a batch dispatched by the kernel to itself
+///
+/// The message is removed from the batch queue as its failure, including that of its sub messages, reverts this removal and is instead recorded upon reply
+pub fn handle_amp_batch_msg(
+    execute_env: ExecuteContext,
+    sender: Addr,
+    ctx: Option<AMPCtx>,
+    message: AMPMsg,
+    index: u64,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        mut deps,
+        info,
+        env,
+        ..
+    } = execute_env;
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+
+    let ctx = ctx.map(|ctx| AMPPkt {
+        messages: vec![],
+        ctx,
+    });
+    let info = MessageInfo {
+        sender,
+        funds: info.funds,
+    };
+    let res = MsgHandler::new(message).handle(deps.branch(), info, env, ctx, index)?;
+
+    let (batch_msg, _) = pop_amp_batch_msg(deps.storage, None)?;
+    ensure!(
+        batch_msg.index == index,
+        ContractError::InvalidPacket {
+            error: Some("amp batch: unexpected message".to_string())
+        }
+    );
+    Ok(res)
 }
 
 pub fn upsert_key_address(
@@ -132,18 +239,17 @@ pub fn create(
             owner: owner.clone().unwrap(),
             ado_type: ado_type.clone(),
         };
-        let ibc_msg = IbcMsg::SendPacket {
-            channel_id: channel_info.direct_channel_id.clone().unwrap(),
-            data: to_binary(&kernel_msg)?,
-            timeout: execute_env
-                .env
-                .block
-                .time
-                .plus_seconds(PACKET_LIFETIME)
-                .into(),
-        };
+        let sub_msg = send_direct_packet(
+            execute_env.deps.storage,
+            channel_info.direct_channel_id.clone().unwrap(),
+            chain.clone(),
+            &kernel_msg,
+            get_packet_timeout(execute_env.env.block.time, None, &channel_info)?.into(),
+            execute_env.info.sender.to_string(),
+            execute_env.info.sender.clone(),
+        )?;
         Ok(Response::default()
-            .add_message(ibc_msg)
+            .add_submessage(sub_msg)
             .add_attributes(vec![
                 attr("action", "execute_create"),
                 attr("ado_type", ado_type),
@@ -159,11 +265,18 @@ pub fn create(
         let ado_owner = owner.unwrap_or(AndrAddr::from_string(execute_env.info.sender.to_string()));
         let owner_addr =
             ado_owner.get_raw_address_from_vfs(&execute_env.deps.as_ref(), vfs_addr)?;
-        let code_id =
-            AOSQuerier::code_id_getter(&execute_env.deps.querier, &adodb_addr, &ado_type)?;
+        let resolved =
+            AOSQuerier::ado_version_resolver(&execute_env.deps.querier, &adodb_addr, &ado_type)?;
+        let ado_version = format!("{}@{}", resolved.ado_type, resolved.version);
+        ensure!(
+            !resolved.status.is_yanked(),
+            ContractError::ADOVersionYanked {
+                version: ado_version
+            }
+        );
         let wasm_msg = WasmMsg::Instantiate {
             admin: Some(owner_addr.to_string()),
-            code_id,
+            code_id: resolved.code_id,
             msg,
             funds: vec![],
             label: format!("ADO:{ado_type}"),
@@ -178,11 +291,21 @@ pub fn create(
 
         ADO_OWNER.save(execute_env.deps.storage, &owner_addr)?;
 
-        Ok(Response::new()
+        let mut res = Response::new()
             .add_submessage(sub_msg)
             .add_attribute("action", "execute_create")
             .add_attribute("ado_type", ado_type)
-            .add_attribute("owner", ado_owner.to_string()))
+            .add_attribute("ado_version", ado_version.clone())
+            .add_attribute("owner", ado_owner.to_string());
+        if let ADOVersionStatus::Deprecated { reason } = resolved.status {
+            let warning = match reason {
+                Some(reason) => format!("{ado_version} is deprecated: {reason}"),
+                None => format!("{ado_version} is deprecated"),
+            };
+            res = res.add_attribute("warning", warning);
+        }
+
+        Ok(res)
     }
 }
 
@@ -193,6 +316,12 @@ pub fn internal(env: ExecuteContext, msg: InternalMsg) -> Result<Response, Contr
             address,
             chain,
         } => register_user_cross_chain(env, chain, username, address),
+        InternalMsg::AMPBatchMsg {
+            sender,
+            ctx,
+            message,
+            index,
+        } => handle_amp_batch_msg(env, sender, ctx, *message, index),
     }
 }
 
@@ -219,16 +348,16 @@ pub fn register_user_cross_chain(
         username: username.clone(),
         address: address.clone(),
     };
-    let ibc_msg = IbcMsg::SendPacket {
-        channel_id: channel_info.direct_channel_id.clone().unwrap(),
-        data: to_binary(&kernel_msg)?,
-        timeout: execute_env
-            .env
-            .block
-            .time
-            .plus_seconds(PACKET_LIFETIME)
-            .into(),
-    };
+    // The packet is tracked so that the registration can be completed for the chain it was sent to
+    let sub_msg = send_direct_packet(
+        execute_env.deps.storage,
+        channel_info.direct_channel_id.clone().unwrap(),
+        chain.clone(),
+        &kernel_msg,
+        get_packet_timeout(execute_env.env.block.time, None, &channel_info)?.into(),
+        execute_env.info.sender.to_string(),
+        execute_env.info.sender.clone(),
+    )?;
 
     Ok(Response::default()
         .add_attributes(vec![
@@ -238,7 +367,7 @@ pub fn register_user_cross_chain(
             attr("chain", chain),
             attr("receiving_kernel_address", channel_info.kernel_address),
         ])
-        .add_message(ibc_msg))
+        .add_submessage(sub_msg))
 }
 
 pub fn assign_channels(
@@ -247,18 +376,26 @@ pub fn assign_channels(
     direct_channel_id: Option<String>,
     chain: String,
     kernel_address: String,
+    packet_timeout: Option<u64>,
 ) -> Result<Response, ContractError> {
     let contract = ADOContract::default();
     ensure!(
         contract.is_contract_owner(execute_env.deps.storage, execute_env.info.sender.as_str())?,
         ContractError::Unauthorized {}
     );
+    ensure!(
+        packet_timeout != Some(0),
+        ContractError::InvalidPacket {
+            error: Some("Packet timeout must be greater than zero".to_string())
+        }
+    );
 
     let channel_info = ChannelInfo {
         ics20_channel_id,
         direct_channel_id,
         kernel_address,
         supported_modules: vec![],
+        packet_timeout,
     };
     CHAIN_TO_CHANNEL.save(execute_env.deps.storage, &chain, &channel_info)?;
     if let Some(channel) = channel_info.direct_channel_id.clone() {
@@ -304,6 +441,100 @@ pub fn recover(execute_env: ExecuteContext) -> Result<Response, ContractError> {
         .add_submessage(sub_msg))
 }
 
+pub fn set_env(
+    execute_env: ExecuteContext,
+    variable: String,
+    value: String,
+) -> Result<Response, ContractError> {
+    let contract = ADOContract::default();
+    ensure!(
+        contract.is_contract_owner(execute_env.deps.storage, execute_env.info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    validate_env_variable(&variable)?;
+    ENV_VARIABLES.save(execute_env.deps.storage, &variable, &value)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "set_env"),
+        attr("variable", variable),
+        attr("value", value),
+    ]))
+}
+
+pub fn unset_env(execute_env: ExecuteContext, variable: String) -> Result<Response, ContractError> {
+    let contract = ADOContract::default();
+    ensure!(
+        contract.is_contract_owner(execute_env.deps.storage, execute_env.info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        ENV_VARIABLES.has(execute_env.deps.storage, &variable),
+        ContractError::InvalidEnvironmentVariable {
+            msg: format!("{variable} is not set"),
+        }
+    );
+    ENV_VARIABLES.remove(execute_env.deps.storage, &variable);
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "unset_env"),
+        attr("variable", variable),
+    ]))
+}
+
+/// Environment variable names must be non-empty and consist of alphanumeric characters or underscores
+fn validate_env_variable(variable: &str) -> Result<(), ContractError> {
+    ensure!(
+        !variable.is_empty(),
+        ContractError::InvalidEnvironmentVariable {
+            msg: "Variable name cannot be empty".to_string(),
+        }
+    );
+    ensure!(
+        variable
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        ContractError::InvalidEnvironmentVariable {
+            msg: format!("{variable} contains invalid characters"),
+        }
+    );
+    Ok(())
+}
+
+/// Generates a sub message to send the given kernel message over a direct channel.
+///
+/// The packet is queued until its sequence is received in the reply, at which point it is stored
+/// in `OUTGOING_IBC_PACKETS` so that it can be tracked on acknowledgement or timeout.
+fn send_direct_packet(
+    storage: &mut dyn Storage,
+    channel_id: String,
+    chain: String,
+    kernel_msg: &IbcExecuteMsg,
+    timeout: IbcTimeout,
+    origin: String,
+    recovery_addr: Addr,
+) -> Result<SubMsg, ContractError> {
+    let mut outgoing_packets = OUTGOING_IBC_DIRECT_PACKETS
+        .load(storage)
+        .unwrap_or_default();
+    outgoing_packets.push(IBCDirectPacketSendState {
+        channel_id: channel_id.clone(),
+        chain,
+        origin,
+        recovery_addr,
+    });
+    OUTGOING_IBC_DIRECT_PACKETS.save(storage, &outgoing_packets)?;
+
+    let msg = IbcMsg::SendPacket {
+        channel_id,
+        data: to_binary(kernel_msg)?,
+        timeout,
+    };
+    Ok(SubMsg::reply_on_success(
+        msg,
+        ReplyId::IBCDirectPacketSend.repr(),
+    ))
+}
+
 /// Handles a given AMP message and returns a response
 ///
 /// Separated due to common functionality across multiple messages
@@ -324,6 +555,40 @@ impl MsgHandler {
         self.clone()
     }
 
+    /// Gets the original sender of the message, either from the packet context or the message sender
+    fn get_origin(&self, info: &MessageInfo, ctx: &Option<AMPPkt>) -> String {
+        if let Some(AMPPkt { ctx, .. }) = ctx {
+            ctx.get_origin()
+        } else {
+            info.sender.to_string()
+        }
+    }
+
+    /// Gets the address to which funds should be returned if the message fails to be relayed via IBC
+    ///
+    /// Defaults to the original sender of the message if no recovery address is provided
+    fn get_recovery_addr(
+        &self,
+        deps: &DepsMut,
+        info: MessageInfo,
+        ctx: Option<AMPPkt>,
+    ) -> Result<Addr, ContractError> {
+        if let Some(IBCConfig {
+            recovery_addr: Some(recovery_addr),
+            ..
+        }) = self.message().config.ibc_config.clone()
+        {
+            Ok(recovery_addr.get_raw_address(&deps.as_ref())?)
+        } else if let Some(origin) =
+            ctx.and_then(|pkt| deps.api.addr_validate(&pkt.ctx.get_origin()).ok())
+        {
+            // The origin of a message relayed from another chain is not a valid local address
+            Ok(origin)
+        } else {
+            Ok(info.sender)
+        }
+    }
+
     #[inline]
     pub fn handle(
         &mut self,
@@ -400,29 +665,32 @@ impl MsgHandler {
                     attr(format!("bank_send_amount:{sequence}"), funds[0].to_string()),
                 ]);
         } else {
-            let origin = if let Some(amp_ctx) = ctx {
-                amp_ctx.ctx.get_origin()
-            } else {
-                info.sender.to_string()
+            let mut context = match ctx {
+                Some(AMPPkt { ctx, .. }) => ctx,
+                None => AMPCtx::new(info.sender.clone(), info.sender.clone(), 0, None),
             };
-            let previous_sender = info.sender.to_string();
+            context.previous_sender = info.sender.to_string();
+            let hop_sequence = assign_packet_id(deps.storage, &mut context)?;
+            PACKET_RECIPIENTS.save(deps.storage, hop_sequence, &recipient_addr)?;
+            let packet_id = context.id;
 
-            let amp_msg = AMPMsg::new(
-                recipient_addr.clone(),
-                message.clone(),
-                Some(vec![funds[0].clone()]),
-            );
+            let amp_msg = AMPMsg::new(recipient_addr.clone(), message.clone(), Some(funds.clone()));
 
-            let new_packet = AMPPkt::new(origin, previous_sender, vec![amp_msg]);
+            let new_packet = AMPPkt {
+                messages: vec![amp_msg],
+                ctx: context,
+            };
 
             let sub_msg = new_packet.to_sub_msg(
                 recipient_addr.clone(),
-                Some(vec![funds[0].clone()]),
+                Some(funds.clone()),
                 ReplyId::AMPMsg.repr(),
             )?;
-            res = res
-                .add_submessage(sub_msg)
-                .add_attributes(vec![attr(format!("recipient:{sequence}"), recipient_addr)]);
+            res = res.add_submessage(sub_msg).add_attributes(vec![
+                attr(format!("recipient:{sequence}"), recipient_addr),
+                attr(format!("packet_id:{sequence}"), packet_id.to_string()),
+                attr(format!("hop_sequence:{sequence}"), hop_sequence.to_string()),
+            ]);
         }
         Ok(res)
     }
@@ -432,6 +700,9 @@ impl MsgHandler {
     The `chain` is the chain ID of the destination chain and an appropriate channel must be present for the given chain.
 
     The VFS path has its protocol stripped and the message is passed via ibc-hooks to the kernel on the receiving chain. The kernel on the receiving chain will receive the message as if it was sent from the local chain and will act accordingly.
+
+    If the message's IBC config provides a route of intermediate chains the message is first sent to the kernel on the first chain in the route, which forwards it along the remaining route.
+    If the message's IBC config provides an ICS20 memo the funds are transferred directly to the recipient on the final chain, which is not required to have an Andromeda kernel.
     */
     fn handle_ibc(
         &self,
@@ -441,16 +712,19 @@ impl MsgHandler {
         ctx: Option<AMPPkt>,
         sequence: u64,
     ) -> Result<Response, ContractError> {
-        if let Some(chain) = self.message().recipient.get_chain() {
+        if let Some(chain) = self.message().get_next_chain() {
             let channel_info =
-                if let Some(channel_info) = CHAIN_TO_CHANNEL.may_load(deps.storage, chain)? {
+                if let Some(channel_info) = CHAIN_TO_CHANNEL.may_load(deps.storage, &chain)? {
                     Ok::<ChannelInfo, ContractError>(channel_info)
                 } else {
                     return Err(ContractError::InvalidPacket {
                         error: Some(format!("Channel not found for chain {chain}")),
                     });
                 }?;
-            if !self.message().funds.is_empty() {
+            // Chains without a kernel can only be reached as the final chain of the route
+            if self.message().get_ics20_memo().is_some() && self.message().get_route().is_empty() {
+                self.handle_ibc_transfer(deps, info, env, ctx, sequence, channel_info)
+            } else if !self.message().funds.is_empty() {
                 self.handle_ibc_hooks(deps, info, env, ctx, sequence, channel_info)
             } else {
                 self.handle_ibc_direct(deps, info, env, ctx, sequence, channel_info)
@@ -464,24 +738,22 @@ impl MsgHandler {
 
     fn handle_ibc_direct(
         &self,
-        _deps: DepsMut,
-        _info: MessageInfo,
+        deps: DepsMut,
+        info: MessageInfo,
         env: Env,
-        _ctx: Option<AMPPkt>,
+        ctx: Option<AMPPkt>,
         sequence: u64,
         channel_info: ChannelInfo,
     ) -> Result<Response, ContractError> {
-        let AMPMsg {
-            recipient, message, ..
-        } = self.message();
+        let AMPMsg { message, .. } = self.message();
         ensure!(
-            Binary::default().eq(message),
+            !Binary::default().eq(message),
             ContractError::InvalidPacket {
                 error: Some("Cannot send an empty message without funds via IBC".to_string())
             }
         );
-        let chain = recipient.get_chain().unwrap();
-        let channel = if let Some(direct_channel) = channel_info.direct_channel_id {
+        let chain = self.message().get_next_chain().unwrap();
+        let channel = if let Some(direct_channel) = channel_info.direct_channel_id.clone() {
             Ok::<String, ContractError>(direct_channel)
         } else {
             return Err(ContractError::InvalidPacket {
@@ -489,25 +761,52 @@ impl MsgHandler {
             });
         }?;
 
-        let kernel_msg = IbcExecuteMsg::SendMessage {
-            recipient: AndrAddr::from_string(recipient.get_raw_path()),
-            message: message.clone(),
+        let origin = self.get_origin(&info, &ctx);
+        let mut context = match ctx.clone() {
+            Some(AMPPkt { ctx, .. }) => ctx,
+            None => AMPCtx::new(origin.clone(), origin.clone(), 0, None),
         };
-        let msg = IbcMsg::SendPacket {
-            channel_id: channel.clone(),
-            data: to_binary(&kernel_msg)?,
-            timeout: env.block.time.plus_seconds(PACKET_LIFETIME).into(),
+        context.previous_sender = env.contract.address.to_string();
+        let hop_sequence = assign_packet_id(deps.storage, &mut context)?;
+        let packet_id = context.id;
+        let recovery_addr = self.get_recovery_addr(&deps, info, ctx)?;
+        let timeout = get_packet_timeout(
+            env.block.time,
+            self.message().get_packet_timeout(),
+            &channel_info,
+        )?;
+
+        let kernel_msg = IbcExecuteMsg::SendMessageWithContext {
+            message: Box::new(self.message().to_next_hop()),
+            context,
         };
+        let sub_msg = send_direct_packet(
+            deps.storage,
+            channel.clone(),
+            chain.clone(),
+            &kernel_msg,
+            timeout.into(),
+            origin,
+            recovery_addr,
+        )?;
 
         Ok(Response::default()
             .add_attribute(format!("method:{sequence}"), "execute_send_message")
             .add_attribute(format!("channel:{sequence}"), channel)
+            .add_attribute(format!("packet_id:{sequence}"), packet_id.to_string())
+            .add_attribute(format!("hop_sequence:{sequence}"), hop_sequence.to_string())
             .add_attribute("receiving_kernel_address:{}", channel_info.kernel_address)
             .add_attribute("chain:{}", chain)
-            .add_message(msg))
+            .add_submessage(sub_msg))
     }
 
-    fn handle_ibc_hooks(
+    /**
+    Handles an IBC AMP Message sent to a chain without an Andromeda kernel.
+
+    The funds are sent to the recipient via ICS20 with a memo that either executes the message on the recipient via ibc-hooks or forwards the funds using packet-forward-middleware.
+    Failed transfers are recovered in the same manner as IBC hooks packets.
+    */
+    fn handle_ibc_transfer(
         &self,
         deps: DepsMut,
         info: MessageInfo,
@@ -516,15 +815,16 @@ impl MsgHandler {
         sequence: u64,
         channel_info: ChannelInfo,
     ) -> Result<Response, ContractError> {
-        let AMPMsg {
-            recipient,
-            message,
-            funds,
-            config,
-            ..
-        } = self.message();
-        let chain = recipient.get_chain().unwrap();
-        let channel = if let Some(ics20_channel) = channel_info.ics20_channel_id {
+        let AMPMsg { funds, .. } = self.message();
+        let chain = self.message().get_next_chain().unwrap();
+        let memo = self.message().get_ics20_memo().unwrap();
+        ensure!(
+            funds.len() == 1,
+            ContractError::InvalidPacket {
+                error: Some("A single coin must be provided to transfer via ICS20".to_string())
+            }
+        );
+        let channel = if let Some(ics20_channel) = channel_info.ics20_channel_id.clone() {
             Ok::<String, ContractError>(ics20_channel)
         } else {
             return Err(ContractError::InvalidPacket {
@@ -532,39 +832,94 @@ impl MsgHandler {
             });
         }?;
         let msg_funds = &funds[0].clone();
-        let recovery_addr = if let Some(IBCConfig {
-            recovery_addr: Some(recovery_addr),
-        }) = config.ibc_config.clone()
-        {
-            let addr = recovery_addr.get_raw_address(&deps.as_ref())?;
-            Ok::<Addr, ContractError>(addr)
-        } else if let Some(AMPPkt { ctx, .. }) = ctx {
-            Ok::<Addr, ContractError>(deps.api.addr_validate(&ctx.get_origin())?)
+        let timeout = get_packet_timeout(
+            env.block.time,
+            self.message().get_packet_timeout(),
+            &channel_info,
+        )?;
+        let origin = self.get_origin(&info, &ctx);
+        let recovery_addr = self.get_recovery_addr(&deps, info, ctx)?;
+
+        let msg = generate_ics20_transfer_message(
+            self.message(),
+            memo,
+            msg_funds.clone(),
+            channel.clone(),
+            env.contract.address.to_string(),
+            timeout,
+        )?;
+        let receiver = msg.receiver.clone();
+
+        let mut outgoing_packets = OUTGOING_IBC_HOOKS_PACKETS
+            .load(deps.storage)
+            .unwrap_or_default();
+        outgoing_packets.push(IBCHooksPacketSendState {
+            channel_id: channel.clone(),
+            origin,
+            amount: msg_funds.clone(),
+            recovery_addr,
+        });
+        OUTGOING_IBC_HOOKS_PACKETS.save(deps.storage, &outgoing_packets)?;
+
+        Ok(Response::default()
+            .add_submessage(SubMsg::reply_always(
+                msg,
+                ReplyId::IBCHooksPacketSend.repr(),
+            ))
+            .add_attribute(format!("method:{sequence}"), "execute_ics20_transfer")
+            .add_attribute(format!("channel:{sequence}"), channel)
+            .add_attribute(format!("receiver:{sequence}"), receiver)
+            .add_attribute(format!("chain:{sequence}"), chain))
+    }
+
+    fn handle_ibc_hooks(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        env: Env,
+        ctx: Option<AMPPkt>,
+        sequence: u64,
+        channel_info: ChannelInfo,
+    ) -> Result<Response, ContractError> {
+        let AMPMsg { funds, .. } = self.message();
+        let chain = self.message().get_next_chain().unwrap();
+        let channel = if let Some(ics20_channel) = channel_info.ics20_channel_id.clone() {
+            Ok::<String, ContractError>(ics20_channel)
         } else {
-            Ok::<Addr, ContractError>(info.sender)
+            return Err(ContractError::InvalidPacket {
+                error: Some(format!("Channel not found for chain {chain}")),
+            });
         }?;
+        let msg_funds = &funds[0].clone();
+        let timeout = get_packet_timeout(
+            env.block.time,
+            self.message().get_packet_timeout(),
+            &channel_info,
+        )?;
+        let origin = self.get_origin(&info, &ctx);
+        let recovery_addr = self.get_recovery_addr(&deps, info, ctx)?;
         let outgoing_state = IBCHooksPacketSendState {
             channel_id: channel.clone(),
+            origin,
             amount: msg_funds.clone(),
             recovery_addr,
         };
 
-        let mut outgoing_packets = OUTGOING_IBC_HOOKS_PACKETS
-            .load(deps.storage)
-            .unwrap_or_default();
-        outgoing_packets.push(outgoing_state);
-        OUTGOING_IBC_HOOKS_PACKETS.save(deps.storage, &outgoing_packets)?;
-
         let msg = generate_transfer_message(
             &deps.as_ref(),
-            recipient.clone(),
-            message.clone(),
+            self.message().to_next_hop(),
             msg_funds.clone(),
             channel.clone(),
             env.contract.address.to_string(),
             channel_info.kernel_address.clone(),
-            env.block.time,
+            timeout,
         )?;
+
+        let mut outgoing_packets = OUTGOING_IBC_HOOKS_PACKETS
+            .load(deps.storage)
+            .unwrap_or_default();
+        outgoing_packets.push(outgoing_state);
+        OUTGOING_IBC_HOOKS_PACKETS.save(deps.storage, &outgoing_packets)?;
         Ok(Response::default()
             .add_submessage(SubMsg::reply_always(
                 msg,
@@ -1,14 +1,21 @@
-use crate::ack::{make_ack_create_ado_success, make_ack_fail, make_ack_success};
+use crate::ack::{make_ack_create_ado_success, make_ack_fail, make_ack_success, Ack};
 use crate::execute;
 use crate::proto::{DenomTrace, MsgTransfer, QueryDenomTraceRequest};
 use crate::reply::ReplyId;
-use crate::state::{CHANNEL_TO_CHAIN, KERNEL_ADDRESSES};
+use crate::state::{CHANNEL_TO_CHAIN, KERNEL_ADDRESSES, OUTGOING_IBC_PACKETS, RECEIVED_PACKET_IDS};
+use crate::sudo::ibc_lifecycle::{receive_ack, receive_timeout};
 use andromeda_std::amp::VFS_KEY;
 use andromeda_std::common::context::ExecuteContext;
 use andromeda_std::error::{ContractError, Never};
 use andromeda_std::{
-    amp::{messages::AMPMsg, AndrAddr},
-    os::{kernel::IbcExecuteMsg, vfs::ExecuteMsg as VFSExecuteMsg},
+    amp::{
+        messages::{AMPMsg, AMPPkt, ICS20Memo},
+        AndrAddr,
+    },
+    os::{
+        kernel::{ChannelInfo, IbcExecuteMsg},
+        vfs::ExecuteMsg as VFSExecuteMsg,
+    },
 };
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
@@ -16,8 +23,9 @@ use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     ensure, from_binary, to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env,
     Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg,
-    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcOrder, IbcPacketAckMsg, IbcPacketReceiveMsg,
-    IbcPacketTimeoutMsg, IbcReceiveResponse, MessageInfo, SubMsg, Timestamp, WasmMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcOrder, IbcPacket, IbcPacketAckMsg,
+    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, MessageInfo, SubMsg, Timestamp,
+    WasmMsg,
 };
 use itertools::Itertools;
 use sha256::digest;
@@ -46,15 +54,20 @@ pub enum SudoMsg {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_timeout(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _msg: IbcPacketTimeoutMsg,
+    msg: IbcPacketTimeoutMsg,
 ) -> Result<IbcBasicResponse, ContractError> {
-    // As with ack above, nothing to do here. If we cared about
-    // keeping track of state between the two chains then we'd want to
-    // respond to this likely as it means that the packet in question
-    // isn't going anywhere.
-    Ok(IbcBasicResponse::new().add_attribute("method", "ibc_packet_timeout"))
+    let username_msg =
+        complete_username_registration(&deps, &msg.packet, Some("Packet timed out".to_string()))?;
+    let res = receive_timeout(deps, msg.packet.src.channel_id, msg.packet.sequence)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_timeout")
+        .add_messages(username_msg)
+        .add_attributes(res.attributes)
+        .add_submessages(res.messages)
+        .add_events(res.events))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -114,11 +127,34 @@ pub fn ibc_packet_receive(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_ack(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _msg: IbcPacketAckMsg,
+    msg: IbcPacketAckMsg,
 ) -> Result<IbcBasicResponse, ContractError> {
-    Ok(IbcBasicResponse::new())
+    let (ack, success) = match from_binary::<Ack>(&msg.acknowledgement.data) {
+        Ok(Ack::Result(data)) => (data.to_base64(), true),
+        Ok(Ack::Error(err)) => (err, false),
+        Err(_) => (msg.acknowledgement.data.to_base64(), false),
+    };
+    let username_msg = complete_username_registration(
+        &deps,
+        &msg.original_packet,
+        (!success).then(|| ack.clone()),
+    )?;
+    let res = receive_ack(
+        deps,
+        msg.original_packet.src.channel_id,
+        msg.original_packet.sequence,
+        ack,
+        success,
+    )?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_ack")
+        .add_messages(username_msg)
+        .add_attributes(res.attributes)
+        .add_submessages(res.messages)
+        .add_events(res.events))
 }
 
 pub fn do_ibc_packet_receive(
@@ -152,6 +188,43 @@ pub fn do_ibc_packet_receive(
                 .add_submessages(res.messages)
                 .add_events(res.events))
         }
+        IbcExecuteMsg::SendMessageWithContext { message, context } => {
+            let chain = CHANNEL_TO_CHAIN.load(execute_env.deps.storage, channel.as_str())?;
+            // Packets relayed by kernels that do not assign hop sequences are not checked for duplicates
+            if context.hop_sequence > 0 {
+                ensure!(
+                    !RECEIVED_PACKET_IDS
+                        .has(execute_env.deps.storage, (&chain, context.hop_sequence)),
+                    ContractError::InvalidPacket {
+                        error: Some(format!(
+                            "Packet {} from {chain} has already been received",
+                            context.hop_sequence
+                        ))
+                    }
+                );
+                RECEIVED_PACKET_IDS.save(
+                    execute_env.deps.storage,
+                    (&chain, context.hop_sequence),
+                    &true,
+                )?;
+            }
+            let received_packet_id = context.id;
+            let received_hop_sequence = context.hop_sequence;
+            let amp_ctx = AMPPkt {
+                messages: vec![],
+                ctx: context,
+            };
+            let res = execute::send(execute_env.with_ctx(amp_ctx), *message)?;
+
+            Ok(IbcReceiveResponse::new()
+                .set_ack(make_ack_success())
+                .add_attribute("source_chain", chain)
+                .add_attribute("received_packet_id", received_packet_id.to_string())
+                .add_attribute("received_hop_sequence", received_hop_sequence.to_string())
+                .add_attributes(res.attributes)
+                .add_submessages(res.messages)
+                .add_events(res.events))
+        }
         IbcExecuteMsg::CreateADO {
             instantiation_msg,
             owner,
@@ -200,6 +273,41 @@ pub fn ibc_register_username(
         .set_ack(make_ack_success()))
 }
 
+/// Confirms a cross-chain username registration with the VFS once its packet is acknowledged, or rolls it back if an error is provided
+fn complete_username_registration(
+    deps: &DepsMut,
+    packet: &IbcPacket,
+    error: Option<String>,
+) -> Result<Option<WasmMsg>, ContractError> {
+    let Ok(IbcExecuteMsg::RegisterUsername { username, .. }) = from_binary(&packet.data) else {
+        return Ok(None);
+    };
+    // The chain is recorded when the packet is sent as the channel may be reassigned while the packet is in flight,
+    // packets sent before the chain was recorded fall back to the chain currently assigned to the channel
+    let recorded_chain = OUTGOING_IBC_PACKETS
+        .may_load(deps.storage, (&packet.src.channel_id, packet.sequence))?
+        .and_then(|outgoing| outgoing.chain);
+    let chain = match recorded_chain {
+        Some(chain) => Some(chain),
+        None => CHANNEL_TO_CHAIN.may_load(deps.storage, packet.src.channel_id.as_str())?,
+    };
+    // Registrations whose chain cannot be determined are left pending to be cleared by the VFS owner
+    let Some(chain) = chain else {
+        return Ok(None);
+    };
+    let vfs_address = KERNEL_ADDRESSES.load(deps.storage, VFS_KEY)?;
+    let msg = VFSExecuteMsg::CompleteUserCrossChain {
+        username,
+        chain,
+        error,
+    };
+    Ok(Some(WasmMsg::Execute {
+        contract_addr: vfs_address.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    }))
+}
+
 pub fn validate_order_and_version(
     channel: &IbcChannel,
     counterparty_version: Option<&str>,
@@ -250,16 +358,35 @@ fn generate_ibc_denom(channel: String, denom: String) -> String {
     format!("ibc/{}", digest(path).to_uppercase())
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Determines when a packet sent to the given chain expires.
+///
+/// The lifetime provided by the message takes precedence over the default lifetime of the chain, falling back to `PACKET_LIFETIME` if neither are set.
+pub fn get_packet_timeout(
+    time: Timestamp,
+    msg_timeout: Option<u64>,
+    channel_info: &ChannelInfo,
+) -> Result<Timestamp, ContractError> {
+    let lifetime = msg_timeout
+        .or(channel_info.packet_timeout)
+        .unwrap_or(PACKET_LIFETIME);
+    ensure!(
+        lifetime > 0,
+        ContractError::InvalidPacket {
+            error: Some("Packet timeout must be greater than zero".to_string())
+        }
+    );
+    Ok(time.plus_seconds(lifetime))
+}
+
+/// Generates an ICS20 transfer with an ibc-hooks memo that passes the given message to the kernel on the receiving chain
 pub fn generate_transfer_message(
     deps: &Deps,
-    recipient: AndrAddr,
-    message: Binary,
+    msg: AMPMsg,
     funds: Coin,
     channel: String,
     from_addr: String,
     to_addr: String,
-    time: Timestamp,
+    timeout: Timestamp,
 ) -> Result<MsgTransfer, ContractError> {
     // Convert funds denom
     let new_denom = if funds.denom.starts_with("ibc/") {
@@ -278,11 +405,12 @@ pub fn generate_transfer_message(
         generate_ibc_denom(channel.clone(), funds.clone().denom)
     };
     let new_coin = Coin::new(funds.amount.u128(), new_denom);
-    let msg = AMPMsg::new(recipient.get_raw_path(), message, Some(vec![new_coin]));
+    let msg = AMPMsg {
+        funds: vec![new_coin],
+        ..msg
+    };
     let serialized = msg.to_ibc_hooks_memo(to_addr.clone(), from_addr.clone());
 
-    let ts = time.plus_seconds(PACKET_LIFETIME);
-
     Ok(MsgTransfer {
         source_port: TRANSFER_PORT.into(),
         source_channel: channel,
@@ -290,11 +418,118 @@ pub fn generate_transfer_message(
         sender: from_addr,
         receiver: to_addr,
         timeout_height: None,
-        timeout_timestamp: Some(ts.nanos()),
+        timeout_timestamp: Some(timeout.nanos()),
         memo: serialized,
     })
 }
 
+/// The receiver of a transfer on a chain that forwards the funds using packet-forward-middleware
+const PFM_RECEIVER: &str = "pfm";
+
+#[derive(::serde::Serialize)]
+struct WasmHook {
+    contract: String,
+    msg: serde_cw_value::Value,
+}
+
+#[derive(::serde::Serialize)]
+struct ForwardNext {
+    wasm: WasmHook,
+}
+
+#[derive(::serde::Serialize)]
+struct Forward {
+    receiver: String,
+    port: String,
+    channel: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next: Option<ForwardNext>,
+}
+
+#[derive(::serde::Serialize)]
+struct ICS20TransferMemo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wasm: Option<WasmHook>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forward: Option<Forward>,
+    ibc_callback: String,
+}
+
+/// Generates an ICS20 transfer of the given funds to a recipient on a chain without an Andromeda kernel.
+///
+/// The memo either executes the message on the recipient via ibc-hooks or forwards the funds to the recipient using packet-forward-middleware.
+/// The sending kernel is always attached as the ibc-hooks callback so that failed transfers can be recovered.
+pub fn generate_ics20_transfer_message(
+    msg: &AMPMsg,
+    memo: ICS20Memo,
+    funds: Coin,
+    channel: String,
+    from_addr: String,
+    timeout: Timestamp,
+) -> Result<MsgTransfer, ContractError> {
+    let recipient = msg
+        .recipient
+        .get_raw_path()
+        .trim_start_matches('/')
+        .to_string();
+    ensure!(
+        !recipient.is_empty(),
+        ContractError::InvalidPacket {
+            error: Some("Recipient not provided".to_string())
+        }
+    );
+    let wasm = if Binary::default().eq(&msg.message) {
+        None
+    } else {
+        let hook_msg = serde_json_wasm::from_slice(&msg.message).map_err(|_| {
+            ContractError::InvalidPacket {
+                error: Some("Message must be valid JSON to be executed via ibc-hooks".to_string()),
+            }
+        })?;
+        Some(WasmHook {
+            contract: recipient.clone(),
+            msg: hook_msg,
+        })
+    };
+
+    let (receiver, memo) = match memo {
+        ICS20Memo::IbcHooks => (
+            recipient,
+            ICS20TransferMemo {
+                wasm,
+                forward: None,
+                ibc_callback: from_addr.clone(),
+            },
+        ),
+        ICS20Memo::PacketForward { channel, port } => (
+            PFM_RECEIVER.to_string(),
+            ICS20TransferMemo {
+                wasm: None,
+                forward: Some(Forward {
+                    receiver: recipient,
+                    port: port.unwrap_or(TRANSFER_PORT.to_string()),
+                    channel,
+                    next: wasm.map(|wasm| ForwardNext { wasm }),
+                }),
+                ibc_callback: from_addr.clone(),
+            },
+        ),
+    };
+
+    Ok(MsgTransfer {
+        source_port: TRANSFER_PORT.into(),
+        source_channel: channel,
+        token: Some(funds.into()),
+        sender: from_addr,
+        receiver,
+        timeout_height: None,
+        timeout_timestamp: Some(timeout.nanos()),
+        memo: serde_json_wasm::to_string(&memo).map_err(|_| ContractError::InvalidPacket {
+            error: Some("Could not serialize ICS20 memo".to_string()),
+        })?,
+    })
+}
+
 // Methods adapted from Osmosis Registry contract found here:
 // https://github.com/osmosis-labs/osmosis/blob/main/cosmwasm/packages/registry/src/registry.rs#L14
 #[cw_serde]
@@ -55,6 +55,13 @@ pub struct MsgTransferResponse {
     pub sequence: u64,
 }
 
+/// The response data returned by wasmd when dispatching an `IbcMsg::SendPacket`
+#[derive(Clone, PartialEq, Eq, ::prost::Message)]
+pub struct MsgIBCSendResponse {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+}
+
 // DenomTrace query message definition.
 #[derive(
     Clone,
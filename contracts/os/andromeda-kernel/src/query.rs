@@ -1,11 +1,21 @@
 use andromeda_std::{
     amp::ADO_DB_KEY,
     error::ContractError,
-    os::{aos_querier::AOSQuerier, kernel::ChannelInfoResponse},
+    os::{
+        aos_querier::AOSQuerier,
+        kernel::{ChannelInfoResponse, PacketInfoResponse},
+    },
 };
-use cosmwasm_std::{Addr, Coin, Deps};
+use cosmwasm_std::{Addr, Coin, Deps, Order};
+use cw_storage_plus::Bound;
 
-use crate::state::{CHAIN_TO_CHANNEL, IBC_FUND_RECOVERY, KERNEL_ADDRESSES};
+use crate::state::{
+    packet_records, PacketRecord, CHAIN_TO_CHANNEL, ENV_VARIABLES, IBC_FUND_RECOVERY,
+    KERNEL_ADDRESSES,
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 pub fn key_address(deps: Deps, key: String) -> Result<Addr, ContractError> {
     Ok(KERNEL_ADDRESSES.load(deps.storage, &key)?)
@@ -34,6 +44,7 @@ pub fn channel_info(
             direct: info.direct_channel_id,
             kernel_address: info.kernel_address,
             supported_modules: info.supported_modules,
+            packet_timeout: info.packet_timeout,
         })
     } else {
         None
@@ -46,3 +57,54 @@ pub fn recoveries(deps: Deps, addr: Addr) -> Result<Vec<Coin>, ContractError> {
         .may_load(deps.storage, &addr)?
         .unwrap_or_default())
 }
+
+pub fn get_env(deps: Deps, variable: String) -> Result<Option<String>, ContractError> {
+    Ok(ENV_VARIABLES.may_load(deps.storage, &variable)?)
+}
+
+pub fn packet_status(
+    deps: Deps,
+    channel: String,
+    sequence: u64,
+) -> Result<Option<PacketInfoResponse>, ContractError> {
+    let record = packet_records().may_load(deps.storage, (&channel, sequence))?;
+    Ok(record.map(|record| packet_info_response(channel, sequence, record)))
+}
+
+pub fn packets_by_origin(
+    deps: Deps,
+    origin: String,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> Result<Vec<PacketInfoResponse>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let packets = packet_records()
+        .idx
+        .origin
+        .prefix(origin)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let ((channel, sequence), record) = item?;
+            Ok(packet_info_response(channel, sequence, record))
+        })
+        .collect::<Result<Vec<PacketInfoResponse>, ContractError>>()?;
+    Ok(packets)
+}
+
+fn packet_info_response(
+    channel: String,
+    sequence: u64,
+    record: PacketRecord,
+) -> PacketInfoResponse {
+    PacketInfoResponse {
+        channel,
+        sequence,
+        origin: record.origin,
+        recovery_addr: record.recovery_addr,
+        amount: record.amount,
+        status: record.status,
+    }
+}
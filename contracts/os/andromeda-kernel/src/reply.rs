@@ -1,17 +1,21 @@
 use crate::{
-    proto::MsgTransferResponse,
+    ack::make_ack_fail,
+    proto::{MsgIBCSendResponse, MsgTransferResponse},
     state::{
-        IBCHooksPacketSendState, OutgoingPacket, ADO_OWNER, OUTGOING_IBC_HOOKS_PACKETS,
-        OUTGOING_IBC_PACKETS,
+        packet_event, pop_amp_batch_msg, save_outgoing_packet, AMPBatchMsgState,
+        IBCDirectPacketSendState, IBCHooksPacketSendState, OutgoingPacket, ADO_OWNER,
+        OUTGOING_IBC_DIRECT_PACKETS, OUTGOING_IBC_HOOKS_PACKETS,
     },
 };
 use andromeda_std::{
-    ado_base::AndromedaMsg, common::response::get_reply_address, error::ContractError,
-    os::aos_querier::AOSQuerier,
+    ado_base::AndromedaMsg,
+    common::response::get_reply_address,
+    error::ContractError,
+    os::{aos_querier::AOSQuerier, kernel::AMPPktResponse},
 };
 use cosmwasm_std::{
-    ensure, wasm_execute, Addr, DepsMut, Empty, Env, Reply, Response, SubMsg, SubMsgResponse,
-    SubMsgResult,
+    attr, ensure, to_binary, wasm_execute, Addr, BankMsg, DepsMut, Empty, Env, Reply, Response,
+    SubMsg, SubMsgResponse, SubMsgResult,
 };
 use enum_repr::EnumRepr;
 
@@ -23,6 +27,8 @@ pub enum ReplyId {
     IBCHooksPacketSend = 4,
     Recovery = 5,
     RegisterUsername = 6,
+    IBCDirectPacketSend = 7,
+    AMPBatchMsg = 8,
 }
 
 /// Handles the reply from an ADO creation
@@ -58,7 +64,9 @@ pub fn on_reply_ibc_hooks_packet_send(
     msg: Reply,
 ) -> Result<Response, ContractError> {
     let SubMsgResult::Ok(SubMsgResponse { data: Some(b), .. }) = msg.result else {
-        return Err(ContractError::InvalidPacket { error: Some(format!("ibc hooks: failed reply: {:?}", msg.result)) })
+        return Err(ContractError::InvalidPacket {
+            error: Some(format!("ibc hooks: failed reply: {:?}", msg.result)),
+        });
     };
 
     let MsgTransferResponse { sequence } =
@@ -78,23 +86,121 @@ pub fn on_reply_ibc_hooks_packet_send(
 
     let IBCHooksPacketSendState {
         channel_id,
+        origin,
         recovery_addr,
         amount,
     } = outgoing_packets.remove(0);
 
     OUTGOING_IBC_HOOKS_PACKETS.save(deps.storage, &outgoing_packets)?;
-    OUTGOING_IBC_PACKETS.save(
-        deps.storage,
-        (&channel_id, sequence),
-        &OutgoingPacket {
-            recovery_addr: recovery_addr.clone(),
-            amount,
-        },
-    )?;
+    let packet = OutgoingPacket {
+        recovery_addr: recovery_addr.clone(),
+        amount: Some(amount),
+        origin: Some(origin),
+        chain: None,
+    };
+    save_outgoing_packet(deps.storage, &channel_id, sequence, &packet)?;
 
     Ok(Response::default()
+        .add_event(packet_event("sent", &channel_id, sequence, &packet))
         .add_attribute("action", "ibc_hooks_packet_send")
         .add_attribute("channel_id", channel_id)
         .add_attribute("sequence", sequence.to_string())
         .add_attribute("recovery_addr", recovery_addr))
 }
+
+/// Handles the reply from sending a packet over a direct channel and stores it against its sequence
+/// so that it can be tracked when it is acknowledged or times out
+pub fn on_reply_ibc_direct_packet_send(
+    deps: DepsMut,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let SubMsgResult::Ok(SubMsgResponse { data: Some(b), .. }) = msg.result else {
+        return Err(ContractError::InvalidPacket {
+            error: Some(format!("ibc direct: failed reply: {:?}", msg.result)),
+        });
+    };
+
+    let MsgIBCSendResponse { sequence } =
+        MsgIBCSendResponse::decode(&b[..]).map_err(|_e| ContractError::InvalidPacket {
+            error: Some(format!("ibc direct: could not decode response: {b}")),
+        })?;
+
+    let mut outgoing_packets = OUTGOING_IBC_DIRECT_PACKETS
+        .load(deps.as_ref().storage)
+        .unwrap_or_default();
+    ensure!(
+        !outgoing_packets.is_empty(),
+        ContractError::InvalidPacket {
+            error: Some("ibc direct: no outgoing packets".to_string())
+        }
+    );
+
+    let IBCDirectPacketSendState {
+        channel_id,
+        chain,
+        origin,
+        recovery_addr,
+    } = outgoing_packets.remove(0);
+
+    OUTGOING_IBC_DIRECT_PACKETS.save(deps.storage, &outgoing_packets)?;
+    let packet = OutgoingPacket {
+        recovery_addr: recovery_addr.clone(),
+        amount: None,
+        origin: Some(origin.clone()),
+        chain: Some(chain),
+    };
+    save_outgoing_packet(deps.storage, &channel_id, sequence, &packet)?;
+
+    Ok(Response::default()
+        .add_event(packet_event("sent", &channel_id, sequence, &packet))
+        .add_attribute("action", "ibc_direct_packet_send")
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("sequence", sequence.to_string())
+        .add_attribute("origin", origin)
+        .add_attribute("recovery_addr", recovery_addr))
+}
+
+/// Handles the failure of a batch message that may fail without reverting the rest of its batch
+///
+/// Records the outcome of the message and refunds its funds to its recovery address
+pub fn on_reply_amp_batch_msg(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let SubMsgResult::Err(error) = msg.result else {
+        return Ok(Response::default());
+    };
+    let (
+        AMPBatchMsgState {
+            index,
+            refund_addr,
+            funds,
+            ..
+        },
+        outcomes,
+    ) = pop_amp_batch_msg(deps.storage, Some(error.clone()))?;
+
+    let mut res = Response::default().add_attributes(vec![
+        attr("action", "amp_batch_msg_reply"),
+        attr(format!("failed:{index}"), error),
+        attr(format!("refund_addr:{index}"), refund_addr.clone()),
+    ]);
+    if !funds.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: refund_addr.to_string(),
+            amount: funds,
+        });
+    }
+
+    Ok(res.set_data(to_binary(&AMPPktResponse { outcomes })?))
+}
+
+/// Handles the failure of registering a username received from another chain
+///
+/// Replaces the packet acknowledgement with an error so that the sending chain can roll back its registration
+pub fn on_reply_register_username(msg: Reply) -> Result<Response, ContractError> {
+    let SubMsgResult::Err(error) = msg.result else {
+        return Ok(Response::default());
+    };
+    Ok(Response::default()
+        .add_attribute("action", "register_username_failed")
+        .add_attribute("error", error.clone())
+        .set_data(make_ack_fail(error)))
+}
@@ -1,23 +1,83 @@
-use andromeda_std::os::kernel::ChannelInfo;
+use andromeda_std::amp::messages::AMPCtx;
+use andromeda_std::os::kernel::{AMPMsgOutcome, ChannelInfo, PacketStatus};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Event, StdError, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[cw_serde]
 pub struct IBCHooksPacketSendState {
     pub channel_id: String,
+    pub origin: String,
     pub recovery_addr: Addr,
     pub amount: Coin,
 }
 
+#[cw_serde]
+pub struct IBCDirectPacketSendState {
+    pub channel_id: String,
+    pub chain: String,
+    pub origin: String,
+    pub recovery_addr: Addr,
+}
+
+/// A message of an AMP packet whose failure does not revert the rest of the packet
+#[cw_serde]
+pub struct AMPBatchMsgState {
+    pub batch_id: u64,
+    /// The position of the message within its batch
+    pub index: u64,
+    pub refund_addr: Addr,
+    pub funds: Vec<Coin>,
+}
+
 #[cw_serde]
 pub struct OutgoingPacket {
     pub recovery_addr: Addr,
-    pub amount: Coin,
+    /// The funds sent with the packet, direct channel packets do not carry any funds
+    pub amount: Option<Coin>,
+    /// The original sender of the message carried by the packet
+    pub origin: Option<String>,
+    /// The chain the packet was sent to, recorded for direct channel packets as the channel may be reassigned while the packet is in flight
+    #[serde(default)]
+    pub chain: Option<String>,
+}
+
+/// A record of an outgoing packet that is kept after the packet has been acknowledged or has timed out
+#[cw_serde]
+pub struct PacketRecord {
+    pub origin: String,
+    pub recovery_addr: Addr,
+    pub amount: Option<Coin>,
+    pub status: PacketStatus,
+}
+
+pub struct PacketRecordIndices<'a> {
+    /// PK: channel + sequence
+    /// Secondary key: origin
+    pub origin: MultiIndex<'a, String, PacketRecord, (String, u64)>,
+}
+
+impl<'a> IndexList<PacketRecord> for PacketRecordIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PacketRecord>> + '_> {
+        let v: Vec<&dyn Index<PacketRecord>> = vec![&self.origin];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn packet_records<'a>() -> IndexedMap<'a, (&'a str, u64), PacketRecord, PacketRecordIndices<'a>>
+{
+    let indexes = PacketRecordIndices {
+        origin: MultiIndex::new(
+            |_pk: &[u8], r| r.origin.clone(),
+            "packet_records",
+            "packet_records_origin",
+        ),
+    };
+    IndexedMap::new("packet_records", indexes)
 }
 
 pub const KERNEL_ADDRESSES: Map<&str, Addr> = Map::new("kernel_addresses");
-pub const _ENV_VARIABLES: Map<&str, String> = Map::new("kernel_env_variables");
+pub const ENV_VARIABLES: Map<&str, String> = Map::new("kernel_env_variables");
 pub const CURR_CHAIN: Item<String> = Item::new("kernel_curr_chain");
 
 //Temporary storage for creating a new ADO to assign a new owner
@@ -33,6 +93,125 @@ pub const CHANNEL_TO_CHAIN: Map<&str, String> = Map::new("kernel_channel_name");
 /// Removed when a reply is received for the packet
 pub const OUTGOING_IBC_HOOKS_PACKETS: Item<Vec<IBCHooksPacketSendState>> =
     Item::new("OUTGOING_IBC_HOOKS_PACKETS");
+/// Used to store the most recent outgoing direct channel packets
+///
+/// Removed when a reply is received for the packet
+pub const OUTGOING_IBC_DIRECT_PACKETS: Item<Vec<IBCDirectPacketSendState>> =
+    Item::new("OUTGOING_IBC_DIRECT_PACKETS");
 pub const OUTGOING_IBC_PACKETS: Map<(&String, u64), OutgoingPacket> =
     Map::new("outgoing_ibc_packets");
 pub const IBC_FUND_RECOVERY: Map<&Addr, Vec<Coin>> = Map::new("ibc_fund_recovery");
+
+/// Used to store the batch messages awaiting a reply
+///
+/// Messages of nested batches are placed at the front of the queue as their replies are received before those of the outer batch
+pub const AMP_BATCH_MSGS: Item<Vec<AMPBatchMsgState>> = Item::new("amp_batch_msgs");
+/// The outcomes of each batch with messages awaiting a reply, removed once all replies are received
+pub const AMP_BATCH_OUTCOMES: Map<u64, Vec<AMPMsgOutcome>> = Map::new("amp_batch_outcomes");
+pub const AMP_BATCH_COUNT: Item<u64> = Item::new("amp_batch_count");
+
+/// The ID of the most recent packet relayed by the kernel, an ID of 0 denotes a packet that has not been relayed by the kernel
+pub const CURR_PACKET_ID: Item<u64> = Item::new("kernel_curr_packet_id");
+/// The recipient of each packet relayed locally by the kernel by hop sequence, used to verify the IDs of packets forwarded back to the kernel
+pub const PACKET_RECIPIENTS: Map<u64, Addr> = Map::new("kernel_packet_recipients");
+/// The hop sequences received from each chain, used to reject duplicate packets
+pub const RECEIVED_PACKET_IDS: Map<(&str, u64), bool> = Map::new("kernel_received_packet_ids");
+
+/// Generates a new unique packet ID
+pub fn generate_packet_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = CURR_PACKET_ID.may_load(storage)?.unwrap_or_default() + 1;
+    CURR_PACKET_ID.save(storage, &id)?;
+    Ok(id)
+}
+
+/// Assigns a new hop sequence to the given context, packets that have not been relayed by a kernel are also assigned it as their ID
+pub fn assign_packet_id(storage: &mut dyn Storage, ctx: &mut AMPCtx) -> StdResult<u64> {
+    let hop_sequence = generate_packet_id(storage)?;
+    ctx.hop_sequence = hop_sequence;
+    if ctx.id == 0 {
+        ctx.id = hop_sequence;
+    }
+    Ok(hop_sequence)
+}
+
+/// Removes the batch message at the front of the queue and records its outcome if it failed
+///
+/// Returns the removed message along with the outcomes of its batch, which are removed once no further messages of the batch remain
+pub fn pop_amp_batch_msg(
+    storage: &mut dyn Storage,
+    error: Option<String>,
+) -> StdResult<(AMPBatchMsgState, Vec<AMPMsgOutcome>)> {
+    let mut batch_msgs = AMP_BATCH_MSGS.may_load(storage)?.unwrap_or_default();
+    if batch_msgs.is_empty() {
+        return Err(StdError::generic_err("amp batch: no pending messages"));
+    }
+    let batch_msg = batch_msgs.remove(0);
+    AMP_BATCH_MSGS.save(storage, &batch_msgs)?;
+
+    let mut outcomes = AMP_BATCH_OUTCOMES.load(storage, batch_msg.batch_id)?;
+    if let Some(error) = error {
+        outcomes[batch_msg.index as usize] = AMPMsgOutcome::Failed { error };
+    }
+    if batch_msgs
+        .first()
+        .map_or(true, |next| next.batch_id != batch_msg.batch_id)
+    {
+        AMP_BATCH_OUTCOMES.remove(storage, batch_msg.batch_id);
+    } else {
+        AMP_BATCH_OUTCOMES.save(storage, batch_msg.batch_id, &outcomes)?;
+    }
+
+    Ok((batch_msg, outcomes))
+}
+
+/// Creates an `andr_packet_<kind>` event for indexers following the lifecycle of an outgoing packet
+///
+/// Events for every stage of the lifecycle share the same keys, identifying the packet by its channel and sequence
+pub fn packet_event(kind: &str, channel_id: &str, sequence: u64, packet: &OutgoingPacket) -> Event {
+    let mut event = Event::new(format!("andr_packet_{kind}")).add_attributes(vec![
+        ("channel", channel_id.to_string()),
+        ("sequence", sequence.to_string()),
+        ("recovery_addr", packet.recovery_addr.to_string()),
+    ]);
+    if let Some(origin) = &packet.origin {
+        event = event.add_attribute("origin", origin);
+    }
+    if let Some(amount) = &packet.amount {
+        event = event.add_attribute("amount", amount.to_string());
+    }
+    event
+}
+
+/// Stores an outgoing packet as in-flight and records it as pending
+pub fn save_outgoing_packet(
+    storage: &mut dyn Storage,
+    channel_id: &String,
+    sequence: u64,
+    packet: &OutgoingPacket,
+) -> StdResult<()> {
+    OUTGOING_IBC_PACKETS.save(storage, (channel_id, sequence), packet)?;
+    packet_records().save(
+        storage,
+        (channel_id, sequence),
+        &PacketRecord {
+            origin: packet.origin.clone().unwrap_or_default(),
+            recovery_addr: packet.recovery_addr.clone(),
+            amount: packet.amount.clone(),
+            status: PacketStatus::Pending,
+        },
+    )
+}
+
+/// Updates the status of a recorded packet, packets sent before records were kept are ignored
+pub fn update_packet_status(
+    storage: &mut dyn Storage,
+    channel_id: &str,
+    sequence: u64,
+    status: PacketStatus,
+) -> StdResult<()> {
+    let Some(mut record) = packet_records().may_load(storage, (channel_id, sequence))? else {
+        return Ok(());
+    };
+    record.status = status;
+    packet_records().save(storage, (channel_id, sequence), &record)
+}
@@ -4,9 +4,12 @@ use cosmwasm_std::{DepsMut, Response};
 pub mod ibc_lifecycle {
     // As with most IBC Hooks methods these were adapted from:
     // https://github.com/osmosis-labs/osmosis/blob/main/cosmwasm/contracts/crosschain-swaps/src/ibc_lifecycle.rs
-    use cosmwasm_std::Coin;
+    use andromeda_std::os::kernel::PacketStatus;
+    use cosmwasm_std::{Coin, Storage};
 
-    use crate::state::{OutgoingPacket, IBC_FUND_RECOVERY, OUTGOING_IBC_PACKETS};
+    use crate::state::{
+        packet_event, update_packet_status, OutgoingPacket, IBC_FUND_RECOVERY, OUTGOING_IBC_PACKETS,
+    };
 
     use super::*;
 
@@ -14,39 +17,55 @@ pub mod ibc_lifecycle {
         deps: DepsMut,
         source_channel: String,
         sequence: u64,
-        _ack: String,
+        ack: String,
         success: bool,
     ) -> Result<Response, ContractError> {
-        let response = Response::new().add_attribute("action", "receive_ack");
+        let response = Response::new()
+            .add_attribute("action", "receive_ack")
+            .add_attribute("channel", source_channel.clone())
+            .add_attribute("sequence", sequence.to_string());
 
         // Check if there is an inflight packet for the received (channel, sequence)
         let sent_packet =
             OUTGOING_IBC_PACKETS.may_load(deps.storage, (&source_channel, sequence))?;
         let Some(inflight_packet) = sent_packet else {
             // If there isn't, continue
-            return Ok(response.add_attribute("msg", "received unexpected ack"))
+            return Ok(response.add_attribute("msg", "received unexpected ack"));
         };
         OUTGOING_IBC_PACKETS.remove(deps.storage, (&source_channel, sequence));
 
         if success {
+            update_packet_status(
+                deps.storage,
+                &source_channel,
+                sequence,
+                PacketStatus::Acknowledged,
+            )?;
             // If the ack was successful, continue
-            return Ok(response.add_attribute("msg", "received successful ack"));
+            return Ok(response
+                .add_attribute("msg", "received successful ack")
+                .add_event(packet_event(
+                    "acknowledged",
+                    &source_channel,
+                    sequence,
+                    &inflight_packet,
+                )));
         };
+        update_packet_status(
+            deps.storage,
+            &source_channel,
+            sequence,
+            PacketStatus::Failed { error: ack.clone() },
+        )?;
 
-        let OutgoingPacket {
-            recovery_addr,
-            amount,
-        } = inflight_packet;
-        IBC_FUND_RECOVERY.update(deps.storage, &recovery_addr, |cur_amount_opt| {
-            let mut recoveries = cur_amount_opt.unwrap_or_default();
-            recoveries.push(amount.clone());
-            Ok::<Vec<Coin>, ContractError>(recoveries)
-        })?;
-
-        Ok(response
+        let response = response
             .add_attribute("msg", "msg failed")
-            .add_attribute("recovery_addr", recovery_addr)
-            .add_attribute("recovery_amount", amount.to_string()))
+            .add_attribute("error", ack.clone())
+            .add_event(
+                packet_event("failed", &source_channel, sequence, &inflight_packet)
+                    .add_attribute("error", ack),
+            );
+        recover_packet(deps.storage, inflight_packet, response)
     }
 
     pub fn receive_timeout(
@@ -54,30 +73,62 @@ pub mod ibc_lifecycle {
         source_channel: String,
         sequence: u64,
     ) -> Result<Response, ContractError> {
-        let response = Response::new().add_attribute("action", "receive_timeout");
+        let response = Response::new()
+            .add_attribute("action", "receive_timeout")
+            .add_attribute("channel", source_channel.clone())
+            .add_attribute("sequence", sequence.to_string());
 
         // Check if there is an inflight packet for the received (channel, sequence)
         let sent_packet =
             OUTGOING_IBC_PACKETS.may_load(deps.storage, (&source_channel, sequence))?;
         let Some(inflight_packet) = sent_packet else {
             // If there isn't, continue
-            return Ok(response.add_attribute("msg", "received unexpected timeout"))
+            return Ok(response.add_attribute("msg", "received unexpected timeout"));
         };
         // Remove the in-flight packet
         OUTGOING_IBC_PACKETS.remove(deps.storage, (&source_channel, sequence));
+        update_packet_status(
+            deps.storage,
+            &source_channel,
+            sequence,
+            PacketStatus::TimedOut,
+        )?;
+
+        let response = response.add_event(packet_event(
+            "timed_out",
+            &source_channel,
+            sequence,
+            &inflight_packet,
+        ));
+        recover_packet(deps.storage, inflight_packet, response)
+    }
 
+    /// Credits any funds sent with a failed packet to its recovery address
+    fn recover_packet(
+        storage: &mut dyn Storage,
+        packet: OutgoingPacket,
+        response: Response,
+    ) -> Result<Response, ContractError> {
         let OutgoingPacket {
             recovery_addr,
             amount,
-        } = inflight_packet;
-        IBC_FUND_RECOVERY.update(deps.storage, &recovery_addr, |cur_amount_opt| {
+            origin,
+            ..
+        } = packet;
+        let mut response = response.add_attribute("recovery_addr", recovery_addr.clone());
+        if let Some(origin) = origin {
+            response = response.add_attribute("origin", origin);
+        }
+        let Some(amount) = amount else {
+            return Ok(response);
+        };
+
+        IBC_FUND_RECOVERY.update(storage, &recovery_addr, |cur_amount_opt| {
             let mut recoveries = cur_amount_opt.unwrap_or_default();
             recoveries.push(amount.clone());
             Ok::<Vec<Coin>, ContractError>(recoveries)
         })?;
 
-        Ok(response
-            .add_attribute("recovery_addr", recovery_addr)
-            .add_attribute("recovery_amount", amount.to_string()))
+        Ok(response.add_attribute("recovery_amount", amount.to_string()))
     }
 }
@@ -1,20 +1,41 @@
 use crate::{
-    contract::{execute, instantiate},
-    ibc::PACKET_LIFETIME,
-    state::{ADO_OWNER, CHAIN_TO_CHANNEL, KERNEL_ADDRESSES},
+    ack::{make_ack_fail, make_ack_success},
+    contract::{execute, instantiate, query, reply},
+    ibc::{ibc_packet_ack, ibc_packet_receive, ibc_packet_timeout, PACKET_LIFETIME},
+    proto::{MsgIBCSendResponse, MsgTransfer},
+    reply::ReplyId,
+    state::{
+        save_outgoing_packet, OutgoingPacket, ADO_OWNER, AMP_BATCH_MSGS, AMP_BATCH_OUTCOMES,
+        CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, IBC_FUND_RECOVERY, KERNEL_ADDRESSES,
+        OUTGOING_IBC_DIRECT_PACKETS, OUTGOING_IBC_HOOKS_PACKETS, OUTGOING_IBC_PACKETS,
+    },
 };
 use andromeda_std::{
-    amp::{ADO_DB_KEY, VFS_KEY},
+    amp::{
+        messages::{AMPCtx, AMPMsg, AMPMsgConfig, AMPPkt, IBCConfig, ICS20Memo},
+        AndrAddr, ADO_DB_KEY, VFS_KEY,
+    },
     error::ContractError,
-    os::kernel::{ChannelInfo, ExecuteMsg, IbcExecuteMsg, InstantiateMsg, InternalMsg},
+    os::kernel::{
+        AMPMsgOutcome, AMPPktResponse, ChannelInfo, ChannelInfoResponse, ExecuteMsg, IbcExecuteMsg,
+        InstantiateMsg, InternalMsg, PacketInfoResponse, PacketStatus, QueryMsg,
+    },
+    os::vfs::ExecuteMsg as VFSExecuteMsg,
     testing::mock_querier::{
-        mock_dependencies_custom, MOCK_ADODB_CONTRACT, MOCK_FAKE_KERNEL_CONTRACT, MOCK_VFS_CONTRACT,
+        mock_dependencies_custom, MOCK_ADODB_CONTRACT, MOCK_DEPRECATED_ADODB_KEY,
+        MOCK_FAKE_KERNEL_CONTRACT, MOCK_VFS_CONTRACT, MOCK_YANKED_ADODB_KEY,
     },
 };
 use cosmwasm_std::{
-    testing::{mock_dependencies, mock_env, mock_info},
-    to_binary, Addr, Binary, CosmosMsg, IbcMsg,
+    attr, coin, from_binary,
+    testing::{
+        mock_dependencies, mock_env, mock_ibc_packet_ack, mock_ibc_packet_recv,
+        mock_ibc_packet_timeout, mock_info, MOCK_CONTRACT_ADDR,
+    },
+    to_binary, Addr, BankMsg, Binary, CosmosMsg, Event, IbcAcknowledgement, IbcMsg, Reply, ReplyOn,
+    SubMsgResponse, SubMsgResult, WasmMsg,
 };
+use prost::Message;
 
 #[test]
 fn proper_initialization() {
@@ -68,6 +89,61 @@ fn test_create_ado() {
     assert_eq!(ADO_OWNER.load(deps.as_ref().storage).unwrap(), info.sender);
 }
 
+#[test]
+fn test_create_ado_version_status() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("creator", &[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "test".to_string(),
+        },
+    )
+    .unwrap();
+
+    let assign_key_msg = ExecuteMsg::UpsertKeyAddress {
+        key: ADO_DB_KEY.to_string(),
+        value: MOCK_ADODB_CONTRACT.to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), assign_key_msg).unwrap();
+    let assign_key_msg = ExecuteMsg::UpsertKeyAddress {
+        key: VFS_KEY.to_string(),
+        value: MOCK_VFS_CONTRACT.to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), assign_key_msg).unwrap();
+
+    let create_msg = ExecuteMsg::Create {
+        ado_type: MOCK_DEPRECATED_ADODB_KEY.to_string(),
+        msg: Binary::default(),
+        owner: None,
+        chain: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), create_msg).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert!(res.attributes.contains(&attr(
+        "warning",
+        format!("{MOCK_DEPRECATED_ADODB_KEY}@0.1.0 is deprecated: Use a newer version")
+    )));
+
+    let create_msg = ExecuteMsg::Create {
+        ado_type: MOCK_YANKED_ADODB_KEY.to_string(),
+        msg: Binary::default(),
+        owner: None,
+        chain: None,
+    };
+    let err = execute(deps.as_mut(), env, info, create_msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ADOVersionYanked {
+            version: format!("{MOCK_YANKED_ADODB_KEY}@0.1.0")
+        }
+    );
+}
+
 #[test]
 fn test_register_user_cross_chain() {
     let mut deps = mock_dependencies_custom(&[]);
@@ -97,6 +173,7 @@ fn test_register_user_cross_chain() {
         ics20_channel_id: Some("1".to_string()),
         direct_channel_id: Some("2".to_string()),
         supported_modules: vec![],
+        packet_timeout: None,
     };
     CHAIN_TO_CHANNEL
         .save(deps.as_mut().storage, chain, &channel_info)
@@ -127,4 +204,1068 @@ fn test_register_user_cross_chain() {
     };
 
     assert_eq!(res.messages.first().unwrap().msg, CosmosMsg::Ibc(expected));
+    assert_eq!(
+        res.messages.first().unwrap().id,
+        ReplyId::IBCDirectPacketSend.repr()
+    );
+    let outgoing = OUTGOING_IBC_DIRECT_PACKETS
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(outgoing.len(), 1);
+    assert_eq!(outgoing[0].chain, chain);
+}
+
+#[test]
+fn test_register_username_ack() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let channel = "channel-1";
+    let chain = "chain";
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    CHANNEL_TO_CHAIN
+        .save(deps.as_mut().storage, channel, &chain.to_string())
+        .unwrap();
+    let packet_data = IbcExecuteMsg::RegisterUsername {
+        username: "username".to_string(),
+        address: "address".to_string(),
+    };
+    let expected_msg = |error: Option<String>| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_VFS_CONTRACT.to_string(),
+            msg: to_binary(&VFSExecuteMsg::CompleteUserCrossChain {
+                username: "username".to_string(),
+                chain: chain.to_string(),
+                error,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+
+    let msg = mock_ibc_packet_ack(
+        channel,
+        &packet_data,
+        IbcAcknowledgement::new(make_ack_success()),
+    )
+    .unwrap();
+    let res = ibc_packet_ack(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(res.messages[0].msg, expected_msg(None));
+
+    let msg = mock_ibc_packet_ack(
+        channel,
+        &packet_data,
+        IbcAcknowledgement::new(make_ack_fail("Unauthorized".to_string())),
+    )
+    .unwrap();
+    let res = ibc_packet_ack(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        expected_msg(Some("Unauthorized".to_string()))
+    );
+
+    let msg = mock_ibc_packet_timeout(channel, &packet_data).unwrap();
+    let res = ibc_packet_timeout(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        expected_msg(Some("Packet timed out".to_string()))
+    );
+
+    // The chain recorded when the packet was sent is used even if the channel is no longer assigned
+    CHANNEL_TO_CHAIN.remove(deps.as_mut().storage, channel);
+    // mock_ibc_packet_timeout always uses sequence 29
+    OUTGOING_IBC_PACKETS
+        .save(
+            deps.as_mut().storage,
+            (&channel.to_string(), 29),
+            &OutgoingPacket {
+                recovery_addr: Addr::unchecked(MOCK_VFS_CONTRACT),
+                amount: None,
+                origin: Some(MOCK_VFS_CONTRACT.to_string()),
+                chain: Some(chain.to_string()),
+            },
+        )
+        .unwrap();
+    let msg = mock_ibc_packet_timeout(channel, &packet_data).unwrap();
+    let res = ibc_packet_timeout(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        expected_msg(Some("Packet timed out".to_string()))
+    );
+    assert!(!OUTGOING_IBC_PACKETS.has(deps.as_ref().storage, (&channel.to_string(), 29)));
+
+    // Registrations whose chain cannot be determined are left for the VFS owner to clear
+    let msg = mock_ibc_packet_timeout(channel, &packet_data).unwrap();
+    let res = ibc_packet_timeout(deps.as_mut(), env.clone(), msg).unwrap();
+    assert!(res.messages.is_empty());
+
+    // Failed registrations on the receiving chain are returned as an error acknowledgement
+    let reply_msg = Reply {
+        id: ReplyId::RegisterUsername.repr(),
+        result: SubMsgResult::Err("Unauthorized".to_string()),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert_eq!(res.data, Some(make_ack_fail("Unauthorized".to_string())));
+}
+
+#[test]
+fn test_send_ibc_direct() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("creator", &[]);
+    let env = mock_env();
+    let chain = "chain";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    let channel_info = ChannelInfo {
+        kernel_address: MOCK_FAKE_KERNEL_CONTRACT.to_string(),
+        ics20_channel_id: Some("1".to_string()),
+        direct_channel_id: Some("2".to_string()),
+        supported_modules: vec![],
+        packet_timeout: None,
+    };
+    CHAIN_TO_CHANNEL
+        .save(deps.as_mut().storage, chain, &channel_info)
+        .unwrap();
+
+    let message = to_binary(&"message").unwrap();
+    let msg = ExecuteMsg::Send {
+        message: AMPMsg::new(
+            format!("ibc://{chain}/home/user/app"),
+            message.clone(),
+            None,
+        ),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let expected = IbcMsg::SendPacket {
+        channel_id: "2".to_string(),
+        data: to_binary(&IbcExecuteMsg::SendMessageWithContext {
+            message: Box::new(AMPMsg::new("/home/user/app", message, None)),
+            context: relayed_ctx("creator", MOCK_CONTRACT_ADDR, 1, 1),
+        })
+        .unwrap(),
+        timeout: env.block.time.plus_seconds(PACKET_LIFETIME).into(),
+    };
+    assert_eq!(res.messages[0].msg, CosmosMsg::Ibc(expected));
+    assert_eq!(res.messages[0].id, ReplyId::IBCDirectPacketSend.repr());
+    assert_eq!(
+        OUTGOING_IBC_DIRECT_PACKETS
+            .load(deps.as_ref().storage)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let sequence = 5u64;
+    let reply_msg = Reply {
+        id: ReplyId::IBCDirectPacketSend.repr(),
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(Binary(MsgIBCSendResponse { sequence }.encode_to_vec())),
+        }),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("andr_packet_sent").add_attributes(vec![
+            ("channel", "2"),
+            ("sequence", "5"),
+            ("recovery_addr", "creator"),
+            ("origin", "creator"),
+        ])]
+    );
+
+    assert!(OUTGOING_IBC_DIRECT_PACKETS
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+    let packet = OUTGOING_IBC_PACKETS
+        .load(deps.as_ref().storage, (&"2".to_string(), sequence))
+        .unwrap();
+    assert_eq!(
+        packet,
+        OutgoingPacket {
+            recovery_addr: Addr::unchecked("creator"),
+            amount: None,
+            origin: Some("creator".to_string()),
+            chain: Some(chain.to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_ibc_packet_ack() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let channel = "channel-1".to_string();
+    let recovery_addr = Addr::unchecked("recovery_addr");
+    let packet = OutgoingPacket {
+        recovery_addr: recovery_addr.clone(),
+        amount: Some(coin(100, "uandr")),
+        origin: Some("origin".to_string()),
+        chain: None,
+    };
+    // mock_ibc_packet_ack always uses sequence 29
+    OUTGOING_IBC_PACKETS
+        .save(deps.as_mut().storage, (&channel, 29), &packet)
+        .unwrap();
+
+    let msg = mock_ibc_packet_ack(
+        &channel,
+        &"data",
+        IbcAcknowledgement::new(make_ack_success()),
+    )
+    .unwrap();
+    let res = ibc_packet_ack(deps.as_mut(), env.clone(), msg).unwrap();
+    assert!(!OUTGOING_IBC_PACKETS.has(deps.as_ref().storage, (&channel, 29)));
+    assert!(!IBC_FUND_RECOVERY.has(deps.as_ref().storage, &recovery_addr));
+    let packet_event = |kind: &str| {
+        Event::new(format!("andr_packet_{kind}")).add_attributes(vec![
+            ("channel", "channel-1"),
+            ("sequence", "29"),
+            ("recovery_addr", "recovery_addr"),
+            ("origin", "origin"),
+            ("amount", "100uandr"),
+        ])
+    };
+    assert_eq!(res.events, vec![packet_event("acknowledged")]);
+
+    OUTGOING_IBC_PACKETS
+        .save(deps.as_mut().storage, (&channel, 29), &packet)
+        .unwrap();
+    let msg = mock_ibc_packet_ack(
+        &channel,
+        &"data",
+        IbcAcknowledgement::new(make_ack_fail("error".to_string())),
+    )
+    .unwrap();
+    let res = ibc_packet_ack(deps.as_mut(), env, msg).unwrap();
+    assert!(!OUTGOING_IBC_PACKETS.has(deps.as_ref().storage, (&channel, 29)));
+    assert_eq!(
+        IBC_FUND_RECOVERY
+            .load(deps.as_ref().storage, &recovery_addr)
+            .unwrap(),
+        vec![coin(100, "uandr")]
+    );
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "error" && attr.value == "error"));
+    assert_eq!(
+        res.events,
+        vec![packet_event("failed").add_attribute("error", "error")]
+    );
+}
+
+#[test]
+fn test_ibc_packet_timeout() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let channel = "channel-1".to_string();
+    let recovery_addr = Addr::unchecked("recovery_addr");
+    // mock_ibc_packet_timeout always uses sequence 29
+    OUTGOING_IBC_PACKETS
+        .save(
+            deps.as_mut().storage,
+            (&channel, 29),
+            &OutgoingPacket {
+                recovery_addr: recovery_addr.clone(),
+                amount: Some(coin(100, "uandr")),
+                origin: Some("origin".to_string()),
+                chain: None,
+            },
+        )
+        .unwrap();
+
+    let msg = mock_ibc_packet_timeout(&channel, &"data").unwrap();
+    let res = ibc_packet_timeout(deps.as_mut(), env, msg).unwrap();
+    assert!(!OUTGOING_IBC_PACKETS.has(deps.as_ref().storage, (&channel, 29)));
+    assert_eq!(
+        res.events,
+        vec![Event::new("andr_packet_timed_out").add_attributes(vec![
+            ("channel", "channel-1"),
+            ("sequence", "29"),
+            ("recovery_addr", "recovery_addr"),
+            ("origin", "origin"),
+            ("amount", "100uandr"),
+        ])]
+    );
+    assert_eq!(
+        IBC_FUND_RECOVERY
+            .load(deps.as_ref().storage, &recovery_addr)
+            .unwrap(),
+        vec![coin(100, "uandr")]
+    );
+}
+
+#[test]
+fn test_packet_status() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let channel = "channel-1".to_string();
+    let packet = OutgoingPacket {
+        recovery_addr: Addr::unchecked("origin"),
+        amount: Some(coin(100, "uandr")),
+        origin: Some("origin".to_string()),
+        chain: None,
+    };
+    // mock_ibc_packet_ack always uses sequence 29
+    for sequence in [28, 29] {
+        save_outgoing_packet(deps.as_mut().storage, &channel, sequence, &packet).unwrap();
+    }
+
+    let query_status = |deps: cosmwasm_std::Deps, sequence: u64| -> Option<PacketInfoResponse> {
+        let msg = QueryMsg::PacketStatus {
+            channel: channel.clone(),
+            sequence,
+        };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    };
+    assert_eq!(
+        query_status(deps.as_ref(), 29).unwrap().status,
+        PacketStatus::Pending
+    );
+    assert!(query_status(deps.as_ref(), 30).is_none());
+
+    let msg = mock_ibc_packet_ack(
+        &channel,
+        &"data",
+        IbcAcknowledgement::new(make_ack_fail("error".to_string())),
+    )
+    .unwrap();
+    ibc_packet_ack(deps.as_mut(), env, msg).unwrap();
+    assert_eq!(
+        query_status(deps.as_ref(), 29).unwrap().status,
+        PacketStatus::Failed {
+            error: "error".to_string()
+        }
+    );
+
+    let msg = QueryMsg::PacketsByOrigin {
+        origin: "origin".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res: Vec<PacketInfoResponse> =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].sequence, 28);
+    assert_eq!(res[0].status, PacketStatus::Pending);
+
+    let msg = QueryMsg::PacketsByOrigin {
+        origin: "origin".to_string(),
+        start_after: Some((channel.clone(), 28)),
+        limit: None,
+    };
+    let res: Vec<PacketInfoResponse> =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].sequence, 29);
+
+    let msg = QueryMsg::PacketsByOrigin {
+        origin: "other".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res: Vec<PacketInfoResponse> =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_send_ibc_multi_hop() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("creator", &[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    CHAIN_TO_CHANNEL
+        .save(
+            deps.as_mut().storage,
+            "chain2",
+            &ChannelInfo {
+                kernel_address: MOCK_FAKE_KERNEL_CONTRACT.to_string(),
+                ics20_channel_id: Some("1".to_string()),
+                direct_channel_id: Some("2".to_string()),
+                supported_modules: vec![],
+                packet_timeout: None,
+            },
+        )
+        .unwrap();
+
+    let message = to_binary(&"message").unwrap();
+    let config = AMPMsgConfig::new(
+        None,
+        None,
+        None,
+        Some(IBCConfig::new(None).with_route(vec!["chain2".to_string()])),
+    );
+    let amp_msg =
+        AMPMsg::new("ibc://chain3/home/user/app", message.clone(), None).with_config(config);
+
+    // No channel exists for the destination chain
+    let msg = ExecuteMsg::Send {
+        message: AMPMsg::new("ibc://chain3/home/user/app", message.clone(), None),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Channel not found for chain chain3".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::Send {
+        message: amp_msg.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let mut forwarded = amp_msg;
+    forwarded.config.ibc_config = Some(IBCConfig::new(None));
+    let expected = IbcMsg::SendPacket {
+        channel_id: "2".to_string(),
+        data: to_binary(&IbcExecuteMsg::SendMessageWithContext {
+            message: Box::new(forwarded),
+            context: relayed_ctx("creator", MOCK_CONTRACT_ADDR, 1, 1),
+        })
+        .unwrap(),
+        timeout: env.block.time.plus_seconds(PACKET_LIFETIME).into(),
+    };
+    assert_eq!(res.messages[0].msg, CosmosMsg::Ibc(expected));
+}
+
+#[test]
+fn test_receive_message_with_context() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("creator", &[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    CHANNEL_TO_CHAIN
+        .save(deps.as_mut().storage, "channel-2", &"chain2".to_string())
+        .unwrap();
+
+    let message = to_binary(&VFSExecuteMsg::AddPath {
+        name: "name".to_string(),
+        address: Addr::unchecked("address"),
+        parent_address: None,
+    })
+    .unwrap();
+    let origin_username = Some(AndrAddr::from_string("user"));
+    let mut context = AMPCtx::new("origin", "previous_sender", 5, origin_username.clone());
+    context.hop_sequence = 7;
+    let packet = IbcExecuteMsg::SendMessageWithContext {
+        message: Box::new(AMPMsg::new("recipient", message.clone(), None)),
+        context,
+    };
+    let msg = mock_ibc_packet_recv("channel-2", &packet).unwrap();
+    let res = ibc_packet_receive(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(res.acknowledgement, make_ack_success());
+
+    // The receiving ADO is provided the origin packet ID along with a new hop sequence by the local kernel
+    let mut ctx = AMPCtx::new("origin", "foreign_kernel", 5, origin_username);
+    ctx.hop_sequence = 1;
+    let expected = AMPPkt {
+        messages: vec![AMPMsg::new("recipient", message, Some(vec![]))],
+        ctx,
+    };
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "recipient".to_string(),
+            msg: to_binary(&ExecuteMsg::AMPReceive(expected)).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "received_packet_id" && attr.value == "5"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "received_hop_sequence" && attr.value == "7"));
+
+    // Duplicate packets are rejected
+    let msg = mock_ibc_packet_recv("channel-2", &packet).unwrap();
+    let res = ibc_packet_receive(deps.as_mut(), env, msg).unwrap();
+    let err = ContractError::InvalidPacket {
+        error: Some("Packet 7 from chain2 has already been received".to_string()),
+    };
+    assert_eq!(res.acknowledgement, make_ack_fail(err.to_string()));
+}
+
+#[test]
+fn test_env_variables() {
+    let mut deps = mock_dependencies();
+    let info = mock_info("creator", &[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+
+    let get_env = |deps: cosmwasm_std::Deps| -> Option<String> {
+        let msg = QueryMsg::GetEnv {
+            variable: "PACKET_LIFETIME".to_string(),
+        };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    };
+    assert!(get_env(deps.as_ref()).is_none());
+
+    let msg = ExecuteMsg::SetEnv {
+        variable: "PACKET_LIFETIME".to_string(),
+        value: "60".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(get_env(deps.as_ref()), Some("60".to_string()));
+
+    let msg = ExecuteMsg::SetEnv {
+        variable: "INVALID-VARIABLE".to_string(),
+        value: "60".to_string(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidEnvironmentVariable {
+            msg: "INVALID-VARIABLE contains invalid characters".to_string()
+        }
+    );
+
+    let msg = ExecuteMsg::UnsetEnv {
+        variable: "PACKET_LIFETIME".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert!(get_env(deps.as_ref()).is_none());
+
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidEnvironmentVariable {
+            msg: "PACKET_LIFETIME is not set".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_packet_timeout() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("creator", &[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+
+    let msg = ExecuteMsg::AssignChannels {
+        ics20_channel_id: Some("1".to_string()),
+        direct_channel_id: Some("2".to_string()),
+        chain: "chain2".to_string(),
+        kernel_address: MOCK_FAKE_KERNEL_CONTRACT.to_string(),
+        packet_timeout: Some(0),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Packet timeout must be greater than zero".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::AssignChannels {
+        ics20_channel_id: Some("1".to_string()),
+        direct_channel_id: Some("2".to_string()),
+        chain: "chain2".to_string(),
+        kernel_address: MOCK_FAKE_KERNEL_CONTRACT.to_string(),
+        packet_timeout: Some(300),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let message = to_binary(&"message").unwrap();
+
+    // Uses the default timeout of the chain
+    let msg = ExecuteMsg::Send {
+        message: AMPMsg::new("ibc://chain2/home/user/app", message.clone(), None),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let CosmosMsg::Ibc(IbcMsg::SendPacket { timeout, .. }) = &res.messages[0].msg else {
+        panic!("Expected an IBC packet")
+    };
+    assert_eq!(timeout, &env.block.time.plus_seconds(300).into());
+
+    // The message's timeout overrides the default timeout of the chain
+    let config = AMPMsgConfig::new(
+        None,
+        None,
+        None,
+        Some(IBCConfig::new(None).with_timeout(60)),
+    );
+    let msg = ExecuteMsg::Send {
+        message: AMPMsg::new("ibc://chain2/home/user/app", message, None).with_config(config),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let CosmosMsg::Ibc(IbcMsg::SendPacket { timeout, .. }) = &res.messages[0].msg else {
+        panic!("Expected an IBC packet")
+    };
+    assert_eq!(timeout, &env.block.time.plus_seconds(60).into());
+
+    let res: Option<ChannelInfoResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::ChannelInfo {
+                chain: "chain2".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.unwrap().packet_timeout, Some(300));
+}
+
+#[test]
+fn test_amp_receive_batch() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            ADO_DB_KEY,
+            &Addr::unchecked(MOCK_ADODB_CONTRACT),
+        )
+        .unwrap();
+
+    let message = to_binary(&"message").unwrap();
+    let non_atomic = AMPMsgConfig::new(None, Some(false), None, None);
+    let packet = AMPPkt::new(
+        "sender",
+        "sender",
+        vec![
+            AMPMsg::new(
+                "recipient1",
+                message.clone(),
+                Some(vec![coin(100, "uandr")]),
+            ),
+            AMPMsg::new("recipient2", message.clone(), Some(vec![coin(50, "uandr")]))
+                .with_config(non_atomic.clone()),
+            AMPMsg::new(
+                "ibc://unknown/home/user",
+                message.clone(),
+                Some(vec![coin(10, "uandr")]),
+            )
+            .with_config(AMPMsgConfig::new(
+                None,
+                Some(false),
+                None,
+                Some(IBCConfig::new(Some(AndrAddr::from_string("recovery")))),
+            )),
+        ],
+    );
+    let packet_msgs = packet.messages.clone();
+    let info = mock_info("sender", &[coin(160, "uandr")]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::AMPReceive(packet),
+    )
+    .unwrap();
+
+    // Only the atomic message is handled inline
+    assert_eq!(
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == "packet_id:0")
+            .unwrap()
+            .value,
+        "1"
+    );
+
+    // Non atomic messages are dispatched to the kernel itself so that their failure only reverts their own state
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(res.messages[0].id, ReplyId::AMPMsg.repr());
+    for (sub_msg, (index, funds)) in res.messages[1..]
+        .iter()
+        .zip([(1u64, coin(50, "uandr")), (2u64, coin(10, "uandr"))])
+    {
+        assert_eq!(sub_msg.id, ReplyId::AMPBatchMsg.repr());
+        assert_eq!(sub_msg.reply_on, ReplyOn::Error);
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds: sent_funds,
+        }) = &sub_msg.msg
+        else {
+            panic!("Expected a wasm execute message");
+        };
+        assert_eq!(contract_addr, MOCK_CONTRACT_ADDR);
+        assert_eq!(sent_funds, &vec![funds]);
+        let ExecuteMsg::Internal(InternalMsg::AMPBatchMsg {
+            sender, index: idx, ..
+        }) = from_binary(msg).unwrap()
+        else {
+            panic!("Expected a batch message");
+        };
+        assert_eq!(sender, Addr::unchecked("sender"));
+        assert_eq!(idx, index);
+    }
+    let data: AMPPktResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(data.outcomes, vec![AMPMsgOutcome::Success; 3]);
+    assert_eq!(AMP_BATCH_MSGS.load(deps.as_ref().storage).unwrap().len(), 2);
+
+    // Batch messages can only be handled by the kernel itself
+    let batch_msg = |index: usize| {
+        ExecuteMsg::Internal(InternalMsg::AMPBatchMsg {
+            sender: Addr::unchecked("sender"),
+            ctx: Some(AMPCtx::new("sender", "sender", 0, None)),
+            message: Box::new(packet_msgs[index].clone()),
+            index: index as u64,
+        })
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[coin(50, "uandr")]),
+        batch_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let kernel_info = mock_info(MOCK_CONTRACT_ADDR, &[coin(50, "uandr")]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        kernel_info.clone(),
+        batch_msg(1),
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].id, ReplyId::AMPMsg.repr());
+    assert!(res.attributes.contains(&attr("packet_id:1", "2")));
+    assert_eq!(AMP_BATCH_MSGS.load(deps.as_ref().storage).unwrap().len(), 1);
+
+    let channel_error = ContractError::InvalidPacket {
+        error: Some("Channel not found for chain unknown".to_string()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), kernel_info, batch_msg(2)).unwrap_err();
+    assert_eq!(err, channel_error);
+
+    // The failed batch message is recorded and refunded to its recovery address
+    let reply_msg = Reply {
+        id: ReplyId::AMPBatchMsg.repr(),
+        result: SubMsgResult::Err(channel_error.to_string()),
+    };
+    let res = reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recovery".to_string(),
+            amount: vec![coin(10, "uandr")],
+        })
+    );
+    let data: AMPPktResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        data.outcomes,
+        vec![
+            AMPMsgOutcome::Success,
+            AMPMsgOutcome::Success,
+            AMPMsgOutcome::Failed {
+                error: channel_error.to_string()
+            },
+        ]
+    );
+    assert!(AMP_BATCH_MSGS
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+    assert!(!AMP_BATCH_OUTCOMES.has(deps.as_ref().storage, 0));
+
+    // Packet IDs that have not been assigned by the kernel are rejected
+    let packet = AMPPkt::new(
+        "sender",
+        "sender",
+        vec![AMPMsg::new("recipient1", message.clone(), None)],
+    );
+    let mut forwarded = packet.with_id(1);
+    forwarded.ctx.hop_sequence = 1;
+    for sender in ["sender", "recipient2"] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(sender, &[]),
+            ExecuteMsg::AMPReceive(forwarded.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPacket {
+                error: Some("Packet ID cannot be provided from outside the Kernel".to_string())
+            }
+        );
+    }
+
+    // The recipient of a packet may forward it, keeping its ID
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("recipient1", &[]),
+        ExecuteMsg::AMPReceive(forwarded),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("packet_id:0", "1")));
+    assert!(res.attributes.contains(&attr("hop_sequence:0", "3")));
+
+    // A failed atomic message reverts the whole packet
+    let packet = AMPPkt::new(
+        "sender",
+        "sender",
+        vec![
+            AMPMsg::new("recipient1", message.clone(), None),
+            AMPMsg::new("ibc://unknown/home/user", message, None),
+        ],
+    );
+    let err = execute(deps.as_mut(), env, info, ExecuteMsg::AMPReceive(packet)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Channel not found for chain unknown".to_string())
+        }
+    );
+}
+
+/// The context of a packet relayed by a kernel with the given packet ID and hop sequence
+fn relayed_ctx(origin: &str, previous_sender: &str, id: u64, hop_sequence: u64) -> AMPCtx {
+    let mut ctx = AMPCtx::new(origin, previous_sender, id, None);
+    ctx.hop_sequence = hop_sequence;
+    ctx
+}
+
+#[test]
+fn test_send_ics20_transfer() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("creator", &[coin(100, "uandr")]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    // Chains without a kernel only require an ICS20 channel
+    CHAIN_TO_CHANNEL
+        .save(
+            deps.as_mut().storage,
+            "osmosis",
+            &ChannelInfo {
+                kernel_address: "".to_string(),
+                ics20_channel_id: Some("channel-1".to_string()),
+                direct_channel_id: None,
+                supported_modules: vec![],
+                packet_timeout: None,
+            },
+        )
+        .unwrap();
+    let timeout = env.block.time.plus_seconds(PACKET_LIFETIME).nanos();
+
+    // Executes the message on the recipient contract via ibc-hooks
+    let config = AMPMsgConfig::new(
+        None,
+        None,
+        None,
+        Some(IBCConfig::new(None).with_ics20_memo(ICS20Memo::IbcHooks)),
+    );
+    let message = AMPMsg::new(
+        "ibc://osmosis/osmo1contract",
+        Binary::from(r#"{"swap":{}}"#.as_bytes()),
+        Some(vec![coin(100, "uandr")]),
+    )
+    .with_config(config);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Send { message },
+    )
+    .unwrap();
+    let expected = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: "channel-1".to_string(),
+        token: Some(coin(100, "uandr").into()),
+        sender: MOCK_CONTRACT_ADDR.to_string(),
+        receiver: "osmo1contract".to_string(),
+        timeout_height: None,
+        timeout_timestamp: Some(timeout),
+        memo: format!(
+            r#"{{"wasm":{{"contract":"osmo1contract","msg":{{"swap":{{}}}}}},"ibc_callback":"{MOCK_CONTRACT_ADDR}"}}"#
+        ),
+    };
+    assert_eq!(res.messages[0].msg, CosmosMsg::from(expected));
+    assert_eq!(res.messages[0].id, ReplyId::IBCHooksPacketSend.repr());
+    assert_eq!(
+        OUTGOING_IBC_HOOKS_PACKETS
+            .load(deps.as_ref().storage)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Forwards the funds to the recipient using packet-forward-middleware
+    let config = AMPMsgConfig::new(
+        None,
+        None,
+        None,
+        Some(
+            IBCConfig::new(None).with_ics20_memo(ICS20Memo::PacketForward {
+                channel: "channel-5".to_string(),
+                port: None,
+            }),
+        ),
+    );
+    let message = AMPMsg::new(
+        "ibc://osmosis/cosmos1recipient",
+        Binary::default(),
+        Some(vec![coin(100, "uandr")]),
+    )
+    .with_config(config);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Send {
+            message: message.clone(),
+        },
+    )
+    .unwrap();
+    let expected = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: "channel-1".to_string(),
+        token: Some(coin(100, "uandr").into()),
+        sender: MOCK_CONTRACT_ADDR.to_string(),
+        receiver: "pfm".to_string(),
+        timeout_height: None,
+        timeout_timestamp: Some(timeout),
+        memo: format!(
+            r#"{{"forward":{{"receiver":"cosmos1recipient","port":"transfer","channel":"channel-5"}},"ibc_callback":"{MOCK_CONTRACT_ADDR}"}}"#
+        ),
+    };
+    assert_eq!(res.messages[0].msg, CosmosMsg::from(expected));
+
+    // Funds must be provided
+    let mut message = message;
+    message.funds = vec![];
+    let err = execute(deps.as_mut(), env, info, ExecuteMsg::Send { message }).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("A single coin must be provided to transfer via ICS20".to_string())
+        }
+    );
 }
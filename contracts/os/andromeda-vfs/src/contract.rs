@@ -10,7 +10,7 @@ use cosmwasm_std::{
 use cw2::{get_contract_version, set_contract_version};
 use semver::Version;
 
-use crate::{execute, query};
+use crate::{execute, query, state::index_symlink_targets};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:andromeda-vfs";
@@ -84,6 +84,39 @@ pub fn execute(
         ExecuteMsg::RegisterUserCrossChain { chain, address } => {
             execute::register_user_cross_chain(execute_env, chain, address)
         }
+        ExecuteMsg::CompleteUserCrossChain {
+            username,
+            chain,
+            error,
+        } => execute::complete_user_cross_chain(execute_env, username, chain, error),
+        ExecuteMsg::ClearUserCrossChain { username, chain } => {
+            execute::clear_user_cross_chain(execute_env, username, chain)
+        }
+        ExecuteMsg::RemovePath { path } => execute::remove_path(execute_env, path),
+        ExecuteMsg::RenamePath { path, name } => execute::rename_path(execute_env, path, name),
+        ExecuteMsg::MovePath { from, to } => execute::move_path(execute_env, from, to),
+        ExecuteMsg::TransferPathOwnership { path, new_owner } => {
+            execute::transfer_path_ownership(execute_env, path, new_owner)
+        }
+        ExecuteMsg::SetPathPermission {
+            path,
+            address,
+            access,
+        } => execute::set_path_permission(execute_env, path, address, access),
+        ExecuteMsg::RemovePathPermission { path, address } => {
+            execute::remove_path_permission(execute_env, path, address)
+        }
+        ExecuteMsg::TransferUsername {
+            username,
+            new_address,
+        } => execute::transfer_username(execute_env, username, new_address),
+        ExecuteMsg::ReleaseUsername { username } => {
+            execute::release_username(execute_env, username)
+        }
+        ExecuteMsg::RenewUsername { username } => execute::renew_username(execute_env, username),
+        ExecuteMsg::SetUsernameRegistrationDuration { duration } => {
+            execute::set_username_registration_duration(execute_env, duration)
+        }
     }
 }
 
@@ -115,6 +148,9 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // Symlinks added by previous versions are not yet indexed by their target
+    index_symlink_targets(deps.storage, deps.api)?;
+
     // Update the ADOContract's version
     contract.execute_update_version(deps)?;
 
@@ -130,9 +166,28 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
     match msg {
         QueryMsg::ResolvePath { path } => encode_binary(&query::resolve_path(deps, path)?),
         QueryMsg::SubDir { path } => encode_binary(&query::subdir(deps, path)?),
+        QueryMsg::ResolveGlob { pattern } => encode_binary(&query::glob(deps, pattern)?),
+        QueryMsg::Tree {
+            path,
+            depth,
+            start_after,
+            limit,
+        } => encode_binary(&query::tree(deps, path, depth, start_after, limit)?),
         QueryMsg::Paths { addr } => encode_binary(&query::paths(deps, addr)?),
         QueryMsg::GetUsername { address } => encode_binary(&query::get_username(deps, address)?),
         QueryMsg::GetLibrary { address } => encode_binary(&query::get_library_name(deps, address)?),
         QueryMsg::ResolveSymlink { path } => encode_binary(&query::get_symlink(deps, path)?),
+        QueryMsg::GetUsernameChains { username } => {
+            encode_binary(&query::get_username_chains(deps, username)?)
+        }
+        QueryMsg::GetUsernameExpiration { username } => {
+            encode_binary(&query::get_username_expiration(deps, username)?)
+        }
+        QueryMsg::PathPermissions { path } => encode_binary(&query::path_permissions(deps, path)?),
+        QueryMsg::ReverseLookup {
+            address,
+            start_after,
+            limit,
+        } => encode_binary(&query::reverse_lookup(deps, address, start_after, limit)?),
     }
 }
@@ -5,15 +5,21 @@ use andromeda_std::os::aos_querier::AOSQuerier;
 use andromeda_std::os::kernel::InternalMsg;
 use andromeda_std::os::{
     kernel::ExecuteMsg as KernelExecuteMsg,
-    vfs::{validate_component_name, validate_username},
+    vfs::{
+        validate_component_name, validate_username, CrossChainUsername, CrossChainUsernameStatus,
+        PathAccess,
+    },
 };
 use cosmwasm_std::{
-    attr, ensure, to_binary, Addr, DepsMut, Env, MessageInfo, Response, SubMsg, WasmMsg,
+    attr, ensure, to_binary, Addr, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+    SubMsg, WasmMsg,
 };
 
 use crate::state::{
-    add_path_symlink, add_pathname, paths, resolve_pathname, ADDRESS_LIBRARY, ADDRESS_USERNAME,
-    LIBRARIES, USERS,
+    add_path_symlink, add_pathname, get_path_key, has_path_access, move_directory, move_pathname,
+    paths, remove_pathname, resolve_pathname, split_parent_path, ADDRESS_LIBRARY, ADDRESS_USERNAME,
+    CROSS_CHAIN_USERNAMES, LIBRARIES, PATH_OWNERS, PATH_PERMISSIONS, USERNAME_EXPIRATIONS,
+    USERNAME_REGISTRATION_DURATION, USERS,
 };
 
 pub struct ExecuteEnv<'a> {
@@ -28,19 +34,28 @@ pub fn add_path(
     address: Addr,
     parent_address: Option<AndrAddr>,
 ) -> Result<Response, ContractError> {
-    let kernel_address = ADOContract::default().get_kernel_address(env.deps.storage)?;
+    let is_sender_dir = parent_address.is_none();
+    let parent_andr_addr = parent_address.unwrap_or(AndrAddr::from_string(env.info.sender.clone()));
+    let parent_addr = resolve_pathname(env.deps.storage, env.deps.api, parent_andr_addr)?;
     ensure!(
-        parent_address.is_none()
-            || env.info.sender == kernel_address
-            || ADOContract::default()
-                .is_contract_owner(env.deps.storage, env.info.sender.as_str())?,
+        is_sender_dir
+            || has_path_access(
+                env.deps.storage,
+                &parent_addr,
+                &env.info.sender,
+                PathAccess::Write
+            )?
+            || is_admin(&env)?,
         ContractError::Unauthorized {}
     );
-    let parent_andr_addr = parent_address.unwrap_or(AndrAddr::from_string(env.info.sender));
-    let parent_addr = resolve_pathname(env.deps.storage, env.deps.api, parent_andr_addr)?;
     validate_component_name(name.clone())?;
+    let key = (parent_addr.clone(), name.clone());
+    if paths().has(env.deps.storage, &key) {
+        ensure_path_owner(&env, &key)?;
+    }
     add_pathname(
         env.deps.storage,
+        env.deps.api,
         parent_addr.clone(),
         name.clone(),
         address.clone(),
@@ -59,19 +74,28 @@ pub fn add_symlink(
     symlink: AndrAddr,
     parent_address: Option<AndrAddr>,
 ) -> Result<Response, ContractError> {
-    let kernel_address = ADOContract::default().get_kernel_address(env.deps.storage)?;
+    let is_sender_dir = parent_address.is_none();
+    let parent_andr_addr = parent_address.unwrap_or(AndrAddr::from_string(env.info.sender.clone()));
+    let parent_addr = resolve_pathname(env.deps.storage, env.deps.api, parent_andr_addr)?;
     ensure!(
-        parent_address.is_none()
-            || env.info.sender == kernel_address
-            || ADOContract::default()
-                .is_contract_owner(env.deps.storage, env.info.sender.as_str())?,
+        is_sender_dir
+            || has_path_access(
+                env.deps.storage,
+                &parent_addr,
+                &env.info.sender,
+                PathAccess::Write
+            )?
+            || is_admin(&env)?,
         ContractError::Unauthorized {}
     );
-    let parent_andr_addr = parent_address.unwrap_or(AndrAddr::from_string(env.info.sender));
-    let parent_addr = resolve_pathname(env.deps.storage, env.deps.api, parent_andr_addr)?;
     validate_component_name(name.clone())?;
+    let key = (parent_addr.clone(), name.clone());
+    if paths().has(env.deps.storage, &key) {
+        ensure_path_owner(&env, &key)?;
+    }
     add_path_symlink(
         env.deps.storage,
+        env.deps.api,
         parent_addr.clone(),
         name.clone(),
         symlink.clone(),
@@ -98,7 +122,13 @@ pub fn add_parent_path(
     // Only add path method can override existing paths as its safe because only owner of the path can execute it
     match existing {
         None => {
-            add_pathname(env.deps.storage, parent_address, name, env.info.sender)?;
+            add_pathname(
+                env.deps.storage,
+                env.deps.api,
+                parent_address,
+                name,
+                env.info.sender,
+            )?;
         }
         Some(path) => {
             ensure!(
@@ -134,11 +164,18 @@ pub fn register_user(
     );
     let sender = address.unwrap_or(env.info.sender.clone());
     let current_user_address = USERS.may_load(env.deps.storage, username.as_str())?;
-    if current_user_address.is_some() {
+    if let Some(current_user_address) = &current_user_address {
+        // Expired usernames may be registered by another address
+        let expired = USERNAME_EXPIRATIONS
+            .may_load(env.deps.storage, username.as_str())?
+            .map_or(false, |expiration| env.env.block.time >= expiration);
         ensure!(
-            current_user_address.unwrap() == sender,
+            current_user_address == sender || expired,
             ContractError::Unauthorized {}
         );
+        if current_user_address != sender {
+            remove_address_username(env.deps.storage, current_user_address, &username)?;
+        }
     }
 
     //Remove username registration from previous username
@@ -146,6 +183,16 @@ pub fn register_user(
 
     validate_username(username.clone())?;
     USERS.save(env.deps.storage, username.as_str(), &sender)?;
+    if current_user_address.as_ref() != Some(&sender) {
+        match USERNAME_REGISTRATION_DURATION.may_load(env.deps.storage)? {
+            Some(duration) => USERNAME_EXPIRATIONS.save(
+                env.deps.storage,
+                username.as_str(),
+                &env.env.block.time.plus_seconds(duration),
+            )?,
+            None => USERNAME_EXPIRATIONS.remove(env.deps.storage, username.as_str()),
+        }
+    }
     //Update current address' username
     ADDRESS_USERNAME.save(env.deps.storage, sender.as_ref(), &username)?;
 
@@ -188,6 +235,31 @@ pub fn register_user_cross_chain(
 ) -> Result<Response, ContractError> {
     let kernel = ADOContract::default().get_kernel_address(env.deps.storage)?;
     let username = ADDRESS_USERNAME.load(env.deps.storage, env.info.sender.as_str())?;
+    // Reserve the registration until the receiving chain acknowledges it
+    let existing = CROSS_CHAIN_USERNAMES.may_load(env.deps.storage, (&username, &chain))?;
+    ensure!(
+        !matches!(
+            existing,
+            Some(CrossChainUsername {
+                status: CrossChainUsernameStatus::Pending,
+                ..
+            })
+        ),
+        ContractError::InvalidUsername {
+            error: Some(format!(
+                "Registration of {username} on {chain} is already pending"
+            ))
+        }
+    );
+    CROSS_CHAIN_USERNAMES.save(
+        env.deps.storage,
+        (&username, &chain),
+        &CrossChainUsername {
+            chain: chain.clone(),
+            address: address.clone(),
+            status: CrossChainUsernameStatus::Pending,
+        },
+    )?;
     let msg = KernelExecuteMsg::Internal(InternalMsg::RegisterUserCrossChain {
         username: username.clone(),
         address: address.clone(),
@@ -211,3 +283,407 @@ pub fn register_user_cross_chain(
         ])
         .add_submessage(sub_msg))
 }
+
+/// Whether the sender is the kernel or the VFS owner, who may modify any path
+fn is_admin(env: &ExecuteEnv) -> Result<bool, ContractError> {
+    let kernel_address = ADOContract::default().get_kernel_address(env.deps.storage)?;
+    Ok(env.info.sender == kernel_address
+        || ADOContract::default().is_contract_owner(env.deps.storage, env.info.sender.as_str())?)
+}
+
+/// Checks that the sender may modify the path stored at the given key.
+///
+/// Paths are managed by their parent directory and anyone with write access to it, unless their ownership has been transferred.
+fn ensure_path_owner(env: &ExecuteEnv, key: &(Addr, String)) -> Result<(), ContractError> {
+    let authorized = match PATH_OWNERS.may_load(env.deps.storage, key)? {
+        Some(owner) => owner == env.info.sender,
+        None => has_path_access(
+            env.deps.storage,
+            &key.0,
+            &env.info.sender,
+            PathAccess::Write,
+        )?,
+    };
+    ensure!(authorized || is_admin(env)?, ContractError::Unauthorized {});
+    Ok(())
+}
+
+/// Checks that the sender may manage access to the given path, returning the address of the path.
+///
+/// Access is checked against the directory the path resolves to rather than the path entry, as any address may add a path pointing to another directory.
+fn ensure_path_admin(env: &ExecuteEnv, path: &AndrAddr) -> Result<Addr, ContractError> {
+    let dir = resolve_pathname(env.deps.storage, env.deps.api, path.clone())?;
+    ensure!(
+        has_path_access(env.deps.storage, &dir, &env.info.sender, PathAccess::Admin)?
+            || is_admin(env)?,
+        ContractError::Unauthorized {}
+    );
+    Ok(dir)
+}
+
+pub fn remove_path(env: ExecuteEnv, path: AndrAddr) -> Result<Response, ContractError> {
+    let key = get_path_key(env.deps.storage, env.deps.api, &path)?;
+    ensure!(
+        paths().has(env.deps.storage, &key),
+        ContractError::InvalidPathname {
+            error: Some("Path does not exist".to_string())
+        }
+    );
+    ensure_path_owner(&env, &key)?;
+    remove_pathname(env.deps.storage, env.deps.api, &key)?;
+
+    Ok(Response::default().add_attributes(vec![attr("action", "remove_path"), attr("path", path)]))
+}
+
+pub fn rename_path(
+    env: ExecuteEnv,
+    path: AndrAddr,
+    name: String,
+) -> Result<Response, ContractError> {
+    validate_component_name(name.clone())?;
+    let (parent_path, _) = split_parent_path(&path)?;
+    let from = get_path_key(env.deps.storage, env.deps.api, &path)?;
+    ensure_path_owner(&env, &from)?;
+    let to = (from.0.clone(), name.clone());
+    let new_path = AndrAddr::from_string(format!("{parent_path}/{name}"));
+    move_pathname(env.deps.storage, env.deps.api, &from, &to, new_path.clone())?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "rename_path"),
+        attr("from", path),
+        attr("to", new_path),
+    ]))
+}
+
+pub fn move_path(env: ExecuteEnv, from: AndrAddr, to: AndrAddr) -> Result<Response, ContractError> {
+    let from_key = get_path_key(env.deps.storage, env.deps.api, &from)?;
+    ensure_path_owner(&env, &from_key)?;
+    let (_, name) = split_parent_path(&to)?;
+    validate_component_name(name)?;
+    let to_key = get_path_key(env.deps.storage, env.deps.api, &to)?;
+    // Paths may only be moved within their current directory or into a directory writable by the sender.
+    // Access is checked against the resolved directory as the destination may be reached through a path pointing to another directory.
+    ensure!(
+        to_key.0 == from_key.0
+            || has_path_access(
+                env.deps.storage,
+                &to_key.0,
+                &env.info.sender,
+                PathAccess::Write
+            )?
+            || is_admin(&env)?,
+        ContractError::Unauthorized {}
+    );
+    move_pathname(
+        env.deps.storage,
+        env.deps.api,
+        &from_key,
+        &to_key,
+        to.clone(),
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "move_path"),
+        attr("from", from),
+        attr("to", to),
+    ]))
+}
+
+pub fn transfer_path_ownership(
+    env: ExecuteEnv,
+    path: AndrAddr,
+    new_owner: Addr,
+) -> Result<Response, ContractError> {
+    let new_owner = env.deps.api.addr_validate(new_owner.as_str())?;
+    let key = get_path_key(env.deps.storage, env.deps.api, &path)?;
+    ensure!(
+        paths().has(env.deps.storage, &key),
+        ContractError::InvalidPathname {
+            error: Some("Path does not exist".to_string())
+        }
+    );
+    ensure_path_owner(&env, &key)?;
+    PATH_OWNERS.save(env.deps.storage, &key, &new_owner)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "transfer_path_ownership"),
+        attr("path", path),
+        attr("new_owner", new_owner),
+    ]))
+}
+
+pub fn set_path_permission(
+    env: ExecuteEnv,
+    path: AndrAddr,
+    address: Addr,
+    access: PathAccess,
+) -> Result<Response, ContractError> {
+    let dir = ensure_path_admin(&env, &path)?;
+    let address = env.deps.api.addr_validate(address.as_str())?;
+    PATH_PERMISSIONS.save(env.deps.storage, (&dir, &address), &access)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "set_path_permission"),
+        attr("path", path),
+        attr("address", address),
+        attr("access", format!("{access:?}")),
+    ]))
+}
+
+pub fn remove_path_permission(
+    env: ExecuteEnv,
+    path: AndrAddr,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let dir = ensure_path_admin(&env, &path)?;
+    PATH_PERMISSIONS.remove(env.deps.storage, (&dir, &address));
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "remove_path_permission"),
+        attr("path", path),
+        attr("address", address),
+    ]))
+}
+
+/// Removes the reverse lookup for an address if it points to the given username
+fn remove_address_username(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    username: &str,
+) -> Result<(), ContractError> {
+    if ADDRESS_USERNAME
+        .may_load(storage, address.as_str())?
+        .as_deref()
+        == Some(username)
+    {
+        ADDRESS_USERNAME.remove(storage, address.as_str());
+    }
+    Ok(())
+}
+
+/// Checks that the sender currently holds the given username and that its registration has not expired, returning the sender
+fn ensure_username_holder(env: &ExecuteEnv, username: &str) -> Result<Addr, ContractError> {
+    let holder = USERS.may_load(env.deps.storage, username)?;
+    ensure!(
+        holder.as_ref() == Some(&env.info.sender),
+        ContractError::Unauthorized {}
+    );
+    let expired = USERNAME_EXPIRATIONS
+        .may_load(env.deps.storage, username)?
+        .map_or(false, |expiration| env.env.block.time >= expiration);
+    ensure!(
+        !expired,
+        ContractError::InvalidUsername {
+            error: Some("Username registration has expired".to_string())
+        }
+    );
+    Ok(env.info.sender.clone())
+}
+
+/// Removes the registrations of a username on other chains, as they refer to addresses of the previous holder
+fn remove_cross_chain_usernames(
+    storage: &mut dyn Storage,
+    username: &str,
+) -> Result<(), ContractError> {
+    let chains = CROSS_CHAIN_USERNAMES
+        .prefix(username)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for chain in chains {
+        CROSS_CHAIN_USERNAMES.remove(storage, (username, &chain));
+    }
+    Ok(())
+}
+
+pub fn transfer_username(
+    env: ExecuteEnv,
+    username: String,
+    new_address: Addr,
+) -> Result<Response, ContractError> {
+    let holder = ensure_username_holder(&env, &username)?;
+    let new_address = env.deps.api.addr_validate(new_address.as_str())?;
+    ensure!(
+        new_address != holder,
+        ContractError::InvalidUsername {
+            error: Some("Cannot transfer a username to its current holder".to_string())
+        }
+    );
+    // An address may only hold a single username
+    if let Some(current_username) =
+        ADDRESS_USERNAME.may_load(env.deps.storage, new_address.as_str())?
+    {
+        ensure!(
+            USERS.may_load(env.deps.storage, current_username.as_str())?
+                != Some(new_address.clone()),
+            ContractError::InvalidUsername {
+                error: Some(format!(
+                    "{new_address} already holds the username {current_username}"
+                ))
+            }
+        );
+    }
+
+    // Paths under the username are stored under the holder's address
+    move_directory(env.deps.storage, &holder, &new_address)?;
+    USERS.save(env.deps.storage, username.as_str(), &new_address)?;
+    remove_address_username(env.deps.storage, &holder, &username)?;
+    ADDRESS_USERNAME.save(env.deps.storage, new_address.as_str(), &username)?;
+    remove_cross_chain_usernames(env.deps.storage, &username)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "transfer_username"),
+        attr("username", username),
+        attr("from", holder),
+        attr("to", new_address),
+    ]))
+}
+
+pub fn release_username(env: ExecuteEnv, username: String) -> Result<Response, ContractError> {
+    let holder = ensure_username_holder(&env, &username)?;
+
+    USERS.remove(env.deps.storage, username.as_str());
+    USERNAME_EXPIRATIONS.remove(env.deps.storage, username.as_str());
+    remove_address_username(env.deps.storage, &holder, &username)?;
+    remove_cross_chain_usernames(env.deps.storage, &username)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "release_username"),
+        attr("username", username),
+        attr("addr", holder),
+    ]))
+}
+
+pub fn renew_username(env: ExecuteEnv, username: String) -> Result<Response, ContractError> {
+    ensure_username_holder(&env, &username)?;
+
+    let expiration = match USERNAME_REGISTRATION_DURATION.may_load(env.deps.storage)? {
+        Some(duration) => {
+            // Renewals extend the current registration if it has not yet expired
+            let current = USERNAME_EXPIRATIONS
+                .may_load(env.deps.storage, username.as_str())?
+                .unwrap_or(env.env.block.time)
+                .max(env.env.block.time);
+            let expiration = current.plus_seconds(duration);
+            USERNAME_EXPIRATIONS.save(env.deps.storage, username.as_str(), &expiration)?;
+            expiration.to_string()
+        }
+        None => {
+            USERNAME_EXPIRATIONS.remove(env.deps.storage, username.as_str());
+            "never".to_string()
+        }
+    };
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "renew_username"),
+        attr("username", username),
+        attr("expiration", expiration),
+    ]))
+}
+
+pub fn set_username_registration_duration(
+    env: ExecuteEnv,
+    duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        ADOContract::default().is_contract_owner(env.deps.storage, env.info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    match duration {
+        Some(duration) => {
+            ensure!(
+                duration > 0,
+                ContractError::InvalidUsername {
+                    error: Some("Registration duration must be greater than zero".to_string())
+                }
+            );
+            USERNAME_REGISTRATION_DURATION.save(env.deps.storage, &duration)?
+        }
+        None => USERNAME_REGISTRATION_DURATION.remove(env.deps.storage),
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "set_username_registration_duration"),
+        attr(
+            "duration",
+            duration.map_or("none".to_string(), |duration| duration.to_string()),
+        ),
+    ]))
+}
+
+pub fn complete_user_cross_chain(
+    env: ExecuteEnv,
+    username: String,
+    chain: String,
+    error: Option<String>,
+) -> Result<Response, ContractError> {
+    let kernel = ADOContract::default().get_kernel_address(env.deps.storage)?;
+    ensure!(env.info.sender == kernel, ContractError::Unauthorized {});
+
+    let res = Response::default().add_attributes(vec![
+        attr("action", "complete_user_cross_chain"),
+        attr("username", username.clone()),
+        attr("chain", chain.clone()),
+    ]);
+    // The reservation may have been removed while the packet was in flight
+    let Some(registration) =
+        CROSS_CHAIN_USERNAMES.may_load(env.deps.storage, (&username, &chain))?
+    else {
+        return Ok(res.add_attribute("status", "not_found"));
+    };
+    if registration.status != CrossChainUsernameStatus::Pending {
+        return Ok(res.add_attribute("status", "not_pending"));
+    }
+
+    match error {
+        None => {
+            CROSS_CHAIN_USERNAMES.save(
+                env.deps.storage,
+                (&username, &chain),
+                &CrossChainUsername {
+                    status: CrossChainUsernameStatus::Registered,
+                    ..registration
+                },
+            )?;
+            Ok(res.add_attribute("status", "registered"))
+        }
+        Some(error) => {
+            CROSS_CHAIN_USERNAMES.remove(env.deps.storage, (&username, &chain));
+            Ok(res
+                .add_attribute("status", "rolled_back")
+                .add_attribute("error", error))
+        }
+    }
+}
+
+pub fn clear_user_cross_chain(
+    env: ExecuteEnv,
+    username: String,
+    chain: String,
+) -> Result<Response, ContractError> {
+    ensure!(
+        ADOContract::default().is_contract_owner(env.deps.storage, env.info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    let registration = CROSS_CHAIN_USERNAMES.may_load(env.deps.storage, (&username, &chain))?;
+    ensure!(
+        matches!(
+            registration,
+            Some(CrossChainUsername {
+                status: CrossChainUsernameStatus::Pending,
+                ..
+            })
+        ),
+        ContractError::InvalidUsername {
+            error: Some(format!(
+                "Registration of {username} on {chain} is not pending"
+            ))
+        }
+    );
+    CROSS_CHAIN_USERNAMES.remove(env.deps.storage, (&username, &chain));
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "clear_user_cross_chain"),
+        attr("username", username),
+        attr("chain", chain),
+    ]))
+}
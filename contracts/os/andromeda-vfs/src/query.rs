@@ -1,10 +1,14 @@
-use andromeda_std::os::vfs::validate_path_name;
+use andromeda_std::os::vfs::{
+    validate_path_name, CrossChainUsername, PathMatch, PathPermission, ReverseLookupResponse,
+    TreeNode,
+};
 use andromeda_std::{amp::AndrAddr, error::ContractError};
-use cosmwasm_std::{Addr, Deps};
+use cosmwasm_std::{Addr, Deps, Order, StdResult, Timestamp};
 
 use crate::state::{
-    get_paths, get_subdir, resolve_pathname, resolve_symlink, PathInfo, ADDRESS_LIBRARY,
-    ADDRESS_USERNAME,
+    get_path_permissions, get_paths, get_reverse_lookup, get_subdir, get_tree, resolve_glob,
+    resolve_pathname, resolve_symlink, PathInfo, ADDRESS_LIBRARY, ADDRESS_USERNAME,
+    CROSS_CHAIN_USERNAMES, USERNAME_EXPIRATIONS,
 };
 
 pub fn resolve_path(deps: Deps, path: AndrAddr) -> Result<Addr, ContractError> {
@@ -16,10 +20,45 @@ pub fn subdir(deps: Deps, path: AndrAddr) -> Result<Vec<PathInfo>, ContractError
     get_subdir(deps.storage, deps.api, path)
 }
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+pub fn glob(deps: Deps, pattern: String) -> Result<Vec<PathMatch>, ContractError> {
+    resolve_glob(deps.storage, deps.api, pattern)
+}
+
+pub fn tree(
+    deps: Deps,
+    path: AndrAddr,
+    depth: u32,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<TreeNode>, ContractError> {
+    validate_path_name(path.to_string())?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    get_tree(deps.storage, deps.api, path, depth, start_after, limit)
+}
+
 pub fn paths(deps: Deps, addr: Addr) -> Result<Vec<String>, ContractError> {
     get_paths(deps.storage, addr)
 }
 
+pub fn reverse_lookup(
+    deps: Deps,
+    addr: Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ReverseLookupResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    get_reverse_lookup(deps.storage, deps.api, addr, start_after, limit)
+}
+
+pub fn path_permissions(deps: Deps, path: AndrAddr) -> Result<Vec<PathPermission>, ContractError> {
+    validate_path_name(path.to_string())?;
+    let dir = resolve_pathname(deps.storage, deps.api, path)?;
+    get_path_permissions(deps.storage, &dir)
+}
+
 pub fn get_symlink(deps: Deps, addr: AndrAddr) -> Result<AndrAddr, ContractError> {
     resolve_symlink(deps.storage, deps.api, addr)
 }
@@ -37,3 +76,22 @@ pub fn get_library_name(deps: Deps, addr: Addr) -> Result<String, ContractError>
         .unwrap_or(addr.to_string());
     Ok(lib_name)
 }
+
+pub fn get_username_expiration(
+    deps: Deps,
+    username: String,
+) -> Result<Option<Timestamp>, ContractError> {
+    Ok(USERNAME_EXPIRATIONS.may_load(deps.storage, username.as_str())?)
+}
+
+pub fn get_username_chains(
+    deps: Deps,
+    username: String,
+) -> Result<Vec<CrossChainUsername>, ContractError> {
+    let registrations = CROSS_CHAIN_USERNAMES
+        .prefix(&username)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, registration)| registration))
+        .collect::<StdResult<Vec<CrossChainUsername>>>()?;
+    Ok(registrations)
+}
@@ -1,6 +1,12 @@
-use andromeda_std::{amp::AndrAddr, error::ContractError};
-use cosmwasm_std::{ensure, Addr, Api, StdError, Storage};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Map, MultiIndex};
+use andromeda_std::{
+    amp::AndrAddr,
+    error::ContractError,
+    os::vfs::{
+        CrossChainUsername, PathAccess, PathMatch, PathPermission, ReverseLookupResponse, TreeNode,
+    },
+};
+use cosmwasm_std::{ensure, Addr, Api, Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -9,6 +15,9 @@ pub struct PathInfo {
     pub address: Addr,
     pub parent_address: Addr,
     pub symlink: Option<AndrAddr>,
+    /// The address the symlink currently resolves to, if it can be resolved
+    #[serde(default)]
+    pub symlink_target: Option<Addr>,
 }
 
 pub struct PathIndices<'a> {
@@ -19,13 +28,17 @@ pub struct PathIndices<'a> {
     /// PK: parent_address + component_name
     /// Secondary key: parent_address
     pub parent: MultiIndex<'a, Addr, PathInfo, (Addr, String)>,
+
+    /// PK: parent_address + component_name
+    /// Secondary key: symlink_target
+    pub symlink: MultiIndex<'a, Addr, PathInfo, (Addr, String)>,
 }
 
 impl<'a> IndexList<PathInfo> for PathIndices<'a> {
     fn get_indexes(
         &'_ self,
     ) -> Box<dyn Iterator<Item = &'_ dyn cw_storage_plus::Index<PathInfo>> + '_> {
-        let v: Vec<&dyn Index<PathInfo>> = vec![&self.address, &self.parent];
+        let v: Vec<&dyn Index<PathInfo>> = vec![&self.address, &self.parent, &self.symlink];
         Box::new(v.into_iter())
     }
 }
@@ -38,6 +51,11 @@ pub fn paths<'a>() -> IndexedMap<'a, &'a (Addr, String), PathInfo, PathIndices<'
             "path",
             "parent_index",
         ),
+        symlink: MultiIndex::new(
+            |_pk: &[u8], r| r.symlink_target.clone().unwrap_or(Addr::unchecked("")),
+            "path",
+            "symlink_index",
+        ),
     };
     IndexedMap::new("path", indexes)
 }
@@ -46,6 +64,29 @@ pub const USERS: Map<&str, Addr> = Map::new("users");
 pub const LIBRARIES: Map<&str, Addr> = Map::new("libraries");
 pub const ADDRESS_USERNAME: Map<&str, String> = Map::new("address_username");
 pub const ADDRESS_LIBRARY: Map<&str, String> = Map::new("address_library");
+/// Registrations of usernames on other chains, keyed by username and chain
+pub const CROSS_CHAIN_USERNAMES: Map<(&str, &str), CrossChainUsername> =
+    Map::new("cross_chain_usernames");
+/// The time at which a username registration expires, after which it may be registered by another address
+pub const USERNAME_EXPIRATIONS: Map<&str, Timestamp> = Map::new("username_expirations");
+/// The duration in seconds of username registrations, registrations do not expire if unset
+pub const USERNAME_REGISTRATION_DURATION: Item<u64> = Item::new("username_registration_duration");
+/// Owners of paths whose ownership has been transferred away from their parent directory
+pub const PATH_OWNERS: Map<&(Addr, String), Addr> = Map::new("path_owners");
+/// Access granted to addresses over the direct children of a directory, keyed by directory address and grantee
+pub const PATH_PERMISSIONS: Map<(&Addr, &Addr), PathAccess> = Map::new("path_permissions");
+/// The new location of paths that have been moved or renamed, used to keep symlinks to their previous location resolving
+pub const MOVED_PATHS: Map<&(Addr, String), AndrAddr> = Map::new("moved_paths");
+/// Symlinks whose target could not be resolved, keyed by the first missing path of their target
+pub const UNRESOLVED_SYMLINKS: Map<&(Addr, String), Vec<(Addr, String)>> =
+    Map::new("unresolved_symlinks");
+
+/// The placeholder address stored for symlinks
+const SYMLINK_ADDRESS: &str = "invalidaddress";
+/// The maximum depth of a tree query
+const MAX_TREE_DEPTH: u32 = 10;
+/// The maximum number of moved paths followed when resolving a single symlink
+const MAX_PATH_REDIRECTS: usize = 10;
 
 pub fn split_pathname(path: String) -> Vec<String> {
     path.split('/')
@@ -132,7 +173,10 @@ fn resolve_path(
         }
         let info = paths().load(storage, &(address, part.clone()))?;
         address = match info.symlink {
-            Some(symlink) => resolve_pathname(storage, api, symlink)?,
+            Some(symlink) => {
+                let symlink = follow_moved_paths(storage, api, symlink)?;
+                resolve_pathname(storage, api, symlink)?
+            }
             None => info.address,
         };
     }
@@ -140,6 +184,230 @@ fn resolve_path(
     Ok(address)
 }
 
+/// Splits a path into its parent path and final component name
+pub fn split_parent_path(path: &AndrAddr) -> Result<(AndrAddr, String), ContractError> {
+    let mut parts = split_pathname(path.to_string());
+    ensure!(
+        path.is_vfs_path() && path.get_protocol().is_none() && parts.len() > 2,
+        ContractError::InvalidPathname {
+            error: Some("Path must contain a component below the root directory".to_string())
+        }
+    );
+    let final_part = parts.pop().unwrap();
+    let reconstructed_addr = parts.join("/");
+    // Need to prepend a '/' unless the path starts with '~'
+    let parent_path = if reconstructed_addr.starts_with('~') {
+        AndrAddr::from_string(reconstructed_addr)
+    } else {
+        AndrAddr::from_string(format!("/{reconstructed_addr}"))
+    };
+    Ok((parent_path, final_part))
+}
+
+/// Gets the storage key (parent address and component name) for the given path
+pub fn get_path_key(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    path: &AndrAddr,
+) -> Result<(Addr, String), ContractError> {
+    let (parent_path, name) = split_parent_path(path)?;
+    let parent_addr = resolve_pathname(storage, api, parent_path)?;
+    Ok((parent_addr, name))
+}
+
+/// Whether the address has at least the given access to the direct children of a directory.
+///
+/// An address always has full access to its own children.
+pub fn has_path_access(
+    storage: &dyn Storage,
+    dir: &Addr,
+    address: &Addr,
+    access: PathAccess,
+) -> Result<bool, ContractError> {
+    if dir == address {
+        return Ok(true);
+    }
+    Ok(PATH_PERMISSIONS
+        .may_load(storage, (dir, address))?
+        .map_or(false, |granted| granted >= access))
+}
+
+/// Gets the access granted to addresses over the direct children of a directory
+pub fn get_path_permissions(
+    storage: &dyn Storage,
+    dir: &Addr,
+) -> Result<Vec<PathPermission>, ContractError> {
+    let permissions = PATH_PERMISSIONS
+        .prefix(dir)
+        .range(storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(address, access)| PathPermission { address, access }))
+        .collect::<StdResult<Vec<PathPermission>>>()?;
+    Ok(permissions)
+}
+
+/// Removes the path stored at the given key along with its ownership record
+pub fn remove_pathname(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    key: &(Addr, String),
+) -> Result<(), ContractError> {
+    let info = paths().load(storage, key)?;
+    paths().remove(storage, key)?;
+    PATH_OWNERS.remove(storage, key);
+    reindex_path_symlinks(storage, api, &info)
+}
+
+/// Moves the path stored at the given key to a new key.
+///
+/// The new location is recorded so that any symlinks to the previous location continue to resolve.
+pub fn move_pathname(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    from: &(Addr, String),
+    to: &(Addr, String),
+    new_path: AndrAddr,
+) -> Result<(), ContractError> {
+    let info = rekey_pathname(storage, from, to)?;
+    MOVED_PATHS.save(storage, from, &new_path)?;
+    MOVED_PATHS.remove(storage, to);
+    // Symlinks are re-indexed once the previous location redirects to the new one
+    reindex_path_symlinks(storage, api, &info)?;
+    index_unresolved_symlinks(storage, api, to)
+}
+
+/// Moves the path stored at the given key to a new key, keeping its ownership
+fn rekey_pathname(
+    storage: &mut dyn Storage,
+    from: &(Addr, String),
+    to: &(Addr, String),
+) -> Result<PathInfo, ContractError> {
+    ensure!(
+        !paths().has(storage, to),
+        ContractError::InvalidPathname {
+            error: Some("Path already exists".to_string())
+        }
+    );
+    let info = paths().load(storage, from)?;
+    let owner = PATH_OWNERS.may_load(storage, from)?;
+    paths().remove(storage, from)?;
+    PATH_OWNERS.remove(storage, from);
+    let info = PathInfo {
+        name: to.1.clone(),
+        parent_address: to.0.clone(),
+        ..info
+    };
+    paths().save(storage, to, &info)?;
+    if let Some(owner) = owner {
+        PATH_OWNERS.save(storage, to, &owner)?;
+    }
+    Ok(info)
+}
+
+/// Moves all direct children of a directory, along with any access granted to it, to a new directory.
+///
+/// The new directory must be empty so that its existing paths and access are never merged with or overwritten by those being moved.
+pub fn move_directory(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+) -> Result<(), ContractError> {
+    let has_children = paths()
+        .idx
+        .parent
+        .prefix(to.clone())
+        .keys_raw(storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    ensure!(
+        !has_children && get_path_permissions(storage, to)?.is_empty(),
+        ContractError::InvalidPathname {
+            error: Some(format!("Directory of {to} is not empty"))
+        }
+    );
+    let names = paths()
+        .idx
+        .parent
+        .prefix(from.clone())
+        .range(storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, info)| info.name))
+        .collect::<StdResult<Vec<String>>>()?;
+    for name in names {
+        rekey_pathname(storage, &(from.clone(), name.clone()), &(to.clone(), name))?;
+    }
+
+    for permission in get_path_permissions(storage, from)? {
+        PATH_PERMISSIONS.remove(storage, (from, &permission.address));
+        PATH_PERMISSIONS.save(storage, (to, &permission.address), &permission.access)?;
+    }
+    Ok(())
+}
+
+/// Rewrites a symlink target so that any moved paths it passes through point to their new location
+pub fn follow_moved_paths(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    path: AndrAddr,
+) -> Result<AndrAddr, ContractError> {
+    let mut path = path;
+    for _ in 0..MAX_PATH_REDIRECTS {
+        match find_moved_path(storage, api, &path)? {
+            Some(moved_path) => path = moved_path,
+            None => return Ok(path),
+        }
+    }
+    Err(ContractError::InvalidPathname {
+        error: Some("Too many moved paths".to_string()),
+    })
+}
+
+/// Finds the first component of the path that no longer exists due to being moved and returns the path with that component replaced by its new location
+fn find_moved_path(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    path: &AndrAddr,
+) -> Result<Option<AndrAddr>, ContractError> {
+    let parts = split_pathname(path.to_string());
+    if !path.is_vfs_path() || path.get_protocol().is_some() || parts.len() <= 2 {
+        return Ok(None);
+    }
+    let root_path = parts[..2].join("/");
+    // Need to prepend a '/' unless the path starts with '~'
+    let root_path = if root_path.starts_with('~') {
+        AndrAddr::from_string(root_path)
+    } else {
+        AndrAddr::from_string(format!("/{root_path}"))
+    };
+    let mut address = resolve_pathname(storage, api, root_path)?;
+    for (idx, part) in parts.iter().enumerate().skip(2) {
+        let key = (address, part.clone());
+        address = match paths().may_load(storage, &key)? {
+            Some(PathInfo {
+                symlink: Some(symlink),
+                ..
+            }) => {
+                let symlink = follow_moved_paths(storage, api, symlink)?;
+                resolve_pathname(storage, api, symlink)?
+            }
+            Some(info) => info.address,
+            None => {
+                let Some(moved_path) = MOVED_PATHS.may_load(storage, &key)? else {
+                    return Ok(None);
+                };
+                let remaining = &parts[idx + 1..];
+                if remaining.is_empty() {
+                    return Ok(Some(moved_path));
+                }
+                return Ok(Some(AndrAddr::from_string(format!(
+                    "{}/{}",
+                    moved_path.as_str().trim_end_matches('/'),
+                    remaining.join("/")
+                ))));
+            }
+        };
+    }
+    Ok(None)
+}
+
 pub fn get_subdir(
     storage: &dyn Storage,
     api: &dyn Api,
@@ -158,6 +426,204 @@ pub fn get_subdir(
     Ok(subdirs)
 }
 
+/// Whether the component name matches the pattern, where `*` matches any sequence of characters and `?` matches a single character
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the name position it was matched from
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Gets the address a path entry resolves to, following symlinks
+fn resolve_path_info(storage: &dyn Storage, api: &dyn Api, info: &PathInfo) -> Option<Addr> {
+    match &info.symlink {
+        Some(symlink) => resolve_symlink_target(storage, api, symlink.clone()),
+        None => Some(info.address.clone()),
+    }
+}
+
+/// Resolves every path matching the given pattern, the root directory of the pattern cannot contain wildcards
+pub fn resolve_glob(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    pattern: String,
+) -> Result<Vec<PathMatch>, ContractError> {
+    let parts = split_pathname(pattern.clone());
+    ensure!(
+        parts.len() >= 2 && !parts[..2].iter().any(|part| part.contains(['*', '?'])),
+        ContractError::InvalidPathname {
+            error: Some("Glob patterns must start with a root directory".to_string())
+        }
+    );
+    let root_path = parts[..2].join("/");
+    // Need to prepend a '/' unless the path starts with '~'
+    let root_path = if root_path.starts_with('~') {
+        root_path
+    } else {
+        format!("/{root_path}")
+    };
+    let root_addr = resolve_pathname(storage, api, AndrAddr::from_string(root_path.clone()))?;
+
+    let mut matches = vec![PathMatch {
+        path: root_path,
+        address: root_addr,
+    }];
+    for part in &parts[2..] {
+        let mut next_matches = vec![];
+        for parent in matches {
+            if part.contains(['*', '?']) {
+                let children = paths()
+                    .idx
+                    .parent
+                    .prefix(parent.address.clone())
+                    .range(storage, None, None, Order::Ascending)
+                    .map(|r| r.map(|(_, info)| info))
+                    .collect::<StdResult<Vec<PathInfo>>>()?;
+                for info in children {
+                    if !matches_glob(part, &info.name) {
+                        continue;
+                    }
+                    if let Some(address) = resolve_path_info(storage, api, &info) {
+                        next_matches.push(PathMatch {
+                            path: format!("{}/{}", parent.path, info.name),
+                            address,
+                        });
+                    }
+                }
+            } else if let Some(info) =
+                paths().may_load(storage, &(parent.address.clone(), part.clone()))?
+            {
+                if let Some(address) = resolve_path_info(storage, api, &info) {
+                    next_matches.push(PathMatch {
+                        path: format!("{}/{part}", parent.path),
+                        address,
+                    });
+                }
+            }
+        }
+        matches = next_matches;
+    }
+    Ok(matches)
+}
+
+/// Lists the paths below the given path in depth first order.
+///
+/// Symlinks are listed but not descended into. Pagination resumes after the path given by `start_after`.
+pub fn get_tree(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    path: AndrAddr,
+    depth: u32,
+    start_after: Option<String>,
+    limit: usize,
+) -> Result<Vec<TreeNode>, ContractError> {
+    let root_addr = resolve_pathname(storage, api, path.clone())?;
+    let root_path = path.to_string().trim_end_matches('/').to_string();
+    let resume = match start_after {
+        Some(start_after) => {
+            let root_parts = split_pathname(root_path.clone());
+            let parts = split_pathname(start_after);
+            ensure!(
+                parts.len() > root_parts.len() && parts.starts_with(&root_parts),
+                ContractError::InvalidPathname {
+                    error: Some("Start after must be a path within the tree".to_string())
+                }
+            );
+            parts[root_parts.len()..].to_vec()
+        }
+        None => vec![],
+    };
+
+    let mut nodes = vec![];
+    add_tree_nodes(
+        storage,
+        api,
+        &root_addr,
+        &root_path,
+        1,
+        depth.min(MAX_TREE_DEPTH),
+        &resume,
+        limit,
+        &mut nodes,
+    )?;
+    Ok(nodes)
+}
+
+/// Adds the children of a directory and their subtrees to `nodes`, skipping nodes up to and including the path given by `resume`
+#[allow(clippy::too_many_arguments)]
+fn add_tree_nodes(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    dir: &Addr,
+    dir_path: &str,
+    level: u32,
+    depth: u32,
+    resume: &[String],
+    limit: usize,
+    nodes: &mut Vec<TreeNode>,
+) -> Result<(), ContractError> {
+    if level > depth {
+        return Ok(());
+    }
+    let children = paths()
+        .idx
+        .parent
+        .prefix(dir.clone())
+        .range(storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, info)| info))
+        .collect::<StdResult<Vec<PathInfo>>>()?;
+    for info in children {
+        if nodes.len() >= limit {
+            break;
+        }
+        let child_resume: &[String] = match resume.split_first() {
+            Some((name, _)) if info.name < *name => continue,
+            // The node itself has already been listed, resume within its subtree
+            Some((name, rest)) if info.name == *name => rest,
+            _ => {
+                nodes.push(TreeNode {
+                    path: format!("{dir_path}/{}", info.name),
+                    address: resolve_path_info(storage, api, &info),
+                    symlink: info.symlink.clone(),
+                    depth: level,
+                });
+                &[]
+            }
+        };
+        if info.symlink.is_none() {
+            add_tree_nodes(
+                storage,
+                api,
+                &info.address,
+                &format!("{dir_path}/{}", info.name),
+                level + 1,
+                depth,
+                child_resume,
+                limit,
+                nodes,
+            )?;
+        }
+    }
+    Ok(())
+}
+
 pub fn get_paths(storage: &dyn Storage, addr: Addr) -> Result<Vec<String>, ContractError> {
     let mut resolved_paths: Vec<String> = vec![];
     let parent_dirs: Vec<PathInfo> = paths()
@@ -186,40 +652,259 @@ pub fn get_paths(storage: &dyn Storage, addr: Addr) -> Result<Vec<String>, Contr
 
 pub fn add_pathname(
     storage: &mut dyn Storage,
+    api: &dyn Api,
     parent_addr: Addr,
     name: String,
     address: Addr,
-) -> Result<(), StdError> {
-    paths().save(
+) -> Result<(), ContractError> {
+    save_path_info(
         storage,
+        api,
         &(parent_addr.clone(), name.clone()),
-        &PathInfo {
+        PathInfo {
             name,
             address,
             parent_address: parent_addr,
             symlink: None,
+            symlink_target: None,
         },
     )
 }
 
 pub fn add_path_symlink(
     storage: &mut dyn Storage,
+    api: &dyn Api,
     parent_addr: Addr,
     name: String,
     symlink: AndrAddr,
-) -> Result<(), StdError> {
-    paths().save(
+) -> Result<(), ContractError> {
+    let symlink_target = resolve_symlink_target(storage, api, symlink.clone());
+    save_path_info(
         storage,
+        api,
         &(parent_addr.clone(), name.clone()),
-        &PathInfo {
+        PathInfo {
             name,
-            address: Addr::unchecked("invalidaddress"),
+            address: Addr::unchecked(SYMLINK_ADDRESS),
             parent_address: parent_addr,
             symlink: Some(symlink),
+            symlink_target,
         },
     )
 }
 
+/// Stores a path, re-indexing any symlinks that resolved through the path it replaces or were waiting on it to be added
+fn save_path_info(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    key: &(Addr, String),
+    info: PathInfo,
+) -> Result<(), ContractError> {
+    let previous = paths().may_load(storage, key)?;
+    paths().save(storage, key, &info)?;
+    if info.symlink.is_some() && info.symlink_target.is_none() {
+        index_symlink(storage, api, key)?;
+    }
+    if let Some(previous) = previous {
+        reindex_path_symlinks(storage, api, &previous)?;
+    }
+    index_unresolved_symlinks(storage, api, key)
+}
+
+/// Resolves the address a symlink points to, if it can be resolved locally
+fn resolve_symlink_target(storage: &dyn Storage, api: &dyn Api, symlink: AndrAddr) -> Option<Addr> {
+    follow_moved_paths(storage, api, symlink)
+        .and_then(|symlink| resolve_pathname(storage, api, symlink))
+        .ok()
+}
+
+/// Resolves and indexes the target of the symlink stored at the given key.
+///
+/// Symlinks that cannot be resolved are re-indexed once the first missing path of their target is added.
+fn index_symlink(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    key: &(Addr, String),
+) -> Result<(), ContractError> {
+    let Some(info) = paths().may_load(storage, key)? else {
+        return Ok(());
+    };
+    let Some(symlink) = info.symlink.clone() else {
+        return Ok(());
+    };
+    let symlink_target = resolve_symlink_target(storage, api, symlink.clone());
+    if symlink_target.is_none() {
+        if let Some(missing) = find_missing_path(storage, api, symlink)? {
+            UNRESOLVED_SYMLINKS.update(storage, &missing, |waiting| -> StdResult<_> {
+                let mut waiting = waiting.unwrap_or_default();
+                if !waiting.contains(key) {
+                    waiting.push(key.clone());
+                }
+                Ok(waiting)
+            })?;
+        }
+    }
+    if symlink_target != info.symlink_target {
+        paths().save(
+            storage,
+            key,
+            &PathInfo {
+                symlink_target,
+                ..info
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Re-indexes the symlinks that resolved to the given path, called when the path is replaced, moved or removed
+fn reindex_path_symlinks(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    info: &PathInfo,
+) -> Result<(), ContractError> {
+    let address = match &info.symlink {
+        Some(_) => info.symlink_target.clone(),
+        None => Some(info.address.clone()),
+    };
+    let Some(address) = address else {
+        return Ok(());
+    };
+    let keys = paths()
+        .idx
+        .symlink
+        .prefix(address)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, String)>>>()?;
+    for key in keys {
+        index_symlink(storage, api, &key)?;
+    }
+    Ok(())
+}
+
+/// Re-indexes the symlinks that were waiting on the path at the given key to be added
+fn index_unresolved_symlinks(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    key: &(Addr, String),
+) -> Result<(), ContractError> {
+    let Some(waiting) = UNRESOLVED_SYMLINKS.may_load(storage, key)? else {
+        return Ok(());
+    };
+    UNRESOLVED_SYMLINKS.remove(storage, key);
+    for symlink_key in waiting {
+        index_symlink(storage, api, &symlink_key)?;
+    }
+    Ok(())
+}
+
+/// Finds the key of the first path along the given path that does not exist
+fn find_missing_path(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    path: AndrAddr,
+) -> Result<Option<(Addr, String)>, ContractError> {
+    let path = follow_moved_paths(storage, api, path)?;
+    let parts = split_pathname(path.to_string());
+    if !path.is_vfs_path() || path.get_protocol().is_some() || parts.len() <= 2 {
+        return Ok(None);
+    }
+    let root_path = parts[..2].join("/");
+    // Need to prepend a '/' unless the path starts with '~'
+    let root_path = if root_path.starts_with('~') {
+        AndrAddr::from_string(root_path)
+    } else {
+        AndrAddr::from_string(format!("/{root_path}"))
+    };
+    let Ok(mut address) = resolve_pathname(storage, api, root_path) else {
+        return Ok(None);
+    };
+    for part in parts.iter().skip(2) {
+        let key = (address, part.clone());
+        address = match paths().may_load(storage, &key)? {
+            Some(PathInfo {
+                symlink: Some(symlink),
+                ..
+            }) => match resolve_symlink_target(storage, api, symlink.clone()) {
+                Some(address) => address,
+                None => return find_missing_path(storage, api, symlink),
+            },
+            Some(info) => info.address,
+            None => return Ok(Some(key)),
+        };
+    }
+    Ok(None)
+}
+
+/// Resolves and indexes the targets of any symlinks added before symlink targets were indexed
+pub fn index_symlink_targets(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+) -> Result<(), ContractError> {
+    // Symlinks are stored with a placeholder address
+    let keys = paths()
+        .idx
+        .address
+        .prefix(Addr::unchecked(SYMLINK_ADDRESS))
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, String)>>>()?;
+    for key in keys {
+        index_symlink(storage, api, &key)?;
+    }
+    Ok(())
+}
+
+/// Gets every path that resolves to the given address along with a page of the symlinks that resolve to it,
+/// symlinks are paginated by the path of the last symlink returned
+pub fn get_reverse_lookup(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    addr: Addr,
+    start_after: Option<String>,
+    limit: usize,
+) -> Result<ReverseLookupResponse, ContractError> {
+    let paths_to_addr = get_paths(storage, addr.clone())?
+        .into_iter()
+        .map(|path| format!("/home/{path}"))
+        .collect();
+
+    let start = match start_after {
+        Some(path) => Some(Bound::exclusive(get_path_key(
+            storage,
+            api,
+            &AndrAddr::from_string(path),
+        )?)),
+        None => None,
+    };
+    let mut symlinks = vec![];
+    let mut listed = 0;
+    for info in
+        paths()
+            .idx
+            .symlink
+            .prefix(addr.clone())
+            .range(storage, start, None, Order::Ascending)
+    {
+        if listed >= limit {
+            break;
+        }
+        let (_, info) = info?;
+        // Symlinks resolving through a directory that has since changed may not have been re-indexed
+        if resolve_path_info(storage, api, &info) != Some(addr.clone()) {
+            continue;
+        }
+        listed += 1;
+        for parent_path in get_paths(storage, info.parent_address)? {
+            symlinks.push(format!("/home/{parent_path}/{}", info.name));
+        }
+    }
+
+    Ok(ReverseLookupResponse {
+        paths: paths_to_addr,
+        symlinks,
+    })
+}
+
 pub fn resolve_symlink(
     storage: &dyn Storage,
     api: &dyn Api,
@@ -240,7 +925,7 @@ pub fn resolve_symlink(
     let addr = resolve_pathname(storage, api, remaining_path)?;
     let info = paths().load(storage, &(addr, final_part))?;
     match info.symlink {
-        Some(symlink) => Ok(symlink),
+        Some(symlink) => follow_moved_paths(storage, api, symlink),
         None => Ok(path),
     }
 }
@@ -334,6 +1019,7 @@ mod test {
                     address: first_directory_address.clone(),
                     parent_address: username_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -358,6 +1044,7 @@ mod test {
                     address: second_directory_address.clone(),
                     parent_address: first_directory_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -381,6 +1068,7 @@ mod test {
                     address: file_address.clone(),
                     parent_address: second_directory_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -430,6 +1118,7 @@ mod test {
                     address: first_directory_address.clone(),
                     parent_address: username_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -454,6 +1143,7 @@ mod test {
                     address: second_directory_address.clone(),
                     parent_address: first_directory_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -477,6 +1167,7 @@ mod test {
                     address: file_address.clone(),
                     parent_address: second_directory_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -514,6 +1205,7 @@ mod test {
                     address: first_directory_address.clone(),
                     parent_address: username_address,
                     symlink: None,
+                    symlink_target: None,
                 },
             )
             .unwrap();
@@ -530,7 +1222,8 @@ mod test {
         let symlink_name = "symlink";
         let symlink = AndrAddr::from_string(format!("/home/{username}/{first_directory}"));
         add_path_symlink(
-            deps.as_mut().storage,
+            &mut deps.storage,
+            &deps.api,
             symlink_parent.clone(),
             symlink_name.to_string(),
             symlink.clone(),
@@ -596,4 +1289,16 @@ mod test {
 
         assert_eq!(res, AndrAddr::from_string("/home/someuser"));
     }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("*", "splitter"));
+        assert!(matches_glob("split*", "splitter"));
+        assert!(matches_glob("*er", "splitter"));
+        assert!(matches_glob("s*l*r", "splitter"));
+        assert!(matches_glob("splitte?", "splitter"));
+        assert!(!matches_glob("splitte?", "splitte"));
+        assert!(!matches_glob("*x*", "splitter"));
+        assert!(!matches_glob("split", "splitter"));
+    }
 }
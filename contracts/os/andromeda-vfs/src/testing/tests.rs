@@ -1,13 +1,19 @@
 use crate::{
     contract::{execute, instantiate, query},
-    state::{add_pathname, resolve_pathname, PathInfo, ADDRESS_LIBRARY, ADDRESS_USERNAME, USERS},
+    state::{
+        add_path_symlink, add_pathname, paths, resolve_pathname, PathInfo, ADDRESS_LIBRARY,
+        ADDRESS_USERNAME, CROSS_CHAIN_USERNAMES, PATH_PERMISSIONS, USERS,
+    },
 };
 
 use andromeda_std::{
     amp::AndrAddr,
     os::{
         kernel::{ExecuteMsg as KernelExecuteMsg, InternalMsg},
-        vfs::{ExecuteMsg, InstantiateMsg},
+        vfs::{
+            CrossChainUsername, CrossChainUsernameStatus, ExecuteMsg, InstantiateMsg, PathAccess,
+            PathMatch, PathPermission, ReverseLookupResponse, TreeNode,
+        },
     },
     testing::mock_querier::{
         mock_dependencies_custom, MOCK_FAKE_KERNEL_CONTRACT, MOCK_KERNEL_CONTRACT,
@@ -17,7 +23,7 @@ use andromeda_std::{error::ContractError, os::vfs::QueryMsg};
 use cosmwasm_std::{
     from_binary,
     testing::{mock_dependencies, mock_env, mock_info},
-    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, WasmMsg,
+    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Order, Storage, Timestamp, WasmMsg,
 };
 
 fn instantiate_contract(deps: DepsMut, env: Env, info: MessageInfo) {
@@ -185,6 +191,153 @@ fn test_register_user_cross_chain() {
     );
 }
 
+#[test]
+fn test_complete_user_cross_chain() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let username = "user1";
+    let sender = "sender";
+    let chain = "chain";
+    let info = mock_info(sender, &[]);
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+    ADDRESS_USERNAME
+        .save(deps.as_mut().storage, sender, &username.to_string())
+        .unwrap();
+
+    let msg = ExecuteMsg::RegisterUserCrossChain {
+        chain: chain.to_string(),
+        address: "address".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUsername {
+            error: Some(format!(
+                "Registration of {username} on {chain} is already pending"
+            ))
+        }
+    );
+
+    let query_msg = QueryMsg::GetUsernameChains {
+        username: username.to_string(),
+    };
+    let res: Vec<CrossChainUsername> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        vec![CrossChainUsername {
+            chain: chain.to_string(),
+            address: "address".to_string(),
+            status: CrossChainUsernameStatus::Pending,
+        }]
+    );
+
+    // A failed acknowledgement rolls back the reservation
+    let complete_msg = ExecuteMsg::CompleteUserCrossChain {
+        username: username.to_string(),
+        chain: chain.to_string(),
+        error: Some("Username already taken".to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        complete_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let kernel_info = mock_info(MOCK_KERNEL_CONTRACT, &[]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        kernel_info.clone(),
+        complete_msg,
+    )
+    .unwrap();
+    let res: Vec<CrossChainUsername> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert!(res.is_empty());
+
+    // A successful acknowledgement confirms the registration
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let complete_msg = ExecuteMsg::CompleteUserCrossChain {
+        username: username.to_string(),
+        chain: chain.to_string(),
+        error: None,
+    };
+    execute(deps.as_mut(), env.clone(), kernel_info, complete_msg).unwrap();
+    let res: Vec<CrossChainUsername> =
+        from_binary(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert_eq!(res[0].status, CrossChainUsernameStatus::Registered);
+}
+
+#[test]
+fn test_clear_user_cross_chain() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let username = "user1";
+    let sender = "sender";
+    let chain = "chain";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    ADDRESS_USERNAME
+        .save(deps.as_mut().storage, sender, &username.to_string())
+        .unwrap();
+
+    let clear_msg = ExecuteMsg::ClearUserCrossChain {
+        username: username.to_string(),
+        chain: chain.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        clear_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUsername {
+            error: Some(format!(
+                "Registration of {username} on {chain} is not pending"
+            ))
+        }
+    );
+
+    let msg = ExecuteMsg::RegisterUserCrossChain {
+        chain: chain.to_string(),
+        address: "address".to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    // Only the VFS owner may clear a pending registration
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        clear_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        clear_msg,
+    )
+    .unwrap();
+    assert!(!CROSS_CHAIN_USERNAMES.has(deps.as_ref().storage, (username, chain)));
+
+    // The username can be registered on the chain again
+    execute(deps.as_mut(), env, mock_info(sender, &[]), msg).unwrap();
+}
+
 #[test]
 fn test_add_path() {
     let mut deps = mock_dependencies();
@@ -511,12 +664,14 @@ fn test_get_subdir() {
             address: Addr::unchecked("f1addr"),
             parent_address: sender.clone(),
             symlink: None,
+            symlink_target: None,
         },
         PathInfo {
             name: "f2".to_string(),
             address: Addr::unchecked("f2addr"),
             parent_address: sender.clone(),
             symlink: None,
+            symlink_target: None,
         },
     ];
     let sub_paths = vec![
@@ -525,12 +680,14 @@ fn test_get_subdir() {
             address: Addr::unchecked("sub1addr"),
             parent_address: root_paths[0].address.clone(),
             symlink: None,
+            symlink_target: None,
         },
         PathInfo {
             name: "sub2".to_string(),
             address: Addr::unchecked("sub2addr"),
             parent_address: root_paths[0].address.clone(),
             symlink: None,
+            symlink_target: None,
         },
     ];
 
@@ -541,7 +698,8 @@ fn test_get_subdir() {
     // Add all root components
     for path in root_paths.clone() {
         let _ = add_pathname(
-            deps.as_mut().storage,
+            &mut deps.storage,
+            &deps.api,
             sender.clone(),
             path.name,
             path.address,
@@ -550,7 +708,8 @@ fn test_get_subdir() {
 
     for path in sub_paths.clone() {
         let _ = add_pathname(
-            deps.as_mut().storage,
+            &mut deps.storage,
+            &deps.api,
             path.parent_address.clone(),
             path.name,
             path.address,
@@ -596,12 +755,14 @@ fn test_get_paths() {
             address: Addr::unchecked("f1addr"),
             parent_address: sender.clone(),
             symlink: None,
+            symlink_target: None,
         },
         PathInfo {
             name: "f2".to_string(),
             address: Addr::unchecked("f2addr"),
             parent_address: sender.clone(),
             symlink: None,
+            symlink_target: None,
         },
     ];
     let sub_paths = vec![
@@ -610,12 +771,14 @@ fn test_get_paths() {
             address: Addr::unchecked("sub1addr"),
             parent_address: root_paths[0].address.clone(),
             symlink: None,
+            symlink_target: None,
         },
         PathInfo {
             name: "sub2".to_string(),
             address: Addr::unchecked("sub2addr"),
             parent_address: root_paths[0].address.clone(),
             symlink: None,
+            symlink_target: None,
         },
     ];
 
@@ -633,14 +796,16 @@ fn test_get_paths() {
     // Add all root components
     for path in root_paths.clone() {
         let _ = add_pathname(
-            deps.as_mut().storage,
+            &mut deps.storage,
+            &deps.api,
             sender.clone(),
             path.name,
             path.address.clone(),
         );
         for sub_path in sub_paths.clone() {
             let _ = add_pathname(
-                deps.as_mut().storage,
+                &mut deps.storage,
+                &deps.api,
                 path.address.clone(),
                 sub_path.name,
                 sub_path.address,
@@ -666,3 +831,988 @@ fn test_get_paths() {
     let val: Vec<String> = from_binary(&res).unwrap();
     assert_eq!(val.len(), 2);
 }
+
+#[test]
+fn test_remove_path() {
+    let mut deps = mock_dependencies();
+    let username = "u1";
+    let sender = "sender";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(sender))
+        .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "f1".to_string(),
+        Addr::unchecked("f1addr"),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::RemovePath {
+        path: AndrAddr::from_string(format!("/home/{username}/f1")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_the_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(resolve_pathname(
+        deps.as_ref().storage,
+        deps.as_ref().api,
+        AndrAddr::from_string(format!("/home/{username}/f1")),
+    )
+    .is_err());
+
+    let err = execute(deps.as_mut(), env, mock_info(sender, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPathname {
+            error: Some("Path does not exist".to_string())
+        }
+    );
+}
+
+#[test]
+fn test_rename_and_move_path() {
+    let mut deps = mock_dependencies();
+    let username = "u1";
+    let sender = "sender";
+    let info = mock_info(sender, &[]);
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(sender))
+        .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "dir".to_string(),
+        Addr::unchecked("diraddr"),
+    )
+    .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked("diraddr"),
+        "file".to_string(),
+        Addr::unchecked("fileaddr"),
+    )
+    .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "other".to_string(),
+        Addr::unchecked("otheraddr"),
+    )
+    .unwrap();
+    add_path_symlink(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "link".to_string(),
+        AndrAddr::from_string(format!("/home/{username}/dir/file")),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::RenamePath {
+        path: AndrAddr::from_string(format!("/home/{username}/dir")),
+        name: "renamed".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let resolve = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, path: String| {
+        resolve_pathname(&deps.storage, &deps.api, AndrAddr::from_string(path))
+    };
+    assert!(resolve(&deps, format!("/home/{username}/dir/file")).is_err());
+    assert_eq!(
+        resolve(&deps, format!("/home/{username}/renamed/file")).unwrap(),
+        Addr::unchecked("fileaddr")
+    );
+    assert_eq!(
+        resolve(&deps, format!("/home/{username}/link")).unwrap(),
+        Addr::unchecked("fileaddr")
+    );
+
+    // Cannot move into a directory not owned by the sender
+    let msg = ExecuteMsg::MovePath {
+        from: AndrAddr::from_string(format!("/home/{username}/renamed")),
+        to: AndrAddr::from_string("/home/someone/renamed"),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Nor into a directory only reached through a path or symlink in the sender's directory
+    add_path_symlink(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "someone".to_string(),
+        AndrAddr::from_string("/home/someone"),
+    )
+    .unwrap();
+    for to in [
+        format!("/home/{username}/other/moved"),
+        format!("/home/{username}/someone/moved"),
+    ] {
+        let msg = ExecuteMsg::MovePath {
+            from: AndrAddr::from_string(format!("/home/{username}/renamed")),
+            to: AndrAddr::from_string(to),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+    assert!(resolve(&deps, "/home/someone/moved".to_string()).is_err());
+
+    // Cannot move onto an existing path
+    let msg = ExecuteMsg::MovePath {
+        from: AndrAddr::from_string(format!("/home/{username}/renamed")),
+        to: AndrAddr::from_string(format!("/home/{username}/other")),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPathname {
+            error: Some("Path already exists".to_string())
+        }
+    );
+
+    // Moving into the directory is allowed once it grants write access to the sender
+    let msg = ExecuteMsg::SetPathPermission {
+        path: AndrAddr::from_string(format!("/home/{username}/other")),
+        address: Addr::unchecked(sender),
+        access: PathAccess::Write,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("otheraddr", &[]), msg).unwrap();
+    let msg = ExecuteMsg::MovePath {
+        from: AndrAddr::from_string(format!("/home/{username}/renamed")),
+        to: AndrAddr::from_string(format!("/home/{username}/other/moved")),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        resolve(&deps, format!("/home/{username}/other/moved/file")).unwrap(),
+        Addr::unchecked("fileaddr")
+    );
+    assert_eq!(
+        resolve(&deps, format!("/home/{username}/link")).unwrap(),
+        Addr::unchecked("fileaddr")
+    );
+
+    let query_msg = QueryMsg::ResolveSymlink {
+        path: AndrAddr::from_string(format!("/home/{username}/link")),
+    };
+    let res: AndrAddr = from_binary(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        AndrAddr::from_string(format!("/home/{username}/other/moved/file"))
+    );
+}
+
+#[test]
+fn test_transfer_path_ownership() {
+    let mut deps = mock_dependencies();
+    let username = "u1";
+    let sender = "sender";
+    let new_owner = "new_owner";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(sender))
+        .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "f1".to_string(),
+        Addr::unchecked("f1addr"),
+    )
+    .unwrap();
+    let path = AndrAddr::from_string(format!("/home/{username}/f1"));
+
+    let msg = ExecuteMsg::TransferPathOwnership {
+        path: path.clone(),
+        new_owner: Addr::unchecked(new_owner),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(new_owner, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg).unwrap();
+
+    // The parent directory can no longer modify or override the path
+    let msg = ExecuteMsg::RenamePath {
+        path: path.clone(),
+        name: "f2".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let add_msg = ExecuteMsg::AddPath {
+        name: "f1".to_string(),
+        address: Addr::unchecked("otheraddr"),
+        parent_address: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), add_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env, mock_info(new_owner, &[]), msg).unwrap();
+    let resolved = resolve_pathname(
+        deps.as_ref().storage,
+        deps.as_ref().api,
+        AndrAddr::from_string(format!("/home/{username}/f2")),
+    )
+    .unwrap();
+    assert_eq!(resolved, Addr::unchecked("f1addr"));
+}
+
+#[test]
+fn test_reverse_lookup() {
+    let mut deps = mock_dependencies();
+    let username = "u1";
+    let sender = "sender";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(sender))
+        .unwrap();
+    ADDRESS_USERNAME
+        .save(deps.as_mut().storage, sender, &username.to_string())
+        .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "app".to_string(),
+        Addr::unchecked("appaddr"),
+    )
+    .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked("appaddr"),
+        "splitter".to_string(),
+        Addr::unchecked("splitteraddr"),
+    )
+    .unwrap();
+    add_path_symlink(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "link".to_string(),
+        AndrAddr::from_string(format!("/home/{username}/app/splitter")),
+    )
+    .unwrap();
+
+    let query_msg = QueryMsg::ReverseLookup {
+        address: Addr::unchecked("splitteraddr"),
+        start_after: None,
+        limit: None,
+    };
+    let res: ReverseLookupResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        ReverseLookupResponse {
+            paths: vec![format!("/home/{username}/app/splitter")],
+            symlinks: vec![format!("/home/{username}/link")],
+        }
+    );
+
+    // Symlinks are no longer listed once their target resolves elsewhere
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked("appaddr"),
+        "splitter".to_string(),
+        Addr::unchecked("newsplitteraddr"),
+    )
+    .unwrap();
+    let res: ReverseLookupResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert!(res.symlinks.is_empty());
+
+    // Symlinks are listed under the address their target currently resolves to
+    let query_msg = QueryMsg::ReverseLookup {
+        address: Addr::unchecked("newsplitteraddr"),
+        start_after: None,
+        limit: None,
+    };
+    let res: ReverseLookupResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.symlinks, vec![format!("/home/{username}/link")]);
+
+    // Including symlinks added before their target existed
+    add_path_symlink(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "early".to_string(),
+        AndrAddr::from_string(format!("/home/{username}/app/later")),
+    )
+    .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked("appaddr"),
+        "later".to_string(),
+        Addr::unchecked("lateraddr"),
+    )
+    .unwrap();
+    let query_msg = QueryMsg::ReverseLookup {
+        address: Addr::unchecked("lateraddr"),
+        start_after: None,
+        limit: None,
+    };
+    let res: ReverseLookupResponse =
+        from_binary(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        ReverseLookupResponse {
+            paths: vec![format!("/home/{username}/app/later")],
+            symlinks: vec![format!("/home/{username}/early")],
+        }
+    );
+}
+
+#[test]
+fn test_reverse_lookup_symlink_index() {
+    let mut deps = mock_dependencies();
+    let username = "u1";
+    let sender = "sender";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(sender))
+        .unwrap();
+    ADDRESS_USERNAME
+        .save(deps.as_mut().storage, sender, &username.to_string())
+        .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "splitter".to_string(),
+        Addr::unchecked("splitteraddr"),
+    )
+    .unwrap();
+    for name in ["link1", "link2", "link3"] {
+        add_path_symlink(
+            &mut deps.storage,
+            &deps.api,
+            Addr::unchecked(sender),
+            name.to_string(),
+            AndrAddr::from_string(format!("/home/{username}/splitter")),
+        )
+        .unwrap();
+    }
+    let indexed_symlinks = |storage: &dyn Storage, addr: &str| {
+        paths()
+            .idx
+            .symlink
+            .prefix(Addr::unchecked(addr))
+            .keys(storage, None, None, Order::Ascending)
+            .map(|key| key.unwrap().1)
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(
+        indexed_symlinks(&deps.storage, "splitteraddr"),
+        vec!["link1", "link2", "link3"]
+    );
+
+    // Symlinks are paginated by the path of the last symlink returned
+    let query_msg = QueryMsg::ReverseLookup {
+        address: Addr::unchecked("splitteraddr"),
+        start_after: None,
+        limit: Some(2),
+    };
+    let res: ReverseLookupResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        ReverseLookupResponse {
+            paths: vec![format!("/home/{username}/splitter")],
+            symlinks: vec![
+                format!("/home/{username}/link1"),
+                format!("/home/{username}/link2")
+            ],
+        }
+    );
+    let query_msg = QueryMsg::ReverseLookup {
+        address: Addr::unchecked("splitteraddr"),
+        start_after: Some(format!("/home/{username}/link2")),
+        limit: Some(2),
+    };
+    let res: ReverseLookupResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.symlinks, vec![format!("/home/{username}/link3")]);
+
+    // Moved paths keep their symlinks indexed through the recorded redirect
+    let info = mock_info(sender, &[]);
+    let msg = ExecuteMsg::RenamePath {
+        path: AndrAddr::from_string(format!("/home/{username}/splitter")),
+        name: "renamed".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        indexed_symlinks(&deps.storage, "splitteraddr"),
+        vec!["link1", "link2", "link3"]
+    );
+
+    // Removed paths are no longer indexed as the target of their symlinks
+    let msg = ExecuteMsg::RemovePath {
+        path: AndrAddr::from_string(format!("/home/{username}/renamed")),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert!(indexed_symlinks(&deps.storage, "splitteraddr").is_empty());
+
+    // Symlinks are indexed again once the path they are waiting on is added
+    let msg = ExecuteMsg::AddPath {
+        name: "renamed".to_string(),
+        address: Addr::unchecked("newsplitteraddr"),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        indexed_symlinks(&deps.storage, "newsplitteraddr"),
+        vec!["link1", "link2", "link3"]
+    );
+}
+
+#[test]
+fn test_path_permissions() {
+    let mut deps = mock_dependencies();
+    let username = "t1";
+    let team = "team_addr";
+    let member = "member";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(team))
+        .unwrap();
+    let team_path = AndrAddr::from_string(format!("/home/{username}"));
+    let add_msg = ExecuteMsg::AddPath {
+        name: "app".to_string(),
+        address: Addr::unchecked("appaddr"),
+        parent_address: Some(team_path.clone()),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(member, &[]),
+        add_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let msg = ExecuteMsg::SetPathPermission {
+        path: team_path.clone(),
+        address: Addr::unchecked(member),
+        access: PathAccess::Write,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(member, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(team, &[]), msg).unwrap();
+
+    // Members with write access can manage paths but not permissions
+    execute(deps.as_mut(), env.clone(), mock_info(member, &[]), add_msg).unwrap();
+    let msg = ExecuteMsg::RenamePath {
+        path: AndrAddr::from_string(format!("/home/{username}/app")),
+        name: "app2".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(member, &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetPathPermission {
+        path: team_path.clone(),
+        address: Addr::unchecked("other"),
+        access: PathAccess::Read,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(member, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let query_msg = QueryMsg::PathPermissions {
+        path: team_path.clone(),
+    };
+    let res: Vec<PathPermission> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        vec![PathPermission {
+            address: Addr::unchecked(member),
+            access: PathAccess::Write,
+        }]
+    );
+
+    let revoke_msg = ExecuteMsg::RemovePathPermission {
+        path: team_path,
+        address: Addr::unchecked(member),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(team, &[]), revoke_msg).unwrap();
+    let msg = ExecuteMsg::RemovePath {
+        path: AndrAddr::from_string(format!("/home/{username}/app2")),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(member, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res: Vec<PathPermission> =
+        from_binary(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_path_permissions_pointer_to_other_directory() {
+    let mut deps = mock_dependencies();
+    let victim = "victim";
+    let attacker = "attacker";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, victim, &Addr::unchecked(victim))
+        .unwrap();
+    USERS
+        .save(deps.as_mut().storage, attacker, &Addr::unchecked(attacker))
+        .unwrap();
+
+    // Any address may add a path in its own directory pointing to another directory
+    let msg = ExecuteMsg::AddPath {
+        name: "x".to_string(),
+        address: Addr::unchecked(victim),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(attacker, &[]), msg).unwrap();
+
+    // Owning the path entry does not grant access to the directory it points to
+    let pointer = AndrAddr::from_string(format!("/home/{attacker}/x"));
+    let msg = ExecuteMsg::SetPathPermission {
+        path: pointer.clone(),
+        address: Addr::unchecked(attacker),
+        access: PathAccess::Admin,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(attacker, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let msg = ExecuteMsg::AddPath {
+        name: "app".to_string(),
+        address: Addr::unchecked("attackeraddr"),
+        parent_address: Some(pointer),
+    };
+    let err = execute(deps.as_mut(), env, mock_info(attacker, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    assert!(!PATH_PERMISSIONS.has(
+        deps.as_ref().storage,
+        (&Addr::unchecked(victim), &Addr::unchecked(attacker))
+    ));
+}
+
+#[test]
+fn test_transfer_and_release_username() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let username = "u1";
+    let sender = "sender";
+    let new_address = "new_address";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    let msg = ExecuteMsg::RegisterUser {
+        username: username.to_string(),
+        address: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg).unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "app".to_string(),
+        Addr::unchecked("appaddr"),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::TransferUsername {
+        username: username.to_string(),
+        new_address: Addr::unchecked(new_address),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(new_address, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg).unwrap();
+
+    assert_eq!(
+        USERS.load(deps.as_ref().storage, username).unwrap(),
+        new_address
+    );
+    assert!(!ADDRESS_USERNAME.has(deps.as_ref().storage, sender));
+    assert_eq!(
+        ADDRESS_USERNAME
+            .load(deps.as_ref().storage, new_address)
+            .unwrap(),
+        username
+    );
+    let resolved = resolve_pathname(
+        deps.as_ref().storage,
+        deps.as_ref().api,
+        AndrAddr::from_string(format!("/home/{username}/app")),
+    )
+    .unwrap();
+    assert_eq!(resolved, Addr::unchecked("appaddr"));
+    assert!(resolve_pathname(
+        deps.as_ref().storage,
+        deps.as_ref().api,
+        AndrAddr::from_string(format!("/home/{sender}/app")),
+    )
+    .is_err());
+
+    let msg = ExecuteMsg::ReleaseUsername {
+        username: username.to_string(),
+    };
+    execute(deps.as_mut(), env, mock_info(new_address, &[]), msg).unwrap();
+    assert!(!USERS.has(deps.as_ref().storage, username));
+    assert!(!ADDRESS_USERNAME.has(deps.as_ref().storage, new_address));
+}
+
+#[test]
+fn test_transfer_username_conflicts() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let username = "u1";
+    let sender = "sender";
+    let mut env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    for (username, address) in [(username, sender), ("u2", "holder2")] {
+        let msg = ExecuteMsg::RegisterUser {
+            username: username.to_string(),
+            address: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(address, &[]), msg).unwrap();
+    }
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "app".to_string(),
+        Addr::unchecked("appaddr"),
+    )
+    .unwrap();
+    add_pathname(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked("occupied"),
+        "app".to_string(),
+        Addr::unchecked("otherappaddr"),
+    )
+    .unwrap();
+    CROSS_CHAIN_USERNAMES
+        .save(
+            deps.as_mut().storage,
+            (username, "osmosis"),
+            &CrossChainUsername {
+                chain: "osmosis".to_string(),
+                address: "osmo1sender".to_string(),
+                status: CrossChainUsernameStatus::Registered,
+            },
+        )
+        .unwrap();
+
+    let transfer = |new_address: &str| ExecuteMsg::TransferUsername {
+        username: username.to_string(),
+        new_address: Addr::unchecked(new_address),
+    };
+
+    // Addresses may only hold a single username
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        transfer("holder2"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUsername {
+            error: Some("holder2 already holds the username u2".to_string())
+        }
+    );
+
+    // Paths are never merged into a directory that already has paths
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        transfer("occupied"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPathname {
+            error: Some("Directory of occupied is not empty".to_string())
+        }
+    );
+    assert_eq!(
+        resolve_pathname(
+            deps.as_ref().storage,
+            deps.as_ref().api,
+            AndrAddr::from_string("/home/occupied/app"),
+        )
+        .unwrap(),
+        Addr::unchecked("otherappaddr")
+    );
+
+    // Registrations on other chains refer to the previous holder's addresses
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        transfer("new_address"),
+    )
+    .unwrap();
+    assert!(!CROSS_CHAIN_USERNAMES.has(deps.as_ref().storage, (username, "osmosis")));
+
+    // Expired holders can no longer transfer or renew
+    let msg = ExecuteMsg::SetUsernameRegistrationDuration {
+        duration: Some(100),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+    let msg = ExecuteMsg::RegisterUser {
+        username: "u3".to_string(),
+        address: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("holder3", &[]), msg).unwrap();
+    env.block.time = env.block.time.plus_seconds(100);
+    let expired = ContractError::InvalidUsername {
+        error: Some("Username registration has expired".to_string()),
+    };
+    let msg = ExecuteMsg::TransferUsername {
+        username: "u3".to_string(),
+        new_address: Addr::unchecked("new_address3"),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("holder3", &[]), msg).unwrap_err();
+    assert_eq!(err, expired);
+    let msg = ExecuteMsg::RenewUsername {
+        username: "u3".to_string(),
+    };
+    let err = execute(deps.as_mut(), env, mock_info("holder3", &[]), msg).unwrap_err();
+    assert_eq!(err, expired);
+}
+
+#[test]
+fn test_username_expiration() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let username = "user1";
+    let sender = "sender";
+    let mut env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+
+    let msg = ExecuteMsg::SetUsernameRegistrationDuration {
+        duration: Some(100),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let register_msg = ExecuteMsg::RegisterUser {
+        username: username.to_string(),
+        address: None,
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(sender, &[]),
+        register_msg.clone(),
+    )
+    .unwrap();
+    let query_msg = QueryMsg::GetUsernameExpiration {
+        username: username.to_string(),
+    };
+    let expiration: Option<Timestamp> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(expiration, Some(env.block.time.plus_seconds(100)));
+
+    // Renewing extends the current registration
+    env.block.time = env.block.time.plus_seconds(50);
+    let msg = ExecuteMsg::RenewUsername {
+        username: username.to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg).unwrap();
+    let expiration: Option<Timestamp> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(expiration, Some(env.block.time.plus_seconds(150)));
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("other", &[]),
+        register_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Once expired the username can be registered by another address
+    env.block.time = env.block.time.plus_seconds(150);
+    execute(deps.as_mut(), env, mock_info("other", &[]), register_msg).unwrap();
+    assert_eq!(
+        USERS.load(deps.as_ref().storage, username).unwrap(),
+        "other"
+    );
+    assert!(!ADDRESS_USERNAME.has(deps.as_ref().storage, sender));
+}
+
+#[test]
+fn test_resolve_glob_and_tree() {
+    let mut deps = mock_dependencies();
+    let username = "u1";
+    let sender = "sender";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    USERS
+        .save(deps.as_mut().storage, username, &Addr::unchecked(sender))
+        .unwrap();
+    // /home/u1/{app1/splitter, app2/{auction, splitter}, link -> app1}
+    for (parent, name, address) in [
+        (sender, "app1", "app1addr"),
+        (sender, "app2", "app2addr"),
+        ("app1addr", "splitter", "splitter1addr"),
+        ("app2addr", "auction", "auctionaddr"),
+        ("app2addr", "splitter", "splitter2addr"),
+    ] {
+        add_pathname(
+            &mut deps.storage,
+            &deps.api,
+            Addr::unchecked(parent),
+            name.to_string(),
+            Addr::unchecked(address),
+        )
+        .unwrap();
+    }
+    add_path_symlink(
+        &mut deps.storage,
+        &deps.api,
+        Addr::unchecked(sender),
+        "link".to_string(),
+        AndrAddr::from_string(format!("/home/{username}/app1")),
+    )
+    .unwrap();
+
+    let query_msg = QueryMsg::ResolveGlob {
+        pattern: format!("/home/{username}/*/splitter"),
+    };
+    let res: Vec<PathMatch> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        vec![
+            PathMatch {
+                path: format!("/home/{username}/app1/splitter"),
+                address: Addr::unchecked("splitter1addr"),
+            },
+            PathMatch {
+                path: format!("/home/{username}/app2/splitter"),
+                address: Addr::unchecked("splitter2addr"),
+            },
+            PathMatch {
+                path: format!("/home/{username}/link/splitter"),
+                address: Addr::unchecked("splitter1addr"),
+            },
+        ]
+    );
+
+    let query_msg = QueryMsg::ResolveGlob {
+        pattern: "/home/*/app1".to_string(),
+    };
+    let err = query(deps.as_ref(), env.clone(), query_msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPathname {
+            error: Some("Glob patterns must start with a root directory".to_string())
+        }
+    );
+
+    let tree_query = |start_after: Option<String>, limit: Option<u32>| QueryMsg::Tree {
+        path: AndrAddr::from_string(format!("/home/{username}")),
+        depth: 2,
+        start_after,
+        limit,
+    };
+    let res: Vec<TreeNode> =
+        from_binary(&query(deps.as_ref(), env.clone(), tree_query(None, None)).unwrap()).unwrap();
+    let paths: Vec<(String, u32)> = res.iter().map(|n| (n.path.clone(), n.depth)).collect();
+    assert_eq!(
+        paths,
+        vec![
+            (format!("/home/{username}/app1"), 1),
+            (format!("/home/{username}/app1/splitter"), 2),
+            (format!("/home/{username}/app2"), 1),
+            (format!("/home/{username}/app2/auction"), 2),
+            (format!("/home/{username}/app2/splitter"), 2),
+            (format!("/home/{username}/link"), 1),
+        ]
+    );
+    assert_eq!(res[5].address, Some(Addr::unchecked("app1addr")));
+
+    // Paginating resumes after the last returned path
+    let res: Vec<TreeNode> =
+        from_binary(&query(deps.as_ref(), env.clone(), tree_query(None, Some(3))).unwrap())
+            .unwrap();
+    assert_eq!(res.len(), 3);
+    let res: Vec<TreeNode> = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            tree_query(Some(res[2].path.clone()), Some(3)),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let paths: Vec<String> = res.into_iter().map(|n| n.path).collect();
+    assert_eq!(
+        paths,
+        vec![
+            format!("/home/{username}/app2/auction"),
+            format!("/home/{username}/app2/splitter"),
+            format!("/home/{username}/link"),
+        ]
+    );
+}
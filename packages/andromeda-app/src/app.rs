@@ -100,10 +100,15 @@ pub enum ExecuteMsg {
     AddAppComponent {
         component: AppComponent,
     },
+    /// Proposes the new owner for the app's components, ownership of each component must then be accepted with `AcceptOwnership` or `AcceptComponentOwnership`
     ClaimOwnership {
         name: Option<String>,
         new_owner: Option<Addr>,
     },
+    /// Accepts ownership of every component (or the named component) still owned by the app for which the sender is the pending owner
+    AcceptComponentOwnership {
+        name: Option<String>,
+    },
     ProxyMessage {
         name: String,
         msg: Binary,
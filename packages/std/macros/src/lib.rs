@@ -47,6 +47,13 @@ pub fn andr_exec(_args: TokenStream, input: TokenStream) -> TokenStream {
                 UpdateOwner {
                     address: String,
                 },
+                ProposeOwner {
+                    address: ::andromeda_std::amp::AndrAddr,
+                    expiration: Option<::andromeda_std::Expiration>,
+                },
+                AcceptOwnership {},
+                CancelOwnershipProposal {},
+                RenounceOwnership {},
                 UpdateOperators {
                     operators: Vec<String>,
                 },
@@ -62,9 +69,44 @@ pub fn andr_exec(_args: TokenStream, input: TokenStream) -> TokenStream {
                     action: String,
                     actor: ::andromeda_std::amp::AndrAddr,
                 },
+                SetPermissions {
+                    entries: Vec<::andromeda_std::ado_base::permissioning::PermissionEntry>,
+                },
                 PermissionAction {
                     action: String
                 },
+                CreateRole {
+                    role: String,
+                },
+                DeleteRole {
+                    role: String,
+                },
+                GrantRole {
+                    role: String,
+                    actors: Vec<::andromeda_std::amp::AndrAddr>,
+                },
+                RevokeRole {
+                    role: String,
+                    actors: Vec<::andromeda_std::amp::AndrAddr>,
+                },
+                SetRolePermission {
+                    role: String,
+                    action: String,
+                    permission: ::andromeda_std::ado_base::permissioning::Permission,
+                },
+                RemoveRolePermission {
+                    role: String,
+                    action: String,
+                },
+                SetGroupPermission {
+                    group: ::andromeda_std::ado_base::permissioning::ActorGroup,
+                    action: String,
+                    permission: ::andromeda_std::ado_base::permissioning::Permission,
+                },
+                RemoveGroupPermission {
+                    group: ::andromeda_std::ado_base::permissioning::ActorGroup,
+                    action: String,
+                },
             }
         }
         .into(),
@@ -196,6 +238,8 @@ pub fn andr_query(_metadata: TokenStream, input: TokenStream) -> TokenStream {
             enum Right {
                 #[returns(andromeda_std::ado_base::ownership::ContractOwnerResponse)]
                 Owner {},
+                #[returns(andromeda_std::ado_base::ownership::PendingOwnerResponse)]
+                PendingOwner {},
                 #[returns(andromeda_std::ado_base::operators::OperatorsResponse)]
                 Operators {},
                 #[returns(andromeda_std::ado_base::ado_type::TypeResponse)]
@@ -218,6 +262,18 @@ pub fn andr_query(_metadata: TokenStream, input: TokenStream) -> TokenStream {
                 Permissions { actor: String, limit: Option<u32>, start_after: Option<String> },
                 #[returns(Vec<String>)]
                 PermissionedActions { },
+                #[returns(Vec<::andromeda_std::ado_base::permissioning::PermissionChange>)]
+                PermissionHistory { action: String, grantee: ::andromeda_std::ado_base::permissioning::PermissionGrantee, start_after: Option<u64>, limit: Option<u32> },
+                #[returns(Vec<::andromeda_std::ado_base::permissioning::RoleMembershipChange>)]
+                RoleHistory { role: String, start_after: Option<u64>, limit: Option<u32> },
+                #[returns(Vec<String>)]
+                RoleMembers { role: String, limit: Option<u32>, start_after: Option<String> },
+                #[returns(Vec<String>)]
+                ActorRoles { actor: String, limit: Option<u32>, start_after: Option<String> },
+                #[returns(Vec<::andromeda_std::ado_base::permissioning::RolePermissionInfo>)]
+                RolePermissions { role: String, limit: Option<u32>, start_after: Option<String> },
+                #[returns(Vec<::andromeda_std::ado_base::permissioning::GroupPermissionInfo>)]
+                GroupPermissions { action: String, limit: Option<u32>, start_after: Option<String> },
             }
         }
         .into(),
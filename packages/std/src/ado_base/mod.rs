@@ -15,11 +15,12 @@ use crate::ado_base::withdraw::Withdrawal;
 #[cfg(feature = "withdraw")]
 use crate::amp::recipient::Recipient;
 use crate::{
-    ado_base::permissioning::Permission,
+    ado_base::permissioning::{ActorGroup, Permission, PermissionEntry, PermissionGrantee},
     amp::{messages::AMPPkt, AndrAddr},
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Binary;
+use cw_utils::Expiration;
 pub use modules::Module;
 
 #[cfg(feature = "modules")]
@@ -39,6 +40,13 @@ pub enum AndromedaMsg {
     UpdateOwner {
         address: String,
     },
+    ProposeOwner {
+        address: AndrAddr,
+        expiration: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
+    RenounceOwnership {},
     UpdateOperators {
         operators: Vec<String>,
     },
@@ -78,9 +86,44 @@ pub enum AndromedaMsg {
         action: String,
         actor: AndrAddr,
     },
+    SetPermissions {
+        entries: Vec<PermissionEntry>,
+    },
     PermissionAction {
         action: String,
     },
+    CreateRole {
+        role: String,
+    },
+    DeleteRole {
+        role: String,
+    },
+    GrantRole {
+        role: String,
+        actors: Vec<AndrAddr>,
+    },
+    RevokeRole {
+        role: String,
+        actors: Vec<AndrAddr>,
+    },
+    SetRolePermission {
+        role: String,
+        action: String,
+        permission: Permission,
+    },
+    RemoveRolePermission {
+        role: String,
+        action: String,
+    },
+    SetGroupPermission {
+        group: ActorGroup,
+        action: String,
+        permission: Permission,
+    },
+    RemoveGroupPermission {
+        group: ActorGroup,
+        action: String,
+    },
 }
 
 #[cw_serde]
@@ -88,6 +131,8 @@ pub enum AndromedaMsg {
 pub enum AndromedaQuery {
     #[returns(self::ownership::ContractOwnerResponse)]
     Owner {},
+    #[returns(self::ownership::PendingOwnerResponse)]
+    PendingOwner {},
     #[returns(self::operators::OperatorsResponse)]
     Operators {},
     #[returns(self::ado_type::TypeResponse)]
@@ -121,4 +166,41 @@ pub enum AndromedaQuery {
     },
     #[returns(Vec<String>)]
     PermissionedActions {},
+    #[returns(Vec<self::permissioning::PermissionChange>)]
+    PermissionHistory {
+        action: String,
+        grantee: PermissionGrantee,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<self::permissioning::RoleMembershipChange>)]
+    RoleHistory {
+        role: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<String>)]
+    RoleMembers {
+        role: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    #[returns(Vec<String>)]
+    ActorRoles {
+        actor: AndrAddr,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    #[returns(Vec<self::permissioning::RolePermissionInfo>)]
+    RolePermissions {
+        role: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    #[returns(Vec<self::permissioning::GroupPermissionInfo>)]
+    GroupPermissions {
+        action: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
 }
@@ -1,7 +1,10 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_utils::Expiration;
 
 #[cw_serde]
 pub struct ContractOwnerResponse {
+    /// Empty once ownership has been renounced
     pub owner: String,
 }
 
@@ -9,3 +12,16 @@ pub struct ContractOwnerResponse {
 pub struct PublisherResponse {
     pub original_publisher: String,
 }
+
+/// A proposed transfer of ownership awaiting acceptance by the proposed owner
+#[cw_serde]
+pub struct OwnershipProposal {
+    pub owner: Addr,
+    pub expiration: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<String>,
+    pub expiration: Option<Expiration>,
+}
@@ -1,8 +1,10 @@
 use core::fmt;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Env;
-use cw_utils::Expiration;
+use cosmwasm_std::{Addr, Env, Timestamp};
+use cw_utils::{Duration, Expiration};
+
+use crate::amp::AndrAddr;
 
 #[cw_serde]
 pub struct PermissionInfo {
@@ -11,11 +13,84 @@ pub struct PermissionInfo {
     pub actor: String,
 }
 
+#[cw_serde]
+pub struct PermissionEntry {
+    pub actor: AndrAddr,
+    pub action: String,
+    pub permission: Permission,
+}
+
+/// The recipient of a permission for an action
+///
+/// - **Actor** - A single actor, for permissions set directly on the actor
+/// - **Role** - Every member of the given role
+/// - **Group** - Every member of the given actor group
+#[cw_serde]
+pub enum PermissionGrantee {
+    Actor(AndrAddr),
+    Role(String),
+    Group(ActorGroup),
+}
+
+/// A change to the permission of an actor, role or actor group for an action, `None` representing the absence of a permission
+#[cw_serde]
+pub struct PermissionChange {
+    pub id: u64,
+    pub action: String,
+    pub grantee: PermissionGrantee,
+    pub changed_by: Addr,
+    pub timestamp: Timestamp,
+    pub old_permission: Option<Permission>,
+    pub new_permission: Option<Permission>,
+}
+
+/// A role being granted to or revoked from an actor
+///
+/// Shares its ids with `PermissionChange` so that both histories can be ordered together
+#[cw_serde]
+pub struct RoleMembershipChange {
+    pub id: u64,
+    pub role: String,
+    pub actor: String,
+    pub granted: bool,
+    pub changed_by: Addr,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct RolePermissionInfo {
+    pub role: String,
+    pub action: String,
+    pub permission: Permission,
+}
+
+/// A set of actors whose membership is determined by querying another contract
+///
+/// - **AddressList** - Any address included in the given address list module
+/// - **Directory** - Any ADO registered below the given VFS directory, e.g. `/home/team/`
+/// - **CW721Holders** - Any address holding at least one token from the given CW721 collection
+///
+/// A group whose membership query fails is treated as having no members, unless it is blacklisted in which case it is treated as including every actor
+#[cw_serde]
+pub enum ActorGroup {
+    AddressList(AndrAddr),
+    Directory(AndrAddr),
+    CW721Holders(AndrAddr),
+}
+
+#[cw_serde]
+pub struct GroupPermissionInfo {
+    pub group: ActorGroup,
+    pub action: String,
+    pub permission: Permission,
+}
+
 /// An enum to represent a user's permission for an action
 ///
 /// - **Blacklisted** - The user cannot perform the action until after the provided expiration
 /// - **Limited** - The user can perform the action while uses are remaining and before the provided expiration **for a permissioned action**
 /// - **Whitelisted** - The user can perform the action until the provided expiration **for a permissioned action**
+/// - **RateLimited** - The user can perform the action `uses_per_window` times per `window` before the provided expiration **for a permissioned action**
 ///
 /// Expiration defaults to `Never` if not provided
 #[cw_serde]
@@ -26,6 +101,18 @@ pub enum Permission {
         uses: u32,
     },
     Whitelisted(Option<Expiration>),
+    RateLimited {
+        expiration: Option<Expiration>,
+        uses_per_window: u32,
+        window: Duration,
+    },
+}
+
+/// The uses of a rate limited permission an actor has consumed within the current window
+#[cw_serde]
+pub struct RateLimitWindow {
+    pub end: Expiration,
+    pub uses: u32,
 }
 
 impl std::default::Default for Permission {
@@ -47,6 +134,18 @@ impl Permission {
         Self::Limited { expiration, uses }
     }
 
+    pub fn rate_limited(
+        expiration: Option<Expiration>,
+        uses_per_window: u32,
+        window: Duration,
+    ) -> Self {
+        Self::RateLimited {
+            expiration,
+            uses_per_window,
+            window,
+        }
+    }
+
     pub fn is_permissioned(&self, env: &Env, strict: bool) -> bool {
         match self {
             Self::Blacklisted(expiration) => {
@@ -76,6 +175,15 @@ impl Permission {
                 }
                 true
             }
+            // Uses within the current window are tracked in storage by the ADO
+            Self::RateLimited { expiration, .. } => {
+                if let Some(expiration) = expiration {
+                    if expiration.is_expired(&env.block) {
+                        return !strict;
+                    }
+                }
+                true
+            }
         }
     }
 
@@ -84,12 +192,13 @@ impl Permission {
             Self::Blacklisted(expiration) => expiration.unwrap_or_default(),
             Self::Limited { expiration, .. } => expiration.unwrap_or_default(),
             Self::Whitelisted(expiration) => expiration.unwrap_or_default(),
+            Self::RateLimited { expiration, .. } => expiration.unwrap_or_default(),
         }
     }
 
     pub fn consume_use(&mut self) {
         if let Self::Limited { uses, .. } = self {
-            *uses -= 1
+            *uses = uses.saturating_sub(1)
         }
     }
 }
@@ -118,6 +227,18 @@ impl fmt::Display for Permission {
                     "whitelisted".to_string()
                 }
             }
+            Self::RateLimited {
+                expiration,
+                uses_per_window,
+                window,
+                ..
+            } => {
+                if let Some(expiration) = expiration {
+                    format!("rate_limited:{expiration}:{uses_per_window}:{window}")
+                } else {
+                    format!("rate_limited:{uses_per_window}:{window}")
+                }
+            }
         };
         write!(f, "{self_as_string}")
     }
@@ -9,7 +9,7 @@ use crate::{
 };
 use cosmwasm_std::{
     attr, from_binary, to_binary, Addr, Api, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    QuerierWrapper, Response, Storage, SubMsg, WasmMsg,
+    QuerierWrapper, Response, Storage, SubMsg, Uint128, WasmMsg,
 };
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -51,6 +51,15 @@ impl<'a> ADOContract<'a> {
                 AndromedaMsg::UpdateOwner { address } => {
                     self.execute_update_owner(ctx.deps, ctx.info, address)
                 }
+                AndromedaMsg::ProposeOwner {
+                    address,
+                    expiration,
+                } => self.execute_propose_owner(ctx, address, expiration),
+                AndromedaMsg::AcceptOwnership {} => self.execute_accept_ownership(ctx),
+                AndromedaMsg::CancelOwnershipProposal {} => {
+                    self.execute_cancel_ownership_proposal(ctx)
+                }
+                AndromedaMsg::RenounceOwnership {} => self.execute_renounce_ownership(ctx),
                 AndromedaMsg::UpdateOperators { operators } => {
                     self.execute_update_operators(ctx.deps, ctx.info, operators)
                 }
@@ -89,9 +98,36 @@ impl<'a> ADOContract<'a> {
                 AndromedaMsg::RemovePermission { action, actor } => {
                     self.execute_remove_permission(ctx, actor, action)
                 }
+                AndromedaMsg::SetPermissions { entries } => {
+                    self.execute_set_permissions(ctx, entries)
+                }
                 AndromedaMsg::PermissionAction { action } => {
                     self.execute_permission_action(ctx, action)
                 }
+                AndromedaMsg::CreateRole { role } => self.execute_create_role(ctx, role),
+                AndromedaMsg::DeleteRole { role } => self.execute_delete_role(ctx, role),
+                AndromedaMsg::GrantRole { role, actors } => {
+                    self.execute_grant_role(ctx, role, actors)
+                }
+                AndromedaMsg::RevokeRole { role, actors } => {
+                    self.execute_revoke_role(ctx, role, actors)
+                }
+                AndromedaMsg::SetRolePermission {
+                    role,
+                    action,
+                    permission,
+                } => self.execute_set_role_permission(ctx, role, action, permission),
+                AndromedaMsg::RemoveRolePermission { role, action } => {
+                    self.execute_remove_role_permission(ctx, role, action)
+                }
+                AndromedaMsg::SetGroupPermission {
+                    group,
+                    action,
+                    permission,
+                } => self.execute_set_group_permission(ctx, group, action, permission),
+                AndromedaMsg::RemoveGroupPermission { group, action } => {
+                    self.execute_remove_group_permission(ctx, group, action)
+                }
                 AndromedaMsg::AMPReceive(_) => panic!("AMP Receive should be handled separately"),
                 AndromedaMsg::Deposit { .. } => Err(ContractError::NotImplemented { msg: None }),
             },
@@ -227,11 +263,27 @@ impl<'a> ADOContract<'a> {
         querier: &QuerierWrapper,
         action: String,
         payee: Addr,
+    ) -> Result<SubMsg, ContractError> {
+        self.pay_fee_for_amount(storage, querier, action, payee, None)
+    }
+
+    /// Generates a message to pay a fee for an action that moves funds, `amount` is used to charge any percentage based fee
+    pub fn pay_fee_for_amount(
+        &self,
+        storage: &dyn Storage,
+        querier: &QuerierWrapper,
+        action: String,
+        payee: Addr,
+        amount: Option<Uint128>,
     ) -> Result<SubMsg, ContractError> {
         let kernel_address = self.get_kernel_address(storage)?;
         let economics_contract_address =
             AOSQuerier::kernel_address_getter(querier, &kernel_address, "economics")?;
-        let economics_msg = EconomicsExecuteMsg::PayFee { action, payee };
+        let economics_msg = EconomicsExecuteMsg::PayFee {
+            action,
+            payee,
+            amount,
+        };
         let msg = SubMsg::reply_on_error(
             CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: economics_contract_address.to_string(),
@@ -2,7 +2,9 @@ use crate::ado_contract::ADOContract;
 use crate::error::ContractError;
 use crate::os::aos_querier::AOSQuerier;
 use crate::os::kernel::QueryMsg as KernelQueryMsg;
-use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, ReplyOn, Storage, SubMsg, WasmMsg};
+use cosmwasm_std::{
+    ensure, Addr, Binary, CosmosMsg, QuerierWrapper, ReplyOn, Storage, SubMsg, WasmMsg,
+};
 
 impl<'a> ADOContract<'a> {
     pub fn generate_instantiate_msg(
@@ -15,6 +17,7 @@ impl<'a> ADOContract<'a> {
         sender: String,
     ) -> Result<SubMsg, ContractError> {
         match self.get_code_id(storage, querier, &ado_type) {
+            Err(err @ ContractError::ADOVersionYanked { .. }) => Err(err),
             Err(_) => Err(ContractError::InvalidModule {
                 msg: Some(String::from(
                     "ADO type provided does not have a valid Code Id",
@@ -51,6 +54,7 @@ impl<'a> ADOContract<'a> {
         Ok(address)
     }
 
+    /// Gets the code ID for the given ADO type, yanked versions are refused
     fn get_code_id(
         &self,
         storage: &mut dyn Storage,
@@ -59,7 +63,13 @@ impl<'a> ADOContract<'a> {
     ) -> Result<u64, ContractError> {
         // Do we want to cache the factory address?
         let adodb_addr = self.get_adodb_address(storage, querier)?;
-        let code_id: u64 = AOSQuerier::code_id_getter(querier, &adodb_addr, name)?;
-        Ok(code_id)
+        let resolved = AOSQuerier::ado_version_resolver(querier, &adodb_addr, name)?;
+        ensure!(
+            !resolved.status.is_yanked(),
+            ContractError::ADOVersionYanked {
+                version: format!("{}@{}", resolved.ado_type, resolved.version)
+            }
+        );
+        Ok(resolved.code_id)
     }
 }
@@ -1,9 +1,13 @@
+use crate::ado_base::ownership::OwnershipProposal;
 use crate::ado_contract::ADOContract;
+use crate::amp::AndrAddr;
+use crate::common::context::ExecuteContext;
 use crate::error::ContractError;
 use cosmwasm_std::{attr, ensure, DepsMut, MessageInfo, Response, Storage};
+use cw_utils::Expiration;
 
 impl<'a> ADOContract<'a> {
-    /// Updates the current contract owner. **Only executable by the current contract owner.**
+    /// Updates the current contract owner immediately, discarding any pending ownership proposal. **Only executable by the current contract owner.**
     pub fn execute_update_owner(
         &self,
         deps: DepsMut,
@@ -16,6 +20,7 @@ impl<'a> ADOContract<'a> {
         );
         let new_owner_addr = deps.api.addr_validate(&new_owner)?;
         self.owner.save(deps.storage, &new_owner_addr)?;
+        self.pending_owner.remove(deps.storage);
 
         Ok(Response::new().add_attributes(vec![
             attr("action", "update_owner"),
@@ -23,6 +28,99 @@ impl<'a> ADOContract<'a> {
         ]))
     }
 
+    /// Proposes a new contract owner, who must accept ownership before the optional expiration. **Only executable by the current contract owner.**
+    ///
+    /// Replaces any existing proposal.
+    pub fn execute_propose_owner(
+        &self,
+        ctx: ExecuteContext,
+        address: AndrAddr,
+        expiration: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        if let Some(expiration) = expiration {
+            ensure!(
+                !expiration.is_expired(&ctx.env.block),
+                ContractError::OwnershipProposalExpired {}
+            );
+        }
+        let owner = address.get_raw_address(&ctx.deps.as_ref())?;
+        self.pending_owner.save(
+            ctx.deps.storage,
+            &OwnershipProposal {
+                owner: owner.clone(),
+                expiration,
+            },
+        )?;
+
+        Ok(Response::new().add_attributes(vec![
+            attr("action", "propose_owner"),
+            attr("pending_owner", owner),
+            attr("expiration", expiration.unwrap_or_default().to_string()),
+        ]))
+    }
+
+    /// Accepts a pending ownership proposal. **Only executable by the proposed owner.**
+    pub fn execute_accept_ownership(&self, ctx: ExecuteContext) -> Result<Response, ContractError> {
+        let proposal = self
+            .pending_owner
+            .may_load(ctx.deps.storage)?
+            .ok_or(ContractError::NoPendingOwner {})?;
+        ensure!(
+            proposal.owner == ctx.info.sender,
+            ContractError::Unauthorized {}
+        );
+        if let Some(expiration) = proposal.expiration {
+            ensure!(
+                !expiration.is_expired(&ctx.env.block),
+                ContractError::OwnershipProposalExpired {}
+            );
+        }
+        self.owner.save(ctx.deps.storage, &proposal.owner)?;
+        self.pending_owner.remove(ctx.deps.storage);
+
+        Ok(Response::new().add_attributes(vec![
+            attr("action", "accept_ownership"),
+            attr("value", proposal.owner),
+        ]))
+    }
+
+    /// Cancels a pending ownership proposal. **Only executable by the current contract owner.**
+    pub fn execute_cancel_ownership_proposal(
+        &self,
+        ctx: ExecuteContext,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        ensure!(
+            self.pending_owner.exists(ctx.deps.storage),
+            ContractError::NoPendingOwner {}
+        );
+        self.pending_owner.remove(ctx.deps.storage);
+
+        Ok(Response::new().add_attributes(vec![attr("action", "cancel_ownership_proposal")]))
+    }
+
+    /// Removes the contract owner, leaving the contract without an owner permanently. **Only executable by the current contract owner.**
+    pub fn execute_renounce_ownership(
+        &self,
+        ctx: ExecuteContext,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        self.owner.remove(ctx.deps.storage);
+        self.pending_owner.remove(ctx.deps.storage);
+
+        Ok(Response::new().add_attributes(vec![attr("action", "renounce_ownership")]))
+    }
+
     /// Updates the current contract operators. **Only executable by the current contract owner.**
     pub fn execute_update_operators(
         &self,
@@ -51,14 +149,14 @@ impl<'a> ADOContract<'a> {
 
     /// Helper function to query if a given address is the current contract owner.
     ///
-    /// Returns a boolean value indicating if the given address is the contract owner.
+    /// Returns a boolean value indicating if the given address is the contract owner, always `false` once ownership has been renounced.
     pub fn is_contract_owner(
         &self,
         storage: &dyn Storage,
         addr: &str,
     ) -> Result<bool, ContractError> {
-        let owner = self.owner.load(storage)?;
-        Ok(addr == owner)
+        let owner = self.owner.may_load(storage)?;
+        Ok(owner.map_or(false, |owner| addr == owner))
     }
 
     /// Helper function to query if a given address is the current contract owner or operator.
@@ -72,3 +170,155 @@ impl<'a> ADOContract<'a> {
         Ok(self.is_contract_owner(storage, addr)? || self.is_operator(storage, addr))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    use super::*;
+
+    #[test]
+    fn test_two_step_ownership() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let new_owner = mock_info("new_owner", &[]);
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), new_owner.clone(), env.clone());
+        let err = contract.execute_accept_ownership(ctx).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), new_owner.clone(), env.clone());
+        let err = contract
+            .execute_propose_owner(ctx, AndrAddr::from_string("new_owner"), None)
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract
+            .execute_propose_owner(
+                ctx,
+                AndrAddr::from_string("new_owner"),
+                Some(Expiration::AtHeight(env.block.height)),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::OwnershipProposalExpired {});
+
+        let expiration = Expiration::AtHeight(env.block.height + 10);
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_propose_owner(ctx, AndrAddr::from_string("new_owner"), Some(expiration))
+            .unwrap();
+
+        let pending = contract.query_pending_owner(deps.as_ref()).unwrap();
+        assert_eq!(pending.pending_owner, Some("new_owner".to_string()));
+        assert_eq!(pending.expiration, Some(expiration));
+        // Ownership does not change until the proposal is accepted
+        assert!(contract
+            .is_contract_owner(deps.as_ref().storage, owner.sender.as_str())
+            .unwrap());
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("anyone", &[]), env.clone());
+        let err = contract.execute_accept_ownership(ctx).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        env.block.height += 10;
+        let ctx = ExecuteContext::new(deps.as_mut(), new_owner.clone(), env.clone());
+        let err = contract.execute_accept_ownership(ctx).unwrap_err();
+        assert_eq!(err, ContractError::OwnershipProposalExpired {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_propose_owner(ctx, AndrAddr::from_string("new_owner"), None)
+            .unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), new_owner.clone(), env);
+        contract.execute_accept_ownership(ctx).unwrap();
+
+        assert_eq!(
+            contract.query_contract_owner(deps.as_ref()).unwrap().owner,
+            "new_owner"
+        );
+        let pending = contract.query_pending_owner(deps.as_ref()).unwrap();
+        assert_eq!(pending.pending_owner, None);
+    }
+
+    #[test]
+    fn test_cancel_ownership_proposal() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract.execute_cancel_ownership_proposal(ctx).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_propose_owner(ctx, AndrAddr::from_string("new_owner"), None)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("new_owner", &[]), env.clone());
+        let err = contract.execute_cancel_ownership_proposal(ctx).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner, env.clone());
+        contract.execute_cancel_ownership_proposal(ctx).unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("new_owner", &[]), env);
+        let err = contract.execute_accept_ownership(ctx).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+    }
+
+    #[test]
+    fn test_renounce_ownership() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_propose_owner(ctx, AndrAddr::from_string("new_owner"), None)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("anyone", &[]), env.clone());
+        let err = contract.execute_renounce_ownership(ctx).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract.execute_renounce_ownership(ctx).unwrap();
+
+        assert!(!contract
+            .is_contract_owner(deps.as_ref().storage, owner.sender.as_str())
+            .unwrap());
+        assert_eq!(
+            contract.query_contract_owner(deps.as_ref()).unwrap().owner,
+            ""
+        );
+
+        // Renouncing discards the pending proposal
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("new_owner", &[]), env.clone());
+        let err = contract.execute_accept_ownership(ctx).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner, env);
+        let err = contract
+            .execute_update_owner(ctx.deps, ctx.info, "owner".to_string())
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+}
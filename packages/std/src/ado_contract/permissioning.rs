@@ -1,10 +1,19 @@
 use crate::{
-    ado_base::permissioning::{Permission, PermissionInfo},
+    ado_base::permissioning::{
+        ActorGroup, GroupPermissionInfo, Permission, PermissionChange, PermissionEntry,
+        PermissionGrantee, PermissionInfo, RateLimitWindow, RoleMembershipChange,
+        RolePermissionInfo,
+    },
     amp::{messages::AMPPkt, AndrAddr},
     common::context::ExecuteContext,
     error::ContractError,
+    os::{aos_querier::AOSQuerier, vfs::QueryMsg as VFSQueryMsg},
 };
-use cosmwasm_std::{ensure, Deps, Env, MessageInfo, Order, Response, Storage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, Addr, Deps, Env, MessageInfo, Order, QuerierWrapper, Response, Storage,
+};
+use cw721::{Cw721QueryMsg, TokensResponse};
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex};
 
 use super::ADOContract;
@@ -36,58 +45,73 @@ pub fn permissions<'a>() -> IndexedMap<'a, &'a str, PermissionInfo, PermissionsI
     IndexedMap::new("andr_permissions", indexes)
 }
 
+/// Mirrors the address list module's query for membership checks
+#[cw_serde]
+pub(crate) enum AddressListQueryMsg {
+    IncludesAddress { address: String },
+}
+
+#[cw_serde]
+pub(crate) struct IncludesAddressResponse {
+    pub included: bool,
+}
+
+impl ActorGroup {
+    /// The key under which permissions for the group are stored
+    fn key(&self) -> String {
+        match self {
+            Self::AddressList(addr) => format!("address_list:{addr}"),
+            Self::Directory(path) => format!("directory:{path}"),
+            Self::CW721Holders(addr) => format!("cw721:{addr}"),
+        }
+    }
+}
+
+impl PermissionGrantee {
+    /// The key under which changes to the grantee's permissions are stored, role and group keys cannot collide with addresses
+    fn key(&self) -> String {
+        match self {
+            Self::Actor(actor) => actor.to_string(),
+            Self::Role(role) => format!("role:{role}"),
+            Self::Group(group) => group.key(),
+        }
+    }
+}
+
+/// Converts a home directory path to the form returned by the VFS `Paths` query, e.g. `/home/team/` to `team`
+fn normalize_directory(path: &AndrAddr) -> Result<String, ContractError> {
+    let raw_path = path.get_raw_path();
+    let dir = raw_path
+        .strip_prefix("/home/")
+        .or_else(|| raw_path.strip_prefix('~'))
+        .ok_or(ContractError::InvalidPathname {
+            error: Some("Directory permissions require a home directory path".to_string()),
+        })?
+        .trim_end_matches('/');
+    ensure!(
+        !dir.is_empty(),
+        ContractError::InvalidPathname {
+            error: Some("Directory permissions require a home directory path".to_string()),
+        }
+    );
+    Ok(dir.to_string())
+}
+
 impl<'a> ADOContract<'a> {
     /// Determines if the provided actor is authorised to perform the given action
     ///
+    /// Permissions set directly for the actor take precedence over any permissions granted through the actor's roles or groups
+    ///
     /// Returns an error if the given action is not permissioned for the given actor
     pub fn is_permissioned(
         &self,
         store: &mut dyn Storage,
+        querier: &QuerierWrapper,
         env: Env,
         action: impl Into<String>,
         actor: impl Into<String>,
     ) -> Result<(), ContractError> {
-        // Converted to strings for cloning
-        let action_string: String = action.into();
-        let actor_string: String = actor.into();
-
-        if self.is_contract_owner(store, actor_string.as_str())? {
-            return Ok(());
-        }
-
-        let permission = Self::get_permission(store, action_string.clone(), actor_string.clone())?;
-        let permissioned_action = self
-            .permissioned_actions
-            .may_load(store, action_string.clone())?
-            .unwrap_or(false);
-        match permission {
-            Some(mut permission) => {
-                ensure!(
-                    permission.is_permissioned(&env, permissioned_action),
-                    ContractError::Unauthorized {}
-                );
-
-                // Consume a use for a limited permission
-                if let Permission::Limited { .. } = permission {
-                    permission.consume_use();
-                    permissions().save(
-                        store,
-                        (action_string.clone() + actor_string.as_str()).as_str(),
-                        &PermissionInfo {
-                            action: action_string,
-                            actor: actor_string,
-                            permission,
-                        },
-                    )?;
-                }
-
-                Ok(())
-            }
-            None => {
-                ensure!(!permissioned_action, ContractError::Unauthorized {});
-                Ok(())
-            }
-        }
+        self.check_permission(store, querier, env, action.into(), actor.into(), false)
     }
 
     /// Determines if the provided actor is authorised to perform the given action
@@ -98,43 +122,272 @@ impl<'a> ADOContract<'a> {
     pub fn is_permissioned_strict(
         &self,
         store: &mut dyn Storage,
+        querier: &QuerierWrapper,
         env: Env,
         action: impl Into<String>,
         actor: impl Into<String>,
     ) -> Result<(), ContractError> {
-        // Converted to strings for cloning
-        let action_string: String = action.into();
-        let actor_string: String = actor.into();
+        self.check_permission(store, querier, env, action.into(), actor.into(), true)
+    }
 
-        if self.is_contract_owner(store, actor_string.as_str())? {
+    fn check_permission(
+        &self,
+        store: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        env: Env,
+        action: String,
+        actor: String,
+        strict: bool,
+    ) -> Result<(), ContractError> {
+        if self.is_contract_owner(store, actor.as_str())? {
             return Ok(());
         }
 
-        let permission = Self::get_permission(store, action_string.clone(), actor_string.clone())?;
+        let permissioned_action = strict
+            || self
+                .permissioned_actions
+                .may_load(store, action.clone())?
+                .unwrap_or(false);
+
+        if let Some(permission) = Self::get_permission(store, action.clone(), actor.clone())? {
+            ensure!(
+                self.is_permission_usable(
+                    store,
+                    &env,
+                    &action,
+                    &actor,
+                    &permission,
+                    permissioned_action,
+                    false
+                )?,
+                ContractError::Unauthorized {}
+            );
+            self.consume_permission_use(store, &env, &action, &actor, permission, false)?;
+
+            return Ok(());
+        }
+
+        let mut grants = self.get_actor_role_permissions(store, &action, &actor)?;
+        grants.extend(self.get_actor_group_permissions(store, querier, &action, &actor)?);
+        if grants.is_empty() {
+            ensure!(!permissioned_action, ContractError::Unauthorized {});
+            return Ok(());
+        }
+
+        // A blacklist on any of the actor's roles or groups denies the action
+        ensure!(
+            !grants.iter().any(|permission| {
+                matches!(permission, Permission::Blacklisted(..))
+                    && !permission.is_permissioned(&env, permissioned_action)
+            }),
+            ContractError::Unauthorized {}
+        );
+
+        for permission in grants {
+            if self.is_permission_usable(
+                store,
+                &env,
+                &action,
+                &actor,
+                &permission,
+                permissioned_action,
+                true,
+            )? {
+                return self.consume_permission_use(store, &env, &action, &actor, permission, true);
+            }
+        }
+        Err(ContractError::Unauthorized {})
+    }
+
+    /// Determines if the actor may use the given permission for an action, including whether any uses remain
+    ///
+    /// Uses of limited permissions granted through a role or group (`shared`) are counted per actor
+    #[allow(clippy::too_many_arguments)]
+    fn is_permission_usable(
+        &self,
+        store: &dyn Storage,
+        env: &Env,
+        action: &str,
+        actor: &str,
+        permission: &Permission,
+        permissioned_action: bool,
+        shared: bool,
+    ) -> Result<bool, ContractError> {
+        if !permission.is_permissioned(env, permissioned_action) {
+            return Ok(false);
+        }
+        let has_remaining_uses = match permission {
+            Permission::Limited { uses, .. } if shared => {
+                self.shared_permission_uses
+                    .may_load(store, (action, actor))?
+                    .unwrap_or_default()
+                    < *uses
+            }
+            Permission::RateLimited {
+                uses_per_window, ..
+            } => {
+                self.get_rate_limit_window(store, env, action, actor)?
+                    .map_or(0, |window| window.uses)
+                    < *uses_per_window
+            }
+            _ => true,
+        };
+        // Exhausted permissions behave as expired permissions
+        Ok(has_remaining_uses || !permissioned_action)
+    }
+
+    /// Consumes a use of a limited or rate limited permission for the given actor
+    fn consume_permission_use(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        action: &str,
+        actor: &str,
+        mut permission: Permission,
+        shared: bool,
+    ) -> Result<(), ContractError> {
         match permission {
-            Some(mut permission) => {
-                ensure!(
-                    permission.is_permissioned(&env, true),
-                    ContractError::Unauthorized {}
-                );
+            Permission::Limited { .. } if shared => {
+                self.shared_permission_uses.update(
+                    store,
+                    (action, actor),
+                    |uses| -> Result<_, ContractError> {
+                        Ok(uses.unwrap_or_default().saturating_add(1))
+                    },
+                )?;
+            }
+            Permission::Limited { .. } => {
+                permission.consume_use();
+                Self::set_permission(store, action, actor, permission)?;
+            }
+            Permission::RateLimited { window, .. } => {
+                // A new window starts with the first use after the current window has ended
+                let window = match self.get_rate_limit_window(store, env, action, actor)? {
+                    Some(current) => RateLimitWindow {
+                        uses: current.uses.saturating_add(1),
+                        ..current
+                    },
+                    None => RateLimitWindow {
+                        end: window.after(&env.block),
+                        uses: 1,
+                    },
+                };
+                self.rate_limit_windows
+                    .save(store, (action, actor), &window)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Gets the actor's current window for a rate limited permission, if it has not yet ended
+    fn get_rate_limit_window(
+        &self,
+        store: &dyn Storage,
+        env: &Env,
+        action: &str,
+        actor: &str,
+    ) -> Result<Option<RateLimitWindow>, ContractError> {
+        Ok(self
+            .rate_limit_windows
+            .may_load(store, (action, actor))?
+            .filter(|window| !window.end.is_expired(&env.block)))
+    }
+
+    /// Gets the permissions for the given action granted by each of the actor's roles
+    fn get_actor_role_permissions(
+        &self,
+        store: &dyn Storage,
+        action: &str,
+        actor: &str,
+    ) -> Result<Vec<Permission>, ContractError> {
+        let roles = self
+            .actor_roles
+            .prefix(actor)
+            .keys(store, None, None, Order::Ascending)
+            .collect::<Result<Vec<String>, _>>()?;
+        let mut role_permissions = vec![];
+        for role in roles {
+            if let Some(permission) = self
+                .role_permissions
+                .may_load(store, (role.as_str(), action))?
+            {
+                role_permissions.push(permission);
+            }
+        }
+        Ok(role_permissions)
+    }
 
-                // Consume a use for a limited permission
-                if let Permission::Limited { .. } = permission {
-                    permission.consume_use();
-                    permissions().save(
-                        store,
-                        (action_string.clone() + actor_string.as_str()).as_str(),
-                        &PermissionInfo {
-                            action: action_string,
-                            actor: actor_string,
-                            permission,
-                        },
-                    )?;
-                }
-
-                Ok(())
+    /// Gets the permissions for the given action granted by each group the actor belongs to
+    ///
+    /// Group membership is determined by querying the contract backing each group
+    fn get_actor_group_permissions(
+        &self,
+        store: &dyn Storage,
+        querier: &QuerierWrapper,
+        action: &str,
+        actor: &str,
+    ) -> Result<Vec<Permission>, ContractError> {
+        let groups = self
+            .group_permissions
+            .prefix(action)
+            .range(store, None, None, Order::Ascending)
+            .collect::<Result<Vec<(String, GroupPermissionInfo)>, _>>()?;
+        let mut group_permissions = vec![];
+        for (_, info) in groups {
+            // A group whose membership cannot be queried, e.g. as its contract has been migrated or the VFS is unavailable, has no members.
+            // A blacklist that cannot be evaluated applies to every actor so that its members are not let through
+            let is_member = match self.query_group_membership(store, querier, &info.group, actor) {
+                Ok(is_member) => is_member,
+                Err(_) => matches!(info.permission, Permission::Blacklisted(..)),
+            };
+            if is_member {
+                group_permissions.push(info.permission);
+            }
+        }
+        Ok(group_permissions)
+    }
+
+    fn query_group_membership(
+        &self,
+        store: &dyn Storage,
+        querier: &QuerierWrapper,
+        group: &ActorGroup,
+        actor: &str,
+    ) -> Result<bool, ContractError> {
+        match group {
+            ActorGroup::AddressList(addr) => {
+                let res: IncludesAddressResponse = querier.query_wasm_smart(
+                    addr.as_str(),
+                    &AddressListQueryMsg::IncludesAddress {
+                        address: actor.to_string(),
+                    },
+                )?;
+                Ok(res.included)
+            }
+            ActorGroup::Directory(path) => {
+                let kernel_address = self.kernel_address.load(store)?;
+                let vfs_address = AOSQuerier::vfs_address_getter(querier, &kernel_address)?;
+                let paths: Vec<String> = querier.query_wasm_smart(
+                    vfs_address,
+                    &VFSQueryMsg::Paths {
+                        addr: Addr::unchecked(actor),
+                    },
+                )?;
+                let prefix = format!("{path}/");
+                Ok(paths.iter().any(|p| p.starts_with(prefix.as_str())))
+            }
+            ActorGroup::CW721Holders(addr) => {
+                let res: TokensResponse = querier.query_wasm_smart(
+                    addr.as_str(),
+                    &Cw721QueryMsg::Tokens {
+                        owner: actor.to_string(),
+                        start_after: None,
+                        limit: Some(1),
+                    },
+                )?;
+                Ok(!res.tokens.is_empty())
             }
-            None => Err(ContractError::Unauthorized {}),
         }
     }
 
@@ -201,14 +454,19 @@ impl<'a> ADOContract<'a> {
         action: impl Into<String>,
         permission: Permission,
     ) -> Result<Response, ContractError> {
-        Self::is_contract_owner(self, ctx.deps.storage, ctx.info.sender.as_str())?;
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
         let actor_addr = actor.get_raw_address(&ctx.deps.as_ref())?;
         let action = action.into();
-        Self::set_permission(
+        self.update_permission(
             ctx.deps.storage,
+            &ctx.env,
+            &ctx.info.sender,
             action.clone(),
-            actor_addr.clone(),
-            permission.clone(),
+            actor_addr.to_string(),
+            Some(permission.clone()),
         )?;
 
         Ok(Response::default().add_attributes(vec![
@@ -219,6 +477,48 @@ impl<'a> ADOContract<'a> {
         ]))
     }
 
+    /// Execute handler for setting several permissions at once
+    ///
+    /// **Whitelisted/Limited permissions will only work for permissioned actions**
+    pub fn execute_set_permissions(
+        &self,
+        ctx: ExecuteContext,
+        entries: Vec<PermissionEntry>,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        ensure!(
+            !entries.is_empty(),
+            ContractError::InvalidPermissionEntries {}
+        );
+        let mut res = Response::default().add_attribute("action", "set_permissions");
+        for PermissionEntry {
+            actor,
+            action,
+            permission,
+        } in entries
+        {
+            let actor_addr = actor.get_raw_address(&ctx.deps.as_ref())?;
+            self.update_permission(
+                ctx.deps.storage,
+                &ctx.env,
+                &ctx.info.sender,
+                action.clone(),
+                actor_addr.to_string(),
+                Some(permission.clone()),
+            )?;
+            res = res.add_attributes(vec![
+                ("actor", actor_addr.as_str()),
+                ("action", action.as_str()),
+                ("permission", permission.to_string().as_str()),
+            ]);
+        }
+
+        Ok(res)
+    }
+
     /// Execute handler for setting permission
     /// TODO: Add permission for execute context
     pub fn execute_remove_permission(
@@ -227,10 +527,20 @@ impl<'a> ADOContract<'a> {
         actor: AndrAddr,
         action: impl Into<String>,
     ) -> Result<Response, ContractError> {
-        Self::is_contract_owner(self, ctx.deps.storage, ctx.info.sender.as_str())?;
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
         let actor_addr = actor.get_raw_address(&ctx.deps.as_ref())?;
         let action = action.into();
-        Self::remove_permission(ctx.deps.storage, action.clone(), actor_addr.clone())?;
+        self.update_permission(
+            ctx.deps.storage,
+            &ctx.env,
+            &ctx.info.sender,
+            action.clone(),
+            actor_addr.to_string(),
+            None,
+        )?;
 
         Ok(Response::default().add_attributes(vec![
             ("action", "remove_permission"),
@@ -239,6 +549,462 @@ impl<'a> ADOContract<'a> {
         ]))
     }
 
+    /// Sets or removes the permission for the given action and actor, recording the change in the permission history
+    fn update_permission(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        action: String,
+        actor: String,
+        permission: Option<Permission>,
+    ) -> Result<(), ContractError> {
+        let old_permission = Self::get_permission(store, action.as_str(), actor.as_str())?;
+        match permission.clone() {
+            Some(permission) => {
+                Self::set_permission(store, action.as_str(), actor.as_str(), permission)?
+            }
+            None => Self::remove_permission(store, action.as_str(), actor.as_str())?,
+        }
+        self.record_permission_change(
+            store,
+            env,
+            sender,
+            action,
+            PermissionGrantee::Actor(AndrAddr::from_string(actor)),
+            old_permission,
+            permission,
+        )
+    }
+
+    /// Sets or removes the permission a role grants for the given action, recording the change in the permission history
+    fn update_role_permission(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        role: &str,
+        action: String,
+        permission: Option<Permission>,
+    ) -> Result<(), ContractError> {
+        let old_permission = self
+            .role_permissions
+            .may_load(store, (role, action.as_str()))?;
+        match &permission {
+            Some(permission) => {
+                self.role_permissions
+                    .save(store, (role, action.as_str()), permission)?
+            }
+            None => self.role_permissions.remove(store, (role, action.as_str())),
+        }
+        self.record_permission_change(
+            store,
+            env,
+            sender,
+            action,
+            PermissionGrantee::Role(role.to_string()),
+            old_permission,
+            permission,
+        )
+    }
+
+    /// Sets or removes the permission an actor group receives for the given action, recording the change in the permission history
+    fn update_group_permission(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        group: ActorGroup,
+        action: String,
+        permission: Option<Permission>,
+    ) -> Result<(), ContractError> {
+        let key = group.key();
+        let old_permission = self
+            .group_permissions
+            .may_load(store, (action.as_str(), key.as_str()))?
+            .map(|info| info.permission);
+        match &permission {
+            Some(permission) => self.group_permissions.save(
+                store,
+                (action.as_str(), key.as_str()),
+                &GroupPermissionInfo {
+                    group: group.clone(),
+                    action: action.clone(),
+                    permission: permission.clone(),
+                },
+            )?,
+            None => self
+                .group_permissions
+                .remove(store, (action.as_str(), key.as_str())),
+        }
+        self.record_permission_change(
+            store,
+            env,
+            sender,
+            action,
+            PermissionGrantee::Group(group),
+            old_permission,
+            permission,
+        )
+    }
+
+    /// Records a change to the permission of an actor, role or actor group, ignoring changes that leave the permission unchanged
+    #[allow(clippy::too_many_arguments)]
+    fn record_permission_change(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        action: String,
+        grantee: PermissionGrantee,
+        old_permission: Option<Permission>,
+        new_permission: Option<Permission>,
+    ) -> Result<(), ContractError> {
+        if old_permission == new_permission {
+            return Ok(());
+        }
+        let id = self.next_history_id(store)?;
+        let key = grantee.key();
+        self.permission_history.save(
+            store,
+            (action.as_str(), key.as_str(), id),
+            &PermissionChange {
+                id,
+                action: action.clone(),
+                grantee,
+                changed_by: sender.clone(),
+                timestamp: env.block.time,
+                old_permission,
+                new_permission,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Adds or removes an actor from a role, recording the change in the role history if membership changed
+    fn update_role_membership(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        role: &str,
+        actor: &str,
+        granted: bool,
+    ) -> Result<(), ContractError> {
+        if self.role_members.has(store, (role, actor)) == granted {
+            return Ok(());
+        }
+        if granted {
+            self.role_members.save(store, (role, actor), &true)?;
+            self.actor_roles.save(store, (actor, role), &true)?;
+        } else {
+            self.role_members.remove(store, (role, actor));
+            self.actor_roles.remove(store, (actor, role));
+        }
+
+        let id = self.next_history_id(store)?;
+        self.role_history.save(
+            store,
+            (role, id),
+            &RoleMembershipChange {
+                id,
+                role: role.to_string(),
+                actor: actor.to_string(),
+                granted,
+                changed_by: sender.clone(),
+                timestamp: env.block.time,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Gets the id for the next permission or role membership change
+    fn next_history_id(&self, store: &mut dyn Storage) -> Result<u64, ContractError> {
+        let id = self
+            .permission_history_count
+            .may_load(store)?
+            .unwrap_or_default();
+        self.permission_history_count.save(store, &(id + 1))?;
+        Ok(id)
+    }
+
+    /// Execute handler for creating a new role
+    pub fn execute_create_role(
+        &self,
+        ctx: ExecuteContext,
+        role: String,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        ensure!(!role.is_empty(), ContractError::InvalidRoleName {});
+        ensure!(
+            !self.roles.has(ctx.deps.storage, role.as_str()),
+            ContractError::RoleAlreadyExists { role }
+        );
+        self.roles.save(ctx.deps.storage, role.as_str(), &true)?;
+
+        Ok(Response::default().add_attributes(vec![("action", "create_role"), ("role", &role)]))
+    }
+
+    /// Execute handler for deleting a role along with its members and permissions
+    pub fn execute_delete_role(
+        &self,
+        ctx: ExecuteContext,
+        role: String,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        self.ensure_role_exists(ctx.deps.storage, &role)?;
+        let members = self
+            .role_members
+            .prefix(role.as_str())
+            .keys(ctx.deps.storage, None, None, Order::Ascending)
+            .collect::<Result<Vec<String>, _>>()?;
+        for member in members {
+            self.update_role_membership(
+                ctx.deps.storage,
+                &ctx.env,
+                &ctx.info.sender,
+                &role,
+                &member,
+                false,
+            )?;
+        }
+        let actions = self
+            .role_permissions
+            .prefix(role.as_str())
+            .keys(ctx.deps.storage, None, None, Order::Ascending)
+            .collect::<Result<Vec<String>, _>>()?;
+        for action in actions {
+            self.update_role_permission(
+                ctx.deps.storage,
+                &ctx.env,
+                &ctx.info.sender,
+                &role,
+                action,
+                None,
+            )?;
+        }
+        self.roles.remove(ctx.deps.storage, role.as_str());
+
+        Ok(Response::default().add_attributes(vec![("action", "delete_role"), ("role", &role)]))
+    }
+
+    /// Execute handler for granting a role to several actors
+    pub fn execute_grant_role(
+        &self,
+        ctx: ExecuteContext,
+        role: String,
+        actors: Vec<AndrAddr>,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        self.ensure_role_exists(ctx.deps.storage, &role)?;
+        ensure!(!actors.is_empty(), ContractError::InvalidRoleActors {});
+        let mut res = Response::default()
+            .add_attributes(vec![("action", "grant_role"), ("role", role.as_str())]);
+        for actor in actors {
+            let actor_addr = actor.get_raw_address(&ctx.deps.as_ref())?;
+            self.update_role_membership(
+                ctx.deps.storage,
+                &ctx.env,
+                &ctx.info.sender,
+                &role,
+                actor_addr.as_str(),
+                true,
+            )?;
+            res = res.add_attribute("actor", actor_addr);
+        }
+
+        Ok(res)
+    }
+
+    /// Execute handler for revoking a role from several actors
+    pub fn execute_revoke_role(
+        &self,
+        ctx: ExecuteContext,
+        role: String,
+        actors: Vec<AndrAddr>,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        self.ensure_role_exists(ctx.deps.storage, &role)?;
+        ensure!(!actors.is_empty(), ContractError::InvalidRoleActors {});
+        let mut res = Response::default()
+            .add_attributes(vec![("action", "revoke_role"), ("role", role.as_str())]);
+        for actor in actors {
+            let actor_addr = actor.get_raw_address(&ctx.deps.as_ref())?;
+            self.update_role_membership(
+                ctx.deps.storage,
+                &ctx.env,
+                &ctx.info.sender,
+                &role,
+                actor_addr.as_str(),
+                false,
+            )?;
+            res = res.add_attribute("actor", actor_addr);
+        }
+
+        Ok(res)
+    }
+
+    /// Execute handler for setting the permission a role grants for an action
+    ///
+    /// **Whitelisted/Limited permissions will only work for permissioned actions**
+    pub fn execute_set_role_permission(
+        &self,
+        ctx: ExecuteContext,
+        role: String,
+        action: String,
+        permission: Permission,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        self.ensure_role_exists(ctx.deps.storage, &role)?;
+        self.update_role_permission(
+            ctx.deps.storage,
+            &ctx.env,
+            &ctx.info.sender,
+            &role,
+            action.clone(),
+            Some(permission.clone()),
+        )?;
+
+        Ok(Response::default().add_attributes(vec![
+            ("action", "set_role_permission"),
+            ("role", role.as_str()),
+            ("action", action.as_str()),
+            ("permission", permission.to_string().as_str()),
+        ]))
+    }
+
+    /// Execute handler for removing the permission a role grants for an action
+    pub fn execute_remove_role_permission(
+        &self,
+        ctx: ExecuteContext,
+        role: String,
+        action: String,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        self.ensure_role_exists(ctx.deps.storage, &role)?;
+        self.update_role_permission(
+            ctx.deps.storage,
+            &ctx.env,
+            &ctx.info.sender,
+            &role,
+            action.clone(),
+            None,
+        )?;
+
+        Ok(Response::default().add_attributes(vec![
+            ("action", "remove_role_permission"),
+            ("role", role.as_str()),
+            ("action", action.as_str()),
+        ]))
+    }
+
+    /// Execute handler for setting the permission an actor group receives for an action
+    ///
+    /// Address list and CW721 groups are stored by contract address, directories in the form returned by the VFS `Paths` query
+    ///
+    /// **Whitelisted/Limited permissions will only work for permissioned actions**
+    pub fn execute_set_group_permission(
+        &self,
+        ctx: ExecuteContext,
+        group: ActorGroup,
+        action: String,
+        permission: Permission,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        let group = Self::resolve_actor_group(&ctx.deps.as_ref(), group)?;
+        let key = group.key();
+        self.update_group_permission(
+            ctx.deps.storage,
+            &ctx.env,
+            &ctx.info.sender,
+            group,
+            action.clone(),
+            Some(permission.clone()),
+        )?;
+
+        Ok(Response::default().add_attributes(vec![
+            ("action", "set_group_permission"),
+            ("group", key.as_str()),
+            ("action", action.as_str()),
+            ("permission", permission.to_string().as_str()),
+        ]))
+    }
+
+    /// Execute handler for removing the permission an actor group receives for an action
+    pub fn execute_remove_group_permission(
+        &self,
+        ctx: ExecuteContext,
+        group: ActorGroup,
+        action: String,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
+        let group = Self::resolve_actor_group(&ctx.deps.as_ref(), group)?;
+        let key = group.key();
+        self.update_group_permission(
+            ctx.deps.storage,
+            &ctx.env,
+            &ctx.info.sender,
+            group,
+            action.clone(),
+            None,
+        )?;
+
+        Ok(Response::default().add_attributes(vec![
+            ("action", "remove_group_permission"),
+            ("group", key.as_str()),
+            ("action", action.as_str()),
+        ]))
+    }
+
+    /// Resolves the contract addresses and directory paths of an actor group to the form used for membership checks
+    fn resolve_actor_group(deps: &Deps, group: ActorGroup) -> Result<ActorGroup, ContractError> {
+        Ok(match group {
+            ActorGroup::AddressList(addr) => {
+                ActorGroup::AddressList(AndrAddr::from_string(addr.get_raw_address(deps)?))
+            }
+            ActorGroup::Directory(path) => {
+                ActorGroup::Directory(AndrAddr::from_string(normalize_directory(&path)?))
+            }
+            ActorGroup::CW721Holders(addr) => {
+                ActorGroup::CW721Holders(AndrAddr::from_string(addr.get_raw_address(deps)?))
+            }
+        })
+    }
+
+    fn ensure_role_exists(&self, store: &dyn Storage, role: &str) -> Result<(), ContractError> {
+        ensure!(
+            self.roles.has(store, role),
+            ContractError::RoleNotFound {
+                role: role.to_string()
+            }
+        );
+        Ok(())
+    }
+
     /// Enables permissioning for a given action
     pub fn permission_action(
         &self,
@@ -260,8 +1026,11 @@ impl<'a> ADOContract<'a> {
         ctx: ExecuteContext,
         action: impl Into<String>,
     ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
         let action_string: String = action.into();
-        Self::is_contract_owner(self, ctx.deps.storage, ctx.info.sender.as_str())?;
         self.permission_action(action_string.clone(), ctx.deps.storage)?;
         Ok(Response::default().add_attributes(vec![
             ("action", "permission_action"),
@@ -274,8 +1043,11 @@ impl<'a> ADOContract<'a> {
         ctx: ExecuteContext,
         action: impl Into<String>,
     ) -> Result<Response, ContractError> {
+        ensure!(
+            self.is_contract_owner(ctx.deps.storage, ctx.info.sender.as_str())?,
+            ContractError::Unauthorized {}
+        );
         let action_string: String = action.into();
-        Self::is_contract_owner(self, ctx.deps.storage, ctx.info.sender.as_str())?;
         Self::disable_action_permission(self, action_string.clone(), ctx.deps.storage);
         Ok(Response::default().add_attributes(vec![
             ("action", "disable_action_permission"),
@@ -305,6 +1077,57 @@ impl<'a> ADOContract<'a> {
         Ok(permissions)
     }
 
+    /// Queries the history of changes to the permission of an actor, role or actor group for a given action, oldest first
+    pub fn query_permission_history(
+        &self,
+        deps: Deps,
+        action: String,
+        grantee: PermissionGrantee,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<PermissionChange>, ContractError> {
+        let grantee = match grantee {
+            PermissionGrantee::Actor(actor) => {
+                PermissionGrantee::Actor(AndrAddr::from_string(actor.get_raw_address(&deps)?))
+            }
+            PermissionGrantee::Group(group) => {
+                PermissionGrantee::Group(Self::resolve_actor_group(&deps, group)?)
+            }
+            role => role,
+        };
+        let key = grantee.key();
+        let min = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        let history = self
+            .permission_history
+            .prefix((action.as_str(), key.as_str()))
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .map(|change| change.map(|(_, change)| change))
+            .collect::<Result<Vec<PermissionChange>, _>>()?;
+        Ok(history)
+    }
+
+    /// Queries the history of actors being granted or revoked a given role, oldest first
+    pub fn query_role_history(
+        &self,
+        deps: Deps,
+        role: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<RoleMembershipChange>, ContractError> {
+        let min = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        let history = self
+            .role_history
+            .prefix(role.as_str())
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .map(|change| change.map(|(_, change)| change))
+            .collect::<Result<Vec<RoleMembershipChange>, _>>()?;
+        Ok(history)
+    }
+
     pub fn query_permissioned_actions(&self, deps: Deps) -> Result<Vec<String>, ContractError> {
         let actions = self
             .permissioned_actions
@@ -313,6 +1136,93 @@ impl<'a> ADOContract<'a> {
             .collect::<Vec<String>>();
         Ok(actions)
     }
+
+    /// Queries all members of a given role
+    pub fn query_role_members(
+        &self,
+        deps: Deps,
+        role: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    ) -> Result<Vec<String>, ContractError> {
+        self.ensure_role_exists(deps.storage, &role)?;
+        let min = start_after.as_deref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        let members = self
+            .role_members
+            .prefix(role.as_str())
+            .keys(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(members)
+    }
+
+    /// Queries all roles granted to a given actor
+    pub fn query_actor_roles(
+        &self,
+        deps: Deps,
+        actor: impl Into<String>,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    ) -> Result<Vec<String>, ContractError> {
+        let actor = actor.into();
+        let min = start_after.as_deref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        let roles = self
+            .actor_roles
+            .prefix(actor.as_str())
+            .keys(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(roles)
+    }
+
+    /// Queries all permissions granted by a given role
+    pub fn query_role_permissions(
+        &self,
+        deps: Deps,
+        role: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    ) -> Result<Vec<RolePermissionInfo>, ContractError> {
+        self.ensure_role_exists(deps.storage, &role)?;
+        let min = start_after.as_deref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        let permissions = self
+            .role_permissions
+            .prefix(role.as_str())
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .map(|p| {
+                p.map(|(action, permission)| RolePermissionInfo {
+                    role: role.clone(),
+                    action,
+                    permission,
+                })
+            })
+            .collect::<Result<Vec<RolePermissionInfo>, _>>()?;
+        Ok(permissions)
+    }
+
+    /// Queries all actor group permissions for a given action
+    pub fn query_group_permissions(
+        &self,
+        deps: Deps,
+        action: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    ) -> Result<Vec<GroupPermissionInfo>, ContractError> {
+        let min = start_after.as_deref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        let permissions = self
+            .group_permissions
+            .prefix(action.as_str())
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .map(|p| p.map(|(_, info)| info))
+            .collect::<Result<Vec<GroupPermissionInfo>, _>>()?;
+        Ok(permissions)
+    }
 }
 
 /// Checks if the provided context is authorised to perform the provided action.
@@ -322,6 +1232,7 @@ impl<'a> ADOContract<'a> {
 /// - The context contains AMP context and the **previous sender** or **origin** are considered the actor
 pub fn is_context_permissioned(
     storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
     info: &MessageInfo,
     env: &Env,
     ctx: &Option<AMPPkt>,
@@ -334,12 +1245,14 @@ pub fn is_context_permissioned(
             let action: String = action.into();
             let is_origin_permissioned = contract.is_permissioned(
                 storage,
+                querier,
                 env.clone(),
                 action.clone(),
                 amp_ctx.ctx.get_origin().as_str(),
             );
             let is_previous_sender_permissioned = contract.is_permissioned(
                 storage,
+                querier,
                 env.clone(),
                 action,
                 amp_ctx.ctx.get_previous_sender().as_str(),
@@ -347,7 +1260,13 @@ pub fn is_context_permissioned(
             Ok(is_origin_permissioned.is_ok() || is_previous_sender_permissioned.is_ok())
         }
         None => Ok(contract
-            .is_permissioned(storage, env.clone(), action, info.sender.to_string())
+            .is_permissioned(
+                storage,
+                querier,
+                env.clone(),
+                action,
+                info.sender.to_string(),
+            )
             .is_ok()),
     }
 }
@@ -359,6 +1278,7 @@ pub fn is_context_permissioned(
 /// - The context contains AMP context and the **previous sender** or **origin** are considered the actor
 pub fn is_context_permissioned_strict(
     storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
     info: &MessageInfo,
     env: &Env,
     ctx: &Option<AMPPkt>,
@@ -371,12 +1291,14 @@ pub fn is_context_permissioned_strict(
             let action: String = action.into();
             let is_origin_permissioned = contract.is_permissioned_strict(
                 storage,
+                querier,
                 env.clone(),
                 action.clone(),
                 amp_ctx.ctx.get_origin().as_str(),
             );
             let is_previous_sender_permissioned = contract.is_permissioned_strict(
                 storage,
+                querier,
                 env.clone(),
                 action,
                 amp_ctx.ctx.get_previous_sender().as_str(),
@@ -384,7 +1306,13 @@ pub fn is_context_permissioned_strict(
             Ok(is_origin_permissioned.is_ok() || is_previous_sender_permissioned.is_ok())
         }
         None => Ok(contract
-            .is_permissioned_strict(storage, env.clone(), action, info.sender.to_string())
+            .is_permissioned_strict(
+                storage,
+                querier,
+                env.clone(),
+                action,
+                info.sender.to_string(),
+            )
             .is_ok()),
     }
 }
@@ -395,9 +1323,15 @@ mod tests {
         testing::{mock_dependencies, mock_env, mock_info},
         Addr,
     };
-    use cw_utils::Expiration;
-
-    use crate::amp::messages::AMPPkt;
+    use cw_utils::{Duration, Expiration};
+
+    use crate::{
+        amp::messages::AMPPkt,
+        testing::mock_querier::{
+            mock_dependencies_custom, MOCK_ADDRESS_LIST_CONTRACT, MOCK_CW721_CONTRACT,
+            UNWHITELISTED_ADDRESS,
+        },
+    };
 
     use super::*;
 
@@ -418,31 +1352,61 @@ mod tests {
             .unwrap();
 
         // Test Whitelisting
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
 
         assert!(res.is_err());
         let permission = Permission::whitelisted(None);
         ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
 
         assert!(res.is_ok());
 
         ADOContract::remove_permission(deps.as_mut().storage, action, actor).unwrap();
 
         // Test Limited
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
 
         assert!(res.is_err());
         let permission = Permission::limited(None, 1);
         ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
 
         assert!(res.is_ok());
 
         // Ensure use is consumed
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
         assert!(res.is_err());
 
         ADOContract::remove_permission(deps.as_mut().storage, action, actor).unwrap();
@@ -451,7 +1415,13 @@ mod tests {
         let permission = Permission::blacklisted(None);
         ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env, action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            actor,
+        );
 
         assert!(res.is_err());
     }
@@ -476,7 +1446,13 @@ mod tests {
         let permission = Permission::blacklisted(None);
         ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env, action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            actor,
+        );
 
         assert!(res.is_err());
     }
@@ -493,14 +1469,25 @@ mod tests {
             .save(deps.as_mut().storage, &Addr::unchecked("owner"))
             .unwrap();
 
-        let res =
-            contract.is_permissioned_strict(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned_strict(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
         assert!(res.is_err());
 
         let permission = Permission::whitelisted(None);
         ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
 
-        let res = contract.is_permissioned_strict(deps.as_mut().storage, env, action, actor);
+        let res = contract.is_permissioned_strict(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            actor,
+        );
         assert!(res.is_ok());
     }
 
@@ -516,61 +1503,201 @@ mod tests {
             .save(deps.as_mut().storage, &Addr::unchecked(actor))
             .unwrap();
 
-        let res =
-            contract.is_permissioned_strict(deps.as_mut().storage, env.clone(), action, actor);
+        let res = contract.is_permissioned_strict(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
         assert!(res.is_ok());
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env, action, actor);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            actor,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_permission_expiration() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        env.block.height = 0;
+        let action = "action";
+        let actor = "actor";
+        let contract = ADOContract::default();
+        let block = 100;
+        let expiration = Expiration::AtHeight(block);
+        contract
+            .owner
+            .save(deps.as_mut().storage, &Addr::unchecked("owner"))
+            .unwrap();
+
+        ADOContract::default()
+            .permission_action(action, deps.as_mut().storage)
+            .unwrap();
+
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
+
+        assert!(res.is_err());
+
+        // Test Whitelist
+        let permission = Permission::Whitelisted(Some(expiration));
+        ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
+
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
+        assert!(res.is_ok());
+
+        env.block.height = block + 1;
+
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
+        assert!(res.is_err());
+
+        env.block.height = 0;
+        // Test Blacklist
+        let permission = Permission::Blacklisted(Some(expiration));
+        ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
+
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
+        assert!(res.is_err());
+
+        env.block.height = block + 1;
+
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            actor,
+        );
         assert!(res.is_ok());
     }
 
     #[test]
-    fn test_permission_expiration() {
+    fn test_rate_limited_permission() {
         let mut deps = mock_dependencies();
         let mut env = mock_env();
-        env.block.height = 0;
         let action = "action";
         let actor = "actor";
         let contract = ADOContract::default();
-        let block = 100;
-        let expiration = Expiration::AtHeight(block);
         contract
             .owner
             .save(deps.as_mut().storage, &Addr::unchecked("owner"))
             .unwrap();
-
-        ADOContract::default()
+        contract
             .permission_action(action, deps.as_mut().storage)
             .unwrap();
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
-
-        assert!(res.is_err());
-
-        // Test Whitelist
-        let permission = Permission::Whitelisted(Some(expiration));
+        let permission = Permission::rate_limited(None, 2, Duration::Time(100));
         ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
-        assert!(res.is_ok());
-
-        env.block.height = block + 1;
+        for _ in 0..2 {
+            let res = contract.is_permissioned(
+                &mut deps.storage,
+                &QuerierWrapper::new(&deps.querier),
+                env.clone(),
+                action,
+                actor,
+            );
+            assert!(res.is_ok());
+        }
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        // The window's uses have been consumed
+        let res = contract.is_permissioned_strict(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
         assert!(res.is_err());
 
-        env.block.height = 0;
-        // Test Blacklist
-        let permission = Permission::Blacklisted(Some(expiration));
-        ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
+        let window = contract
+            .rate_limit_windows
+            .load(deps.as_ref().storage, (action, actor))
+            .unwrap();
+        assert_eq!(
+            window,
+            RateLimitWindow {
+                end: Expiration::AtTime(env.block.time.plus_seconds(100)),
+                uses: 2,
+            }
+        );
+        // The permission itself is unchanged
+        let permission = ADOContract::get_permission(deps.as_ref().storage, action, actor)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            permission,
+            Permission::rate_limited(None, 2, Duration::Time(100))
+        );
+
+        // Uses are refilled once the window ends
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            actor,
+        );
+        assert!(res.is_ok());
 
-        let res = contract.is_permissioned(deps.as_mut().storage, env.clone(), action, actor);
+        let window = contract
+            .rate_limit_windows
+            .load(deps.as_ref().storage, (action, actor))
+            .unwrap();
+        assert_eq!(
+            window,
+            RateLimitWindow {
+                end: Expiration::AtTime(env.block.time.plus_seconds(100)),
+                uses: 1,
+            }
+        );
+
+        // Expired rate limited permissions are no longer usable
+        let permission = Permission::rate_limited(
+            Some(Expiration::AtHeight(env.block.height)),
+            2,
+            Duration::Height(10),
+        );
+        ADOContract::set_permission(deps.as_mut().storage, action, actor, permission).unwrap();
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            actor,
+        );
         assert!(res.is_err());
-
-        env.block.height = block + 1;
-
-        let res = contract.is_permissioned(deps.as_mut().storage, env, action, actor);
-        assert!(res.is_ok());
     }
 
     #[test]
@@ -591,6 +1718,7 @@ mod tests {
 
         assert!(is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -605,6 +1733,7 @@ mod tests {
 
         assert!(!is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -618,6 +1747,7 @@ mod tests {
 
         assert!(is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -630,6 +1760,7 @@ mod tests {
 
         assert!(!is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -643,6 +1774,7 @@ mod tests {
 
         assert!(is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -656,6 +1788,7 @@ mod tests {
 
         assert!(is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -669,6 +1802,7 @@ mod tests {
 
         assert!(!is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -682,6 +1816,7 @@ mod tests {
 
         assert!(is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -694,6 +1829,7 @@ mod tests {
 
         assert!(is_context_permissioned(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -720,6 +1856,7 @@ mod tests {
 
         assert!(!is_context_permissioned_strict(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -733,6 +1870,7 @@ mod tests {
 
         assert!(is_context_permissioned_strict(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -745,6 +1883,7 @@ mod tests {
 
         assert!(!is_context_permissioned_strict(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -758,6 +1897,7 @@ mod tests {
 
         assert!(is_context_permissioned_strict(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -771,6 +1911,7 @@ mod tests {
 
         assert!(is_context_permissioned_strict(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -783,6 +1924,7 @@ mod tests {
 
         assert!(!is_context_permissioned_strict(
             context.deps.storage,
+            &context.deps.querier,
             &context.info,
             &context.env,
             &context.amp_ctx,
@@ -834,6 +1976,867 @@ mod tests {
         assert_eq!(permissions.len(), 5);
     }
 
+    #[test]
+    fn test_permission_history() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let action = "action";
+        let actor = "actor";
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info(actor, &[]), env.clone());
+        let err = contract
+            .execute_set_permission(
+                ctx,
+                AndrAddr::from_string(actor),
+                action,
+                Permission::whitelisted(None),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_permission(
+                ctx,
+                AndrAddr::from_string(actor),
+                action,
+                Permission::whitelisted(None),
+            )
+            .unwrap();
+        let first_change = env.block.time;
+
+        env.block.time = env.block.time.plus_seconds(10);
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract.execute_set_permissions(ctx, vec![]).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermissionEntries {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_permissions(
+                ctx,
+                vec![
+                    PermissionEntry {
+                        actor: AndrAddr::from_string(actor),
+                        action: action.to_string(),
+                        permission: Permission::blacklisted(None),
+                    },
+                    PermissionEntry {
+                        actor: AndrAddr::from_string("actor2"),
+                        action: action.to_string(),
+                        permission: Permission::whitelisted(None),
+                    },
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            ADOContract::get_permission(deps.as_ref().storage, action, "actor2").unwrap(),
+            Some(Permission::whitelisted(None))
+        );
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_remove_permission(ctx, AndrAddr::from_string(actor), action)
+            .unwrap();
+
+        let history = contract
+            .query_permission_history(
+                deps.as_ref(),
+                action.to_string(),
+                PermissionGrantee::Actor(AndrAddr::from_string(actor)),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                PermissionChange {
+                    id: 0,
+                    action: action.to_string(),
+                    grantee: PermissionGrantee::Actor(AndrAddr::from_string(actor)),
+                    changed_by: owner.sender.clone(),
+                    timestamp: first_change,
+                    old_permission: None,
+                    new_permission: Some(Permission::whitelisted(None)),
+                },
+                PermissionChange {
+                    id: 1,
+                    action: action.to_string(),
+                    grantee: PermissionGrantee::Actor(AndrAddr::from_string(actor)),
+                    changed_by: owner.sender.clone(),
+                    timestamp: env.block.time,
+                    old_permission: Some(Permission::whitelisted(None)),
+                    new_permission: Some(Permission::blacklisted(None)),
+                },
+                PermissionChange {
+                    id: 3,
+                    action: action.to_string(),
+                    grantee: PermissionGrantee::Actor(AndrAddr::from_string(actor)),
+                    changed_by: owner.sender.clone(),
+                    timestamp: env.block.time,
+                    old_permission: Some(Permission::blacklisted(None)),
+                    new_permission: None,
+                },
+            ]
+        );
+
+        let history = contract
+            .query_permission_history(
+                deps.as_ref(),
+                action.to_string(),
+                PermissionGrantee::Actor(AndrAddr::from_string(actor)),
+                Some(1),
+                Some(1),
+            )
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, 3);
+
+        let history = contract
+            .query_permission_history(
+                deps.as_ref(),
+                action.to_string(),
+                PermissionGrantee::Actor(AndrAddr::from_string("actor2")),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, 2);
+
+        // Role and group permissions and role membership are recorded
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_create_role(ctx, "minter".to_string())
+            .unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_role_permission(
+                ctx,
+                "minter".to_string(),
+                action.to_string(),
+                Permission::whitelisted(None),
+            )
+            .unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_grant_role(
+                ctx,
+                "minter".to_string(),
+                vec![AndrAddr::from_string(actor), AndrAddr::from_string(actor)],
+            )
+            .unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_delete_role(ctx, "minter".to_string())
+            .unwrap();
+        let group = ActorGroup::AddressList(AndrAddr::from_string("address_list"));
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_group_permission(
+                ctx,
+                group.clone(),
+                action.to_string(),
+                Permission::whitelisted(None),
+            )
+            .unwrap();
+        // Removing a permission that does not exist is not recorded
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_remove_group_permission(ctx, group.clone(), "other".to_string())
+            .unwrap();
+
+        let history = contract
+            .query_permission_history(
+                deps.as_ref(),
+                action.to_string(),
+                PermissionGrantee::Role("minter".to_string()),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|change| (change.id, change.new_permission.clone()))
+                .collect::<Vec<_>>(),
+            vec![(4, Some(Permission::whitelisted(None))), (7, None)]
+        );
+        let history = contract
+            .query_role_history(deps.as_ref(), "minter".to_string(), None, None)
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                RoleMembershipChange {
+                    id: 5,
+                    role: "minter".to_string(),
+                    actor: actor.to_string(),
+                    granted: true,
+                    changed_by: owner.sender.clone(),
+                    timestamp: env.block.time,
+                },
+                RoleMembershipChange {
+                    id: 6,
+                    role: "minter".to_string(),
+                    actor: actor.to_string(),
+                    granted: false,
+                    changed_by: owner.sender.clone(),
+                    timestamp: env.block.time,
+                },
+            ]
+        );
+        let history = contract
+            .query_permission_history(
+                deps.as_ref(),
+                action.to_string(),
+                PermissionGrantee::Group(group),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![PermissionChange {
+                id: 8,
+                action: action.to_string(),
+                grantee: PermissionGrantee::Group(ActorGroup::AddressList(AndrAddr::from_string(
+                    "address_list"
+                ))),
+                changed_by: owner.sender,
+                timestamp: env.block.time,
+                old_permission: None,
+                new_permission: Some(Permission::whitelisted(None)),
+            }]
+        );
+        assert_eq!(
+            contract
+                .permission_history_count
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_role_permissions() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let action = "action";
+        let role = "minter";
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+        contract
+            .permission_action(action, deps.as_mut().storage)
+            .unwrap();
+
+        // Only the owner can manage roles
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("actor", &[]), env.clone());
+        let err = contract
+            .execute_create_role(ctx, role.to_string())
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract
+            .execute_grant_role(ctx, role.to_string(), vec![AndrAddr::from_string("actor")])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RoleNotFound {
+                role: role.to_string()
+            }
+        );
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract.execute_create_role(ctx, role.to_string()).unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract
+            .execute_create_role(ctx, role.to_string())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RoleAlreadyExists {
+                role: role.to_string()
+            }
+        );
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract
+            .execute_grant_role(ctx, role.to_string(), vec![])
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoleActors {});
+
+        // Roles can be granted to several actors at once
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_grant_role(
+                ctx,
+                role.to_string(),
+                vec![
+                    AndrAddr::from_string("actor"),
+                    AndrAddr::from_string("actor2"),
+                ],
+            )
+            .unwrap();
+
+        // Role members are not permissioned until the role has a permission for the action
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor",
+        );
+        assert!(res.is_err());
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_role_permission(
+                ctx,
+                role.to_string(),
+                action.to_string(),
+                Permission::whitelisted(None),
+            )
+            .unwrap();
+
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor",
+        );
+        assert!(res.is_ok());
+        let res = contract.is_permissioned_strict(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor2",
+        );
+        assert!(res.is_ok());
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor3",
+        );
+        assert!(res.is_err());
+
+        let info = mock_info("actor2", &[]);
+        assert!(is_context_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            &info,
+            &env,
+            &None,
+            action
+        )
+        .unwrap());
+
+        // Permissions set directly on the actor take precedence over roles
+        ADOContract::set_permission(
+            deps.as_mut().storage,
+            action,
+            "actor2",
+            Permission::blacklisted(None),
+        )
+        .unwrap();
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor2",
+        );
+        assert!(res.is_err());
+
+        // A blacklist on any role denies the action
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_create_role(ctx, "banned".to_string())
+            .unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_role_permission(
+                ctx,
+                "banned".to_string(),
+                action.to_string(),
+                Permission::blacklisted(None),
+            )
+            .unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_grant_role(
+                ctx,
+                "banned".to_string(),
+                vec![AndrAddr::from_string("actor")],
+            )
+            .unwrap();
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor",
+        );
+        assert!(res.is_err());
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_revoke_role(
+                ctx,
+                "banned".to_string(),
+                vec![AndrAddr::from_string("actor")],
+            )
+            .unwrap();
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor",
+        );
+        assert!(res.is_ok());
+
+        // Limited and rate limited role permissions count uses for each member
+        ADOContract::remove_permission(deps.as_mut().storage, action, "actor2").unwrap();
+        for permission in [
+            Permission::limited(None, 1),
+            Permission::rate_limited(None, 1, Duration::Time(100)),
+        ] {
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract
+                .execute_set_role_permission(ctx, role.to_string(), action.to_string(), permission)
+                .unwrap();
+            for actor in ["actor2", "actor"] {
+                let res = contract.is_permissioned(
+                    &mut deps.storage,
+                    &QuerierWrapper::new(&deps.querier),
+                    env.clone(),
+                    action,
+                    actor,
+                );
+                assert!(res.is_ok());
+                let res = contract.is_permissioned(
+                    &mut deps.storage,
+                    &QuerierWrapper::new(&deps.querier),
+                    env.clone(),
+                    action,
+                    actor,
+                );
+                assert!(res.is_err());
+            }
+        }
+        // The role's permission is unchanged
+        let res = contract
+            .query_role_permissions(deps.as_ref(), role.to_string(), None, None)
+            .unwrap();
+        assert_eq!(
+            res[0].permission,
+            Permission::rate_limited(None, 1, Duration::Time(100))
+        );
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner, env.clone());
+        contract
+            .execute_revoke_role(ctx, role.to_string(), vec![AndrAddr::from_string("actor2")])
+            .unwrap();
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env,
+            action,
+            "actor2",
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_query_roles() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+
+        for role in ["admin", "minter"] {
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract.execute_create_role(ctx, role.to_string()).unwrap();
+        }
+        for (role, actor) in [
+            ("minter", "actor"),
+            ("minter", "actor2"),
+            ("admin", "actor"),
+        ] {
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract
+                .execute_grant_role(ctx, role.to_string(), vec![AndrAddr::from_string(actor)])
+                .unwrap();
+        }
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_set_role_permission(
+                ctx,
+                "minter".to_string(),
+                "mint".to_string(),
+                Permission::whitelisted(None),
+            )
+            .unwrap();
+
+        let members = contract
+            .query_role_members(deps.as_ref(), "minter".to_string(), None, None)
+            .unwrap();
+        assert_eq!(members, vec!["actor".to_string(), "actor2".to_string()]);
+
+        let members = contract
+            .query_role_members(
+                deps.as_ref(),
+                "minter".to_string(),
+                None,
+                Some("actor".to_string()),
+            )
+            .unwrap();
+        assert_eq!(members, vec!["actor2".to_string()]);
+
+        let roles = contract
+            .query_actor_roles(deps.as_ref(), "actor", None, None)
+            .unwrap();
+        assert_eq!(roles, vec!["admin".to_string(), "minter".to_string()]);
+
+        let permissions = contract
+            .query_role_permissions(deps.as_ref(), "minter".to_string(), None, None)
+            .unwrap();
+        assert_eq!(
+            permissions,
+            vec![RolePermissionInfo {
+                role: "minter".to_string(),
+                action: "mint".to_string(),
+                permission: Permission::whitelisted(None),
+            }]
+        );
+
+        let err = contract
+            .query_role_members(deps.as_ref(), "unknown".to_string(), None, None)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RoleNotFound {
+                role: "unknown".to_string()
+            }
+        );
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        contract
+            .execute_remove_role_permission(ctx, "minter".to_string(), "mint".to_string())
+            .unwrap();
+        let permissions = contract
+            .query_role_permissions(deps.as_ref(), "minter".to_string(), None, None)
+            .unwrap();
+        assert!(permissions.is_empty());
+
+        // Deleting a role removes it from its members
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("actor", &[]), env.clone());
+        let err = contract
+            .execute_delete_role(ctx, "minter".to_string())
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let ctx = ExecuteContext::new(deps.as_mut(), owner, env);
+        contract
+            .execute_delete_role(ctx, "minter".to_string())
+            .unwrap();
+        let roles = contract
+            .query_actor_roles(deps.as_ref(), "actor", None, None)
+            .unwrap();
+        assert_eq!(roles, vec!["admin".to_string()]);
+        let err = contract
+            .query_role_members(deps.as_ref(), "minter".to_string(), None, None)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RoleNotFound {
+                role: "minter".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_group_permissions() {
+        let mut deps = mock_dependencies_custom(&[]);
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let action = "action";
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+        contract
+            .permission_action(action, deps.as_mut().storage)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("actor", &[]), env.clone());
+        let err = contract
+            .execute_set_group_permission(
+                ctx,
+                ActorGroup::AddressList(AndrAddr::from_string(MOCK_ADDRESS_LIST_CONTRACT)),
+                action.to_string(),
+                Permission::whitelisted(None),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let groups = [
+            ActorGroup::AddressList(AndrAddr::from_string(MOCK_ADDRESS_LIST_CONTRACT)),
+            ActorGroup::Directory(AndrAddr::from_string("/home/team/")),
+            ActorGroup::CW721Holders(AndrAddr::from_string(MOCK_CW721_CONTRACT)),
+        ];
+        for group in groups {
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract
+                .execute_set_group_permission(
+                    ctx,
+                    group.clone(),
+                    action.to_string(),
+                    Permission::whitelisted(None),
+                )
+                .unwrap();
+
+            let res = contract.is_permissioned(
+                &mut deps.storage,
+                &QuerierWrapper::new(&deps.querier),
+                env.clone(),
+                action,
+                "actor",
+            );
+            assert!(res.is_ok());
+            let res = contract.is_permissioned(
+                &mut deps.storage,
+                &QuerierWrapper::new(&deps.querier),
+                env.clone(),
+                action,
+                UNWHITELISTED_ADDRESS,
+            );
+            assert!(res.is_err());
+
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract
+                .execute_remove_group_permission(ctx, group, action.to_string())
+                .unwrap();
+            let res = contract.is_permissioned(
+                &mut deps.storage,
+                &QuerierWrapper::new(&deps.querier),
+                env.clone(),
+                action,
+                "actor",
+            );
+            assert!(res.is_err());
+        }
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+        let err = contract
+            .execute_set_group_permission(
+                ctx,
+                ActorGroup::Directory(AndrAddr::from_string("/lib/team")),
+                action.to_string(),
+                Permission::whitelisted(None),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPathname {
+                error: Some("Directory permissions require a home directory path".to_string())
+            }
+        );
+
+        // A blacklist on any group denies the action
+        for (group, permission) in [
+            (
+                ActorGroup::Directory(AndrAddr::from_string("~team")),
+                Permission::whitelisted(None),
+            ),
+            (
+                ActorGroup::CW721Holders(AndrAddr::from_string(MOCK_CW721_CONTRACT)),
+                Permission::blacklisted(None),
+            ),
+        ] {
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract
+                .execute_set_group_permission(ctx, group, action.to_string(), permission)
+                .unwrap();
+        }
+        let info = mock_info("actor", &[]);
+        assert!(!is_context_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            &info,
+            &env,
+            &None,
+            action
+        )
+        .unwrap());
+
+        let permissions = contract
+            .query_group_permissions(deps.as_ref(), action.to_string(), None, None)
+            .unwrap();
+        assert_eq!(
+            permissions,
+            vec![
+                GroupPermissionInfo {
+                    group: ActorGroup::CW721Holders(AndrAddr::from_string(MOCK_CW721_CONTRACT)),
+                    action: action.to_string(),
+                    permission: Permission::blacklisted(None),
+                },
+                GroupPermissionInfo {
+                    group: ActorGroup::Directory(AndrAddr::from_string("team")),
+                    action: action.to_string(),
+                    permission: Permission::whitelisted(None),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_permissions_unavailable_group() {
+        // No contracts exist to answer membership queries
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let action = "action";
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+        contract
+            .kernel_address
+            .save(deps.as_mut().storage, &Addr::unchecked("kernel"))
+            .unwrap();
+
+        let groups = [
+            ActorGroup::AddressList(AndrAddr::from_string("migrated_list")),
+            ActorGroup::Directory(AndrAddr::from_string("/home/team/")),
+            ActorGroup::CW721Holders(AndrAddr::from_string("migrated_cw721")),
+        ];
+        for group in groups {
+            let ctx = ExecuteContext::new(deps.as_mut(), owner.clone(), env.clone());
+            contract
+                .execute_set_group_permission(
+                    ctx,
+                    group,
+                    action.to_string(),
+                    Permission::whitelisted(None),
+                )
+                .unwrap();
+        }
+
+        // Actors are treated as non-members rather than failing every permission check
+        let res = contract.is_permissioned(
+            &mut deps.storage,
+            &QuerierWrapper::new(&deps.querier),
+            env.clone(),
+            action,
+            "actor",
+        );
+        assert!(res.is_ok());
+
+        contract
+            .permission_action(action, deps.as_mut().storage)
+            .unwrap();
+        let err = contract
+            .is_permissioned(
+                &mut deps.storage,
+                &QuerierWrapper::new(&deps.querier),
+                env,
+                action,
+                "actor",
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn test_group_permissions_unavailable_blacklisted_group() {
+        // No contract exists to answer membership queries
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        let action = "action";
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &owner.sender)
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), owner, env.clone());
+        contract
+            .execute_set_group_permission(
+                ctx,
+                ActorGroup::AddressList(AndrAddr::from_string("migrated_list")),
+                action.to_string(),
+                Permission::blacklisted(None),
+            )
+            .unwrap();
+
+        // A blacklist that cannot be evaluated denies the action, even if the action is not permissioned
+        let err = contract
+            .is_permissioned(
+                &mut deps.storage,
+                &QuerierWrapper::new(&deps.querier),
+                env,
+                action,
+                "actor",
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn test_execute_permission_action_unauthorized() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let action = "action";
+        let contract = ADOContract::default();
+        contract
+            .owner
+            .save(deps.as_mut().storage, &Addr::unchecked("owner"))
+            .unwrap();
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("actor", &[]), env.clone());
+        let err = contract.execute_permission_action(ctx, action).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        assert!(contract
+            .query_permissioned_actions(deps.as_ref())
+            .unwrap()
+            .is_empty());
+
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("owner", &[]), env.clone());
+        contract.execute_permission_action(ctx, action).unwrap();
+        let ctx = ExecuteContext::new(deps.as_mut(), mock_info("actor", &[]), env);
+        let err = contract
+            .execute_disable_action_permission(ctx, action)
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        assert_eq!(
+            contract.query_permissioned_actions(deps.as_ref()).unwrap(),
+            vec![action.to_string()]
+        );
+    }
+
     #[test]
     fn test_query_permissioned_actions() {
         let mut deps = mock_dependencies();
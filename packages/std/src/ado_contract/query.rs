@@ -5,7 +5,7 @@ use crate::{
         block_height::BlockHeightResponse,
         kernel_address::KernelAddressResponse,
         operators::{IsOperatorResponse, OperatorsResponse},
-        ownership::{ContractOwnerResponse, PublisherResponse},
+        ownership::{ContractOwnerResponse, PendingOwnerResponse, PublisherResponse},
         version::VersionResponse,
         AndromedaQuery,
     },
@@ -28,6 +28,7 @@ impl<'a> ADOContract<'a> {
         match from_binary::<AndromedaQuery>(&msg) {
             Ok(msg) => match msg {
                 AndromedaQuery::Owner {} => encode_binary(&self.query_contract_owner(deps)?),
+                AndromedaQuery::PendingOwner {} => encode_binary(&self.query_pending_owner(deps)?),
                 AndromedaQuery::Operators {} => encode_binary(&self.query_operators(deps)?),
                 AndromedaQuery::OriginalPublisher {} => {
                     encode_binary(&self.query_original_publisher(deps)?)
@@ -58,6 +59,51 @@ impl<'a> ADOContract<'a> {
                 AndromedaQuery::PermissionedActions {} => {
                     encode_binary(&self.query_permissioned_actions(deps)?)
                 }
+                AndromedaQuery::PermissionHistory {
+                    action,
+                    grantee,
+                    start_after,
+                    limit,
+                } => encode_binary(&self.query_permission_history(
+                    deps,
+                    action,
+                    grantee,
+                    start_after,
+                    limit,
+                )?),
+                AndromedaQuery::RoleHistory {
+                    role,
+                    start_after,
+                    limit,
+                } => encode_binary(&self.query_role_history(deps, role, start_after, limit)?),
+                AndromedaQuery::RoleMembers {
+                    role,
+                    limit,
+                    start_after,
+                } => encode_binary(&self.query_role_members(deps, role, limit, start_after)?),
+                AndromedaQuery::ActorRoles {
+                    actor,
+                    limit,
+                    start_after,
+                } => {
+                    let actor = actor.get_raw_address(&deps)?;
+                    encode_binary(&self.query_actor_roles(deps, actor, limit, start_after)?)
+                }
+                AndromedaQuery::RolePermissions {
+                    role,
+                    limit,
+                    start_after,
+                } => encode_binary(&self.query_role_permissions(deps, role, limit, start_after)?),
+                AndromedaQuery::GroupPermissions {
+                    action,
+                    limit,
+                    start_after,
+                } => encode_binary(&self.query_group_permissions(
+                    deps,
+                    action,
+                    limit,
+                    start_after,
+                )?),
                 _ => Err(ContractError::UnsupportedOperation {}),
             },
             Err(_) => Err(ContractError::UnsupportedOperation {}),
@@ -68,10 +114,20 @@ impl<'a> ADOContract<'a> {
 impl<'a> ADOContract<'a> {
     #[inline]
     pub fn query_contract_owner(&self, deps: Deps) -> Result<ContractOwnerResponse, ContractError> {
-        let owner = self.owner.load(deps.storage)?;
+        let owner = self.owner.may_load(deps.storage)?;
 
         Ok(ContractOwnerResponse {
-            owner: owner.to_string(),
+            owner: owner.map(|owner| owner.to_string()).unwrap_or_default(),
+        })
+    }
+
+    #[inline]
+    pub fn query_pending_owner(&self, deps: Deps) -> Result<PendingOwnerResponse, ContractError> {
+        let proposal = self.pending_owner.may_load(deps.storage)?;
+
+        Ok(PendingOwnerResponse {
+            pending_owner: proposal.as_ref().map(|p| p.owner.to_string()),
+            expiration: proposal.and_then(|p| p.expiration),
         })
     }
 
@@ -1,5 +1,11 @@
 #[cfg(feature = "modules")]
 use crate::ado_base::modules::Module;
+use crate::ado_base::{
+    ownership::OwnershipProposal,
+    permissioning::{
+        GroupPermissionInfo, Permission, PermissionChange, RateLimitWindow, RoleMembershipChange,
+    },
+};
 use cosmwasm_std::Addr;
 #[cfg(feature = "withdraw")]
 use cw_asset::AssetInfo;
@@ -7,6 +13,7 @@ use cw_storage_plus::{Item, Map};
 
 pub struct ADOContract<'a> {
     pub(crate) owner: Item<'a, Addr>,
+    pub(crate) pending_owner: Item<'a, OwnershipProposal>,
     pub(crate) original_publisher: Item<'a, Addr>,
     pub(crate) block_height: Item<'a, u64>,
     pub(crate) operators: Map<'a, &'a str, bool>,
@@ -15,6 +22,25 @@ pub struct ADOContract<'a> {
     pub(crate) app_contract: Item<'a, Addr>,
     pub(crate) kernel_address: Item<'a, Addr>,
     pub(crate) permissioned_actions: Map<'a, String, bool>,
+    pub(crate) roles: Map<'a, &'a str, bool>,
+    /// Role members keyed by role then actor
+    pub(crate) role_members: Map<'a, (&'a str, &'a str), bool>,
+    /// Role members keyed by actor then role
+    pub(crate) actor_roles: Map<'a, (&'a str, &'a str), bool>,
+    /// Role permissions keyed by role then action
+    pub(crate) role_permissions: Map<'a, (&'a str, &'a str), Permission>,
+    /// Permission changes keyed by action, grantee and change id
+    pub(crate) permission_history: Map<'a, (&'a str, &'a str, u64), PermissionChange>,
+    /// Role membership changes keyed by role and change id
+    pub(crate) role_history: Map<'a, (&'a str, u64), RoleMembershipChange>,
+    /// The number of permission and role membership changes recorded
+    pub(crate) permission_history_count: Item<'a, u64>,
+    /// Actor group permissions keyed by action then group
+    pub(crate) group_permissions: Map<'a, (&'a str, &'a str), GroupPermissionInfo>,
+    /// The current window of each actor's rate limited permissions keyed by action then actor
+    pub(crate) rate_limit_windows: Map<'a, (&'a str, &'a str), RateLimitWindow>,
+    /// Uses of limited role and group permissions consumed by each actor keyed by action then actor
+    pub(crate) shared_permission_uses: Map<'a, (&'a str, &'a str), u32>,
     #[cfg(feature = "modules")]
     pub(crate) module_info: Map<'a, &'a str, Module>,
     #[cfg(feature = "modules")]
@@ -27,6 +53,7 @@ impl<'a> Default for ADOContract<'a> {
     fn default() -> Self {
         ADOContract {
             owner: Item::new("owner"),
+            pending_owner: Item::new("andr_pending_owner"),
             original_publisher: Item::new("original_publisher"),
             block_height: Item::new("block_height"),
             operators: Map::new("operators"),
@@ -35,6 +62,16 @@ impl<'a> Default for ADOContract<'a> {
             app_contract: Item::new("app_contract"),
             kernel_address: Item::new("kernel_address"),
             permissioned_actions: Map::new("andr_permissioned_actions"),
+            roles: Map::new("andr_roles"),
+            role_members: Map::new("andr_role_members"),
+            actor_roles: Map::new("andr_actor_roles"),
+            role_permissions: Map::new("andr_role_permissions"),
+            group_permissions: Map::new("andr_group_permissions"),
+            rate_limit_windows: Map::new("andr_rate_limit_windows"),
+            shared_permission_uses: Map::new("andr_shared_permission_uses"),
+            permission_history: Map::new("andr_permission_history"),
+            role_history: Map::new("andr_role_history"),
+            permission_history_count: Item::new("andr_permission_history_count"),
             #[cfg(feature = "modules")]
             module_info: Map::new("andr_modules"),
             #[cfg(feature = "modules")]
@@ -24,15 +24,60 @@ pub enum ExecuteMsg {
 #[derive(Default)]
 pub struct IBCConfig {
     pub recovery_addr: Option<AndrAddr>,
+    /// Intermediate chains the message is forwarded through, in order, before reaching the chain in the recipient address
+    pub route: Option<Vec<String>>,
+    /// The lifetime of the IBC packet in seconds, overrides the default of the receiving chain
+    pub timeout: Option<u64>,
+    /// Sends the funds via ICS20 to the recipient on a chain without an Andromeda kernel, using the given memo
+    pub ics20_memo: Option<ICS20Memo>,
 }
 
 impl IBCConfig {
     #[inline]
     pub fn new(recovery_addr: Option<AndrAddr>) -> IBCConfig {
-        IBCConfig { recovery_addr }
+        IBCConfig {
+            recovery_addr,
+            route: None,
+            timeout: None,
+            ics20_memo: None,
+        }
+    }
+
+    /// Adds a route of intermediate chains to the config
+    pub fn with_route(mut self, route: Vec<String>) -> IBCConfig {
+        self.route = Some(route);
+        self
+    }
+
+    /// Sets the lifetime of the IBC packet in seconds
+    pub fn with_timeout(mut self, timeout: u64) -> IBCConfig {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the memo used to send the funds to a chain without an Andromeda kernel
+    pub fn with_ics20_memo(mut self, memo: ICS20Memo) -> IBCConfig {
+        self.ics20_memo = Some(memo);
+        self
     }
 }
 
+/// The memo attached to an ICS20 transfer sent to a chain without an Andromeda kernel
+#[cw_serde]
+pub enum ICS20Memo {
+    /// Transfers the funds to the recipient, executing the message on the recipient contract via ibc-hooks if one is provided
+    IbcHooks,
+    /// Forwards the funds from the receiving chain to the recipient using packet-forward-middleware.
+    ///
+    /// If a message is provided it is executed on the recipient contract via ibc-hooks on the final chain.
+    PacketForward {
+        /// The channel on the receiving chain through which the funds are forwarded
+        channel: String,
+        /// The port on the receiving chain, defaults to `transfer`
+        port: Option<String>,
+    },
+}
+
 /// The configuration of the message to be sent.
 ///
 /// Used when a sub message is generated for the given AMP Msg (only used in the case of Wasm Messages).
@@ -184,9 +229,7 @@ impl AMPMsg {
             msg.config.ibc_config = Some(ibc_config);
             msg
         } else if let Some(recovery_addr) = recovery_addr {
-            let ibc_config = Some(IBCConfig {
-                recovery_addr: Some(recovery_addr),
-            });
+            let ibc_config = Some(IBCConfig::new(Some(recovery_addr)));
             let mut msg = self.clone();
             msg.config.ibc_config = ibc_config;
             msg
@@ -194,6 +237,62 @@ impl AMPMsg {
             self.clone()
         }
     }
+
+    /// Gets the intermediate chains the message must still be forwarded through
+    pub fn get_route(&self) -> Vec<String> {
+        self.config
+            .ibc_config
+            .clone()
+            .and_then(|config| config.route)
+            .unwrap_or_default()
+    }
+
+    /// Gets the chain the message must be relayed to next.
+    ///
+    /// This is the first chain in the message's route or, if no route is provided, the chain in the recipient address.
+    pub fn get_next_chain(&self) -> Option<String> {
+        match self.get_route().first() {
+            Some(chain) => Some(chain.clone()),
+            None => self.recipient.get_chain().map(|chain| chain.to_string()),
+        }
+    }
+
+    /// Gets the packet lifetime in seconds provided by the message's IBC config
+    pub fn get_packet_timeout(&self) -> Option<u64> {
+        self.config
+            .ibc_config
+            .as_ref()
+            .and_then(|config| config.timeout)
+    }
+
+    /// Gets the ICS20 memo used when sending the message to a chain without an Andromeda kernel
+    pub fn get_ics20_memo(&self) -> Option<ICS20Memo> {
+        self.config
+            .ibc_config
+            .clone()
+            .and_then(|config| config.ics20_memo)
+    }
+
+    /// Generates the message to be received by the kernel on the next chain in the message's route.
+    ///
+    /// If the next chain is an intermediate chain the recipient is kept intact and the chain is removed from the route,
+    /// otherwise the recipient is stripped of its protocol and chain.
+    /// Any recovery address is removed as it is only valid on the sending chain.
+    pub fn to_next_hop(&self) -> AMPMsg {
+        let mut msg = self.clone();
+        let route = self.get_route();
+        if let Some(config) = msg.config.ibc_config.as_mut() {
+            config.recovery_addr = None;
+            config.route = match route.len() {
+                0 | 1 => None,
+                _ => Some(route[1..].to_vec()),
+            };
+        }
+        if route.is_empty() {
+            msg.recipient = AndrAddr::from_string(self.recipient.get_raw_path());
+        }
+        msg
+    }
 }
 
 #[cw_serde]
@@ -201,7 +300,12 @@ pub struct AMPCtx {
     origin: String,
     origin_username: Option<AndrAddr>,
     pub previous_sender: String,
+    /// The unique ID assigned to the packet by the first kernel to relay it, kept across every hop so that multi-hop flows can be linked.
+    /// 0 if the packet has not been relayed by a kernel
     pub id: u64,
+    /// The unique ID assigned to this hop of the packet by the kernel that most recently relayed it, 0 if the packet has not been relayed by a kernel
+    #[serde(default)]
+    pub hop_sequence: u64,
 }
 
 impl AMPCtx {
@@ -217,6 +321,7 @@ impl AMPCtx {
             origin_username,
             previous_sender: previous_sender.into(),
             id,
+            hop_sequence: 0,
         }
     }
 
@@ -225,6 +330,11 @@ impl AMPCtx {
         self.origin.clone()
     }
 
+    /// Gets the username of the original sender of a message, if provided
+    pub fn get_origin_username(&self) -> Option<AndrAddr> {
+        self.origin_username.clone()
+    }
+
     /// Gets the previous sender of a message
     pub fn get_previous_sender(&self) -> String {
         self.previous_sender.clone()
@@ -522,12 +632,52 @@ mod tests {
             })
         );
     }
+    #[test]
+    fn test_to_next_hop() {
+        let msg = AMPMsg::new("ibc://chain3/home/user/app", Binary::default(), None);
+        assert_eq!(msg.get_next_chain(), Some("chain3".to_string()));
+        let next = msg.to_next_hop();
+        assert_eq!(next.recipient, AndrAddr::from_string("/home/user/app"));
+
+        let config = AMPMsgConfig::new(
+            None,
+            None,
+            None,
+            Some(
+                IBCConfig::new(Some(AndrAddr::from_string("recovery")))
+                    .with_route(vec!["chain1".to_string(), "chain2".to_string()]),
+            ),
+        );
+        let msg = msg.with_config(config);
+        assert_eq!(msg.get_next_chain(), Some("chain1".to_string()));
+
+        let next = msg.to_next_hop();
+        assert_eq!(next.recipient, msg.recipient);
+        assert_eq!(next.get_route(), vec!["chain2".to_string()]);
+        assert_eq!(next.get_next_chain(), Some("chain2".to_string()));
+        assert!(next
+            .config
+            .ibc_config
+            .clone()
+            .unwrap()
+            .recovery_addr
+            .is_none());
+
+        let next = next.to_next_hop();
+        assert_eq!(next.recipient, msg.recipient);
+        assert!(next.get_route().is_empty());
+        assert_eq!(next.get_next_chain(), Some("chain3".to_string()));
+
+        let next = next.to_next_hop();
+        assert_eq!(next.recipient, AndrAddr::from_string("/home/user/app"));
+    }
+
     #[test]
     fn test_to_json() {
         let msg = AMPPkt::new("origin", "previoussender", vec![]);
 
         let memo = msg.to_json();
-        assert_eq!(memo, "{\"messages\":[],\"ctx\":{\"origin\":\"origin\",\"origin_username\":null,\"previous_sender\":\"previoussender\",\"id\":0}}".to_string());
+        assert_eq!(memo, "{\"messages\":[],\"ctx\":{\"origin\":\"origin\",\"origin_username\":null,\"previous_sender\":\"previoussender\",\"id\":0,\"hop_sequence\":0}}".to_string());
     }
 
     #[test]
@@ -535,6 +685,6 @@ mod tests {
         let msg = AMPPkt::new("origin", "previoussender", vec![]);
         let contract_addr = "contractaddr";
         let memo = msg.to_ibc_hooks_memo(contract_addr.to_string(), "callback".to_string());
-        assert_eq!(memo, "{\"wasm\":{\"contract\":\"contractaddr\",\"msg\":{\"amp_receive\":{\"messages\":[],\"ctx\":{\"origin\":\"origin\",\"origin_username\":null,\"previous_sender\":\"previoussender\",\"id\":0}}}},\"ibc_callback\":\"callback\"}".to_string());
+        assert_eq!(memo, "{\"wasm\":{\"contract\":\"contractaddr\",\"msg\":{\"amp_receive\":{\"messages\":[],\"ctx\":{\"origin\":\"origin\",\"origin_username\":null,\"previous_sender\":\"previoussender\",\"id\":0,\"hop_sequence\":0}}}},\"ibc_callback\":\"callback\"}".to_string());
     }
 }
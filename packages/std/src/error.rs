@@ -26,6 +26,27 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Role {role} does not exist")]
+    RoleNotFound { role: String },
+
+    #[error("Role {role} already exists")]
+    RoleAlreadyExists { role: String },
+
+    #[error("Role name cannot be empty")]
+    InvalidRoleName {},
+
+    #[error("At least one actor must be provided")]
+    InvalidRoleActors {},
+
+    #[error("At least one permission entry must be provided")]
+    InvalidPermissionEntries {},
+
+    #[error("No ownership proposal is pending")]
+    NoPendingOwner {},
+
+    #[error("Ownership proposal has expired")]
+    OwnershipProposalExpired {},
+
     #[error("ContractLocked")]
     ContractLocked {},
 
@@ -296,6 +317,12 @@ pub enum ContractError {
     #[error("InvalidADOVersion: {msg:?}")]
     InvalidADOVersion { msg: Option<String> },
 
+    #[error("ADO version {version} has been yanked")]
+    ADOVersionYanked { version: String },
+
+    #[error("Contract {address} was not published through the ADODB")]
+    UnpublishedADO { address: String },
+
     #[error("AuctionRewardAlreadyClaimed")]
     AuctionAlreadyClaimed {},
 
@@ -577,6 +604,9 @@ pub enum ContractError {
     #[error("Invalid Username, {error:?}")]
     InvalidUsername { error: Option<String> },
 
+    #[error("Invalid Environment Variable, {msg:?}")]
+    InvalidEnvironmentVariable { msg: String },
+
     #[error("Invalid Packet, {error:?}")]
     InvalidPacket { error: Option<String> },
 
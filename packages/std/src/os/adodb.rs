@@ -1,5 +1,6 @@
+use crate::error::ContractError;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{ensure, Addr, Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,7 @@ pub enum ExecuteMsg {
         action_fees: Option<Vec<ActionFee>>,
         version: String,
         publisher: Option<String>,
+        metadata: Option<ADOVersionMetadata>,
     },
     UpdateActionFees {
         ado_type: String,
@@ -30,6 +32,60 @@ pub enum ExecuteMsg {
         ado_type: String,
         publisher: String,
     },
+    /// Marks a published version as deprecated, it can still be instantiated but a warning is emitted
+    Deprecate {
+        ado_type: String,
+        version: String,
+        reason: Option<String>,
+    },
+    /// Marks a published version as yanked, it can no longer be instantiated
+    Yank {
+        ado_type: String,
+        version: String,
+        reason: Option<String>,
+    },
+}
+
+/// Information stored alongside a published ADO version
+#[cw_serde]
+#[derive(Default)]
+pub struct ADOVersionMetadata {
+    pub description: Option<String>,
+    /// URL of the source code the code ID was built from
+    pub source: Option<String>,
+    /// Hex encoded SHA-256 checksum of the JSON schema produced by `build_schema.sh`
+    pub schema_hash: Option<String>,
+    #[serde(default)]
+    pub audits: Vec<ADOAudit>,
+}
+
+impl ADOVersionMetadata {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if let Some(schema_hash) = &self.schema_hash {
+            ensure!(
+                schema_hash.len() == 64 && schema_hash.chars().all(|c| c.is_ascii_hexdigit()),
+                ContractError::InvalidADOVersion {
+                    msg: Some("Schema hash must be a hex encoded SHA-256 checksum".to_string())
+                }
+            );
+        }
+        for audit in &self.audits {
+            ensure!(
+                !audit.auditor.is_empty() && !audit.report.is_empty(),
+                ContractError::InvalidADOVersion {
+                    msg: Some("Audits must include an auditor and a report".to_string())
+                }
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct ADOAudit {
+    pub auditor: String,
+    /// URL or reference to the audit report
+    pub report: String,
 }
 
 #[cw_serde]
@@ -38,6 +94,9 @@ pub struct ActionFee {
     pub asset: String,
     pub amount: Uint128,
     pub receiver: Option<Addr>,
+    /// Percentage of the funds moved by the action charged in addition to `amount`, paid in `asset`
+    #[serde(default)]
+    pub percentage: Option<Decimal>,
 }
 
 impl ActionFee {
@@ -47,17 +106,30 @@ impl ActionFee {
             asset,
             amount,
             receiver: None,
+            percentage: None,
         }
     }
 
     pub fn with_receive(&self, receiver: Addr) -> Self {
         Self {
-            action: self.action.clone(),
-            asset: self.asset.clone(),
-            amount: self.amount,
             receiver: Some(receiver),
+            ..self.clone()
         }
     }
+
+    pub fn with_percentage(&self, percentage: Decimal) -> Self {
+        Self {
+            percentage: Some(percentage),
+            ..self.clone()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if let Some(percentage) = self.percentage {
+            ensure!(percentage <= Decimal::one(), ContractError::InvalidRate {});
+        }
+        Ok(())
+    }
 }
 
 #[cw_serde]
@@ -69,11 +141,71 @@ pub struct ADOMetadata {
     pub latest_version: String,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub enum ADOVersionStatus {
+    #[default]
+    Active,
+    Deprecated {
+        reason: Option<String>,
+    },
+    Yanked {
+        reason: Option<String>,
+    },
+}
+
+impl ADOVersionStatus {
+    #[inline]
+    pub fn is_yanked(&self) -> bool {
+        matches!(self, ADOVersionStatus::Yanked { .. })
+    }
+}
+
+#[cw_serde]
+pub struct ADOVersionResponse {
+    /// The versioned ADO type, e.g. `splitter@1.0.0`
+    pub version: String,
+    pub status: ADOVersionStatus,
+}
+
+#[cw_serde]
+pub struct ResolvedVersionResponse {
+    pub ado_type: String,
+    pub version: String,
+    pub code_id: u64,
+    #[serde(default)]
+    pub status: ADOVersionStatus,
+}
+
+#[cw_serde]
+pub struct ADOVersionInfoResponse {
+    pub ado_type: String,
+    pub version: String,
+    pub code_id: u64,
+    pub publisher: String,
+    pub status: ADOVersionStatus,
+    pub metadata: ADOVersionMetadata,
+}
+
+#[cw_serde]
+pub struct VerifyADOResponse {
+    pub code_id: u64,
+    pub ado_type: String,
+    pub version: String,
+    pub publisher: String,
+    pub status: ADOVersionStatus,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(u64)]
     CodeId { key: String },
+    /// Resolves a key such as `splitter@^1.2` to the published version used for it
+    ///
+    /// Yanked versions are only resolved when requested exactly, callers must check the returned status before using them
+    #[returns(ResolvedVersionResponse)]
+    ResolveVersion { key: String },
     #[returns(Option<String>)]
     #[serde(rename = "ado_type")]
     ADOType { code_id: u64 },
@@ -90,9 +222,25 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets the published versions of an ADO type along with their status
+    #[returns(Vec<ADOVersionResponse>)]
+    #[serde(rename = "ado_versions_with_status")]
+    ADOVersionsWithStatus {
+        ado_type: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     #[returns(Option<ADOMetadata>)]
     #[serde(rename = "ado_metadata")]
     ADOMetadata { ado_type: String },
+    /// Gets the stored information for the published version matching the key, such as `splitter@^1.2`
+    #[returns(ADOVersionInfoResponse)]
+    #[serde(rename = "ado_version_info")]
+    ADOVersionInfo { key: String },
+    /// Verifies that the contract at the given address was instantiated from a code ID published in the ADODB
+    #[returns(VerifyADOResponse)]
+    #[serde(rename = "verify_ado")]
+    VerifyADO { address: String },
     #[returns(Option<ActionFee>)]
     ActionFee { ado_type: String, action: String },
     #[returns(Option<ActionFee>)]
@@ -7,7 +7,9 @@ use lazy_static::__Deref;
 use serde::de::DeserializeOwned;
 use std::str::from_utf8;
 
-use super::adodb::{ActionFee, QueryMsg as ADODBQueryMsg};
+use super::adodb::{
+    ActionFee, QueryMsg as ADODBQueryMsg, ResolvedVersionResponse, VerifyADOResponse,
+};
 use super::kernel::ChannelInfo;
 
 #[cw_serde]
@@ -99,6 +101,21 @@ impl AOSQuerier {
         }
     }
 
+    /// Verifies that the contract at `address` was instantiated from a code ID published in the ADODB
+    ///
+    /// Returns the contract's ADO type, version and publisher, or an error if it was not published
+    pub fn verify_ado(
+        querier: &QuerierWrapper,
+        adodb_addr: &Addr,
+        address: &Addr,
+    ) -> Result<VerifyADOResponse, ContractError> {
+        let query = ADODBQueryMsg::VerifyADO {
+            address: address.to_string(),
+        };
+        let verified: VerifyADOResponse = querier.query_wasm_smart(adodb_addr, &query)?;
+        Ok(verified)
+    }
+
     pub fn code_id_getter_raw(
         querier: &QuerierWrapper,
         adodb_addr: &Addr,
@@ -125,6 +142,19 @@ impl AOSQuerier {
         Ok(code_id)
     }
 
+    /// Queries the ADODB for the published version and code ID matching the given key, such as `splitter@^1.2`
+    pub fn ado_version_resolver(
+        querier: &QuerierWrapper,
+        adodb_addr: &Addr,
+        key: &str,
+    ) -> Result<ResolvedVersionResponse, ContractError> {
+        let query = ADODBQueryMsg::ResolveVersion {
+            key: key.to_string(),
+        };
+        let resolved: ResolvedVersionResponse = querier.query_wasm_smart(adodb_addr, &query)?;
+        Ok(resolved)
+    }
+
     /// Queries the kernel's raw storage for the VFS's address
     pub fn vfs_address_getter(
         querier: &QuerierWrapper,
@@ -195,6 +225,16 @@ impl AOSQuerier {
         }
     }
 
+    /// Queries the kernel's raw storage for the value of the given environment variable
+    pub fn get_env_variable(
+        querier: &QuerierWrapper,
+        kernel_addr: &Addr,
+        variable: &str,
+    ) -> Result<Option<String>, ContractError> {
+        let key = AOSQuerier::get_map_storage_key("kernel_env_variables", &[variable.as_bytes()])?;
+        AOSQuerier::query_storage(querier, kernel_addr, &key)
+    }
+
     /// Queries the current chain name from the kernel
     pub fn get_chain_info(
         querier: &QuerierWrapper,
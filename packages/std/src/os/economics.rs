@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 use crate::amp::AndrAddr;
@@ -25,9 +25,13 @@ pub enum ExecuteMsg {
     /// 1. The balance of the ADO contract itself
     /// 2. The balance of the App contract for the ADO
     /// 3. The provided payee address
+    ///
+    /// Discounts and exemptions are determined by the payee. The amount of funds moved by the action can be
+    /// provided for fees that charge a percentage.
     PayFee {
         payee: Addr,
         action: String,
+        amount: Option<Uint128>,
     },
     /// Withdraw native funds from the Andromeda economics module.
     ///
@@ -45,6 +49,45 @@ pub enum ExecuteMsg {
         asset: String,
     },
     Receive(Cw20ReceiveMsg),
+    /// Replaces the fee tiers used to discount fees, can only be called by the contract owner
+    SetFeeTiers {
+        tiers: Vec<FeeTier>,
+    },
+    /// Exempts an address from paying fees, can only be called by the contract owner
+    AddFeeExemption {
+        address: AndrAddr,
+    },
+    /// Removes a fee exemption for an address, can only be called by the contract owner
+    RemoveFeeExemption {
+        address: AndrAddr,
+    },
+}
+
+#[cw_serde]
+pub enum FeeTierRequirement {
+    /// The payer has previously paid at least `amount` in fees for the given asset
+    Volume { asset: String, amount: Uint128 },
+    /// The payer has at least `amount` delegated to validators
+    Staked { amount: Uint128 },
+}
+
+/// A discount applied to fees for payers that meet the requirement.
+/// If a payer meets the requirements of multiple tiers the largest discount is applied.
+#[cw_serde]
+pub struct FeeTier {
+    pub requirement: FeeTierRequirement,
+    pub discount: Decimal,
+}
+
+#[cw_serde]
+pub struct FeeEstimate {
+    pub asset: String,
+    /// The fixed amount charged after any discount
+    pub amount: Uint128,
+    /// The percentage of the funds moved by the action charged after any discount
+    pub percentage: Option<Decimal>,
+    pub discount: Decimal,
+    pub exempt: bool,
 }
 
 #[cw_serde]
@@ -58,6 +101,34 @@ pub enum Cw20HookMsg {
 #[cw_serde]
 pub struct MigrateMsg {}
 
+/// A record of a fee paid to a receiver
+#[cw_serde]
+pub struct FeePayment {
+    pub id: u64,
+    pub ado_type: String,
+    pub action: String,
+    pub payee: Addr,
+    pub receiver: Addr,
+    pub asset: String,
+    pub amount: Uint128,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct FeeRevenue {
+    pub ado_type: String,
+    pub action: String,
+    pub asset: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct RevenueResponse {
+    pub revenue: Vec<FeeRevenue>,
+    /// The ID of the last payment included, used as `start_after` to continue the query
+    pub last_payment: Option<u64>,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -66,6 +137,54 @@ pub enum QueryMsg {
     /// Returns a `Uint128` representing the current balance
     #[returns(Uint128)]
     Balance { asset: String, address: AndrAddr },
+    /// Estimates the fee charged to the payer for the given ADO type and action
+    ///
+    /// Returns `None` if the action has no fee
+    #[returns(Option<FeeEstimate>)]
+    EstimateFee {
+        ado_type: String,
+        action: String,
+        payer: AndrAddr,
+    },
+    #[returns(Vec<FeeTier>)]
+    FeeTiers {},
+    #[returns(bool)]
+    FeeExempt { address: AndrAddr },
+    /// Queries the fees paid to a receiver within a time range, ordered by payment time
+    ///
+    /// `start_time` is inclusive and `end_time` is exclusive, `start_after` is the ID of a payment to the receiver.
+    /// Filtering by `action` requires the `ado_type` to be provided.
+    #[returns(Vec<FeePayment>)]
+    FeePayments {
+        receiver: AndrAddr,
+        ado_type: Option<String>,
+        action: Option<String>,
+        start_time: Option<Timestamp>,
+        end_time: Option<Timestamp>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Sums the fees paid to a receiver within a time range per ADO type, action and asset
+    ///
+    /// Only a page of at most `limit` (up to 100) payments is summed, the totals for the whole range are
+    /// the sum of every page, continuing each query from `last_payment`. Use `TotalRevenue` for all time totals.
+    #[returns(RevenueResponse)]
+    Revenue {
+        receiver: AndrAddr,
+        start_time: Option<Timestamp>,
+        end_time: Option<Timestamp>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Queries the total fees ever paid to a receiver per ADO type, action and asset
+    ///
+    /// `start_after` is the `(ado_type, action, asset)` of the last total returned
+    #[returns(Vec<FeeRevenue>)]
+    TotalRevenue {
+        receiver: AndrAddr,
+        start_after: Option<(String, String, String)>,
+        limit: Option<u32>,
+    },
 }
 
 #[cfg(test)]
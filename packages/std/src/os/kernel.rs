@@ -1,9 +1,11 @@
+use crate::amp::messages::AMPCtx;
 use crate::amp::messages::AMPMsg;
 use crate::amp::messages::AMPPkt;
 use crate::amp::AndrAddr;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
 use cosmwasm_std::Binary;
+use cosmwasm_std::Coin;
 
 #[cw_serde]
 pub struct ChannelInfo {
@@ -11,6 +13,8 @@ pub struct ChannelInfo {
     pub ics20_channel_id: Option<String>,
     pub direct_channel_id: Option<String>,
     pub supported_modules: Vec<String>,
+    /// The default lifetime in seconds of packets sent to the chain
+    pub packet_timeout: Option<u64>,
 }
 
 #[cw_serde]
@@ -46,9 +50,20 @@ pub enum ExecuteMsg {
         direct_channel_id: Option<String>,
         chain: String,
         kernel_address: String,
+        /// The default lifetime in seconds of packets sent to the chain
+        packet_timeout: Option<u64>,
     },
     /// Recovers funds from failed IBC messages
     Recover {},
+    /// Sets a protocol wide environment variable, restricted to the owner of the kernel
+    SetEnv {
+        variable: String,
+        value: String,
+    },
+    /// Removes a protocol wide environment variable, restricted to the owner of the kernel
+    UnsetEnv {
+        variable: String,
+    },
     // Only accessible to key contracts
     Internal(InternalMsg),
 }
@@ -61,6 +76,13 @@ pub enum InternalMsg {
         address: String,
         chain: String,
     },
+    // Restricted to the Kernel, handles a message of a packet whose failure does not revert the rest of the packet
+    AMPBatchMsg {
+        sender: Addr,
+        ctx: Option<AMPCtx>,
+        message: Box<AMPMsg>,
+        index: u64,
+    },
 }
 
 #[cw_serde]
@@ -72,6 +94,46 @@ pub struct ChannelInfoResponse {
     pub direct: Option<String>,
     pub kernel_address: String,
     pub supported_modules: Vec<String>,
+    pub packet_timeout: Option<u64>,
+}
+
+/// The lifecycle status of a packet sent by the kernel via IBC
+#[cw_serde]
+pub enum PacketStatus {
+    /// The packet has been sent and no acknowledgement or timeout has been received
+    Pending,
+    /// The packet was successfully acknowledged by the receiving chain
+    Acknowledged,
+    /// The receiving chain returned an error acknowledgement
+    Failed { error: String },
+    /// The packet timed out before being received
+    TimedOut,
+}
+
+/// The outcome of a single message within an AMP packet handled by the kernel
+#[cw_serde]
+pub enum AMPMsgOutcome {
+    /// The message was executed, or relayed in the case of IBC messages
+    Success,
+    /// The message failed and any funds attached to it were refunded to its recovery address
+    Failed { error: String },
+}
+
+/// The data returned by the kernel after handling a set of AMP messages
+#[cw_serde]
+pub struct AMPPktResponse {
+    /// The outcome of each message, in the order the messages were provided
+    pub outcomes: Vec<AMPMsgOutcome>,
+}
+
+#[cw_serde]
+pub struct PacketInfoResponse {
+    pub channel: String,
+    pub sequence: u64,
+    pub origin: String,
+    pub recovery_addr: Addr,
+    pub amount: Option<Coin>,
+    pub status: PacketStatus,
 }
 
 #[cw_serde]
@@ -85,6 +147,18 @@ pub enum QueryMsg {
     ChannelInfo { chain: String },
     #[returns(Vec<::cosmwasm_std::Coin>)]
     Recoveries { addr: Addr },
+    #[returns(Option<String>)]
+    GetEnv { variable: String },
+    /// The status of an outgoing packet identified by its source channel and sequence
+    #[returns(Option<PacketInfoResponse>)]
+    PacketStatus { channel: String, sequence: u64 },
+    /// All outgoing packets sent on behalf of the given origin
+    #[returns(Vec<PacketInfoResponse>)]
+    PacketsByOrigin {
+        origin: String,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -93,6 +167,12 @@ pub enum IbcExecuteMsg {
         recipient: AndrAddr,
         message: Binary,
     },
+    /// Relays a message along with the context of the packet it was sent in,
+    /// the message is forwarded again if its recipient is on another chain
+    SendMessageWithContext {
+        message: Box<AMPMsg>,
+        context: AMPCtx,
+    },
     CreateADO {
         instantiation_msg: Binary,
         owner: AndrAddr,
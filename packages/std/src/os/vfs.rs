@@ -1,6 +1,6 @@
 use crate::{amp::AndrAddr, error::ContractError};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{ensure, Addr, QuerierWrapper};
+use cosmwasm_std::{ensure, Addr, QuerierWrapper, Timestamp};
 use regex::Regex;
 
 pub const COMPONENT_NAME_REGEX: &str = r"^[A-Za-z0-9\.\-_]{1,40}$";
@@ -106,6 +106,102 @@ pub enum ExecuteMsg {
         chain: String,
         address: String,
     },
+    // Restricted to Kernel, confirms a pending cross-chain registration or rolls it back if an error is provided
+    CompleteUserCrossChain {
+        username: String,
+        chain: String,
+        error: Option<String>,
+    },
+    // Restricted to VFS owner, removes a pending cross-chain registration whose packet was never acknowledged
+    ClearUserCrossChain {
+        username: String,
+        chain: String,
+    },
+    // Restricted to the owner of the path, symlinks to the path are not removed
+    RemovePath {
+        path: AndrAddr,
+    },
+    // Restricted to the owner of the path
+    RenamePath {
+        path: AndrAddr,
+        name: String,
+    },
+    // Restricted to the owner of the path, `to` is the full destination path.
+    // Moving to another directory requires write access to the directory the destination resolves to.
+    MovePath {
+        from: AndrAddr,
+        to: AndrAddr,
+    },
+    // Restricted to the owner of the path
+    TransferPathOwnership {
+        path: AndrAddr,
+        new_owner: Addr,
+    },
+    // Restricted to the address the path resolves to or an address with admin access to it
+    SetPathPermission {
+        path: AndrAddr,
+        address: Addr,
+        access: PathAccess,
+    },
+    // Restricted to the address the path resolves to or an address with admin access to it
+    RemovePathPermission {
+        path: AndrAddr,
+        address: Addr,
+    },
+    // Restricted to the holder of an unexpired username, paths under the username are moved to the new address.
+    // The new address must not hold a username or have any paths, and registrations on other chains are removed.
+    TransferUsername {
+        username: String,
+        new_address: Addr,
+    },
+    // Restricted to the holder of an unexpired username, paths under the username remain under the holder's address
+    ReleaseUsername {
+        username: String,
+    },
+    // Restricted to the holder of an unexpired username
+    RenewUsername {
+        username: String,
+    },
+    // Restricted to VFS owner, usernames registered after this is set expire after the given number of seconds unless renewed
+    // Expired usernames continue to resolve until registered by another address
+    SetUsernameRegistrationDuration {
+        duration: Option<u64>,
+    },
+}
+
+/// Access granted to an address over the direct children of a path.
+///
+/// Each level of access includes the levels below it.
+#[cw_serde]
+#[derive(PartialOrd, Eq, Ord)]
+pub enum PathAccess {
+    /// Informational only, VFS paths are publicly readable
+    Read,
+    /// May add, remove, rename and move paths within the path
+    Write,
+    /// May also grant and revoke access to the path
+    Admin,
+}
+
+#[cw_serde]
+pub enum CrossChainUsernameStatus {
+    /// The registration has been sent and is awaiting acknowledgement
+    Pending,
+    /// The registration has been acknowledged by the receiving chain
+    Registered,
+}
+
+#[cw_serde]
+pub struct CrossChainUsername {
+    pub chain: String,
+    pub address: String,
+    pub status: CrossChainUsernameStatus,
+}
+
+#[cw_serde]
+pub struct PathPermission {
+    pub address: Addr,
+    pub access: PathAccess,
 }
 
 #[cw_serde]
@@ -126,6 +222,59 @@ pub enum QueryMsg {
     GetLibrary { address: Addr },
     #[returns(AndrAddr)]
     ResolveSymlink { path: AndrAddr },
+    #[returns(Option<Timestamp>)]
+    GetUsernameExpiration { username: String },
+    /// Lists the chains the username has been registered on, including pending registrations
+    #[returns(Vec<CrossChainUsername>)]
+    GetUsernameChains { username: String },
+    /// Resolves every path matching the pattern.
+    ///
+    /// Within a component below the root directory `*` matches any sequence of characters and `?` matches a single character.
+    #[returns(Vec<PathMatch>)]
+    ResolveGlob { pattern: String },
+    /// Lists the paths below the given path in depth first order, descending at most `depth` levels
+    #[returns(Vec<TreeNode>)]
+    Tree {
+        path: AndrAddr,
+        depth: u32,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists every path and symlink that resolves to the given address.
+    ///
+    /// Symlinks are paginated, `start_after` being the last symlink path of the previous page.
+    #[returns(ReverseLookupResponse)]
+    ReverseLookup {
+        address: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<PathPermission>)]
+    PathPermissions { path: AndrAddr },
+}
+
+#[cw_serde]
+pub struct PathMatch {
+    pub path: String,
+    pub address: Addr,
+}
+
+#[cw_serde]
+pub struct TreeNode {
+    pub path: String,
+    /// The address the path resolves to, `None` for symlinks that cannot be resolved
+    pub address: Option<Addr>,
+    pub symlink: Option<AndrAddr>,
+    /// The depth of the path below the queried path, starting at 1
+    pub depth: u32,
+}
+
+#[cw_serde]
+pub struct ReverseLookupResponse {
+    /// Paths that resolve directly to the address
+    pub paths: Vec<String>,
+    /// Symlinks whose target resolves to the address
+    pub symlinks: Vec<String>,
 }
 
 /// Queries the provided VFS contract address to resolve the given path
@@ -1,8 +1,14 @@
 use crate::{
     ado_base::AndromedaQuery,
-    ado_contract::ADOContract,
+    ado_contract::{
+        permissioning::{AddressListQueryMsg, IncludesAddressResponse},
+        ADOContract,
+    },
     amp::{ADO_DB_KEY, ECONOMICS_KEY, OSMOSIS_ROUTER_KEY, VFS_KEY},
-    os::adodb::{ActionFee, QueryMsg as ADODBQueryMsg},
+    os::adodb::{
+        ADOVersion, ADOVersionStatus, ActionFee, QueryMsg as ADODBQueryMsg,
+        ResolvedVersionResponse, VerifyADOResponse,
+    },
     os::kernel::QueryMsg as KernelQueryMsg,
     os::vfs::QueryMsg as VFSQueryMsg,
 };
@@ -11,12 +17,11 @@ use cosmwasm_std::SubMsg;
 use cosmwasm_std::{
     from_binary, from_slice,
     testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR},
-    to_binary, Addr, Binary, Coin, ContractInfoResponse, ContractResult, OwnedDeps, Querier,
-    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+    to_binary, Addr, Binary, Coin, ContractInfoResponse, ContractResult, Decimal, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
-#[cfg(feature = "primitive")]
-use cosmwasm_std::{Decimal, Uint128};
 use cw20::{BalanceResponse, Cw20QueryMsg};
+use cw721::{Cw721QueryMsg, TokensResponse};
 
 /// Mock CW20 Contract Address
 pub const MOCK_CW20_CONTRACT: &str = "cw20_contract";
@@ -43,6 +48,8 @@ pub const MOCK_ECONOMICS_CONTRACT: &str = "economics_contract";
 pub const MOCK_RATES_CONTRACT: &str = "rates_contract";
 /// Mock Address List Contract Address
 pub const MOCK_ADDRESS_LIST_CONTRACT: &str = "address_list_contract";
+/// Mock CW721 Contract Address
+pub const MOCK_CW721_CONTRACT: &str = "cw721_contract";
 
 /// An invalid contract address
 pub const INVALID_CONTRACT: &str = "invalid_contract";
@@ -50,8 +57,14 @@ pub const INVALID_CONTRACT: &str = "invalid_contract";
 pub const FAKE_VFS_PATH: &str = "/f";
 /// An invalid ADODB Key
 pub const FAKE_ADODB_KEY: &str = "fake_adodb_key";
+/// An ADODB Key that resolves to a deprecated version
+pub const MOCK_DEPRECATED_ADODB_KEY: &str = "deprecated_ado";
+/// An ADODB Key that resolves to a yanked version
+pub const MOCK_YANKED_ADODB_KEY: &str = "yanked_ado";
 /// A valid action
 pub const MOCK_ACTION: &str = "action";
+/// A valid action with a fee of 10 uusd plus 5% of the funds moved
+pub const MOCK_PERCENTAGE_ACTION: &str = "percentage_action";
 pub const UNWHITELISTED_ADDRESS: &str = "unwhitelisted_address";
 pub const RATES_EXCLUDED_ADDRESS: &str = "rates_excluded_address";
 
@@ -136,8 +149,8 @@ impl MockAndromedaQuerier {
                     MOCK_KERNEL_CONTRACT => self.handle_kernel_query(msg),
                     MOCK_VFS_CONTRACT => self.handle_vfs_query(msg),
                     MOCK_ADODB_CONTRACT => self.handle_adodb_query(msg),
-                    #[cfg(feature = "modules")]
                     MOCK_ADDRESS_LIST_CONTRACT => self.handle_address_list_query(msg),
+                    MOCK_CW721_CONTRACT => self.handle_cw721_query(msg),
                     _ => match from_binary::<AndromedaQuery>(msg) {
                         Ok(msg) => self.handle_ado_query(msg),
                         _ => panic!("Unsupported query for contract: {contract_addr}"),
@@ -195,6 +208,8 @@ impl MockAndromedaQuerier {
     /// Handles all VFS queries.
     ///
     /// Returns the path provided for `ResolvePath` queries, or an error for`FAKE_PATH`.
+    ///
+    /// Returns a path below the `team` home directory for `Paths` queries for any address excluding `UNWHITELISTED_ADDRESS`.
     fn handle_vfs_query(&self, msg: &Binary) -> QuerierResult {
         match from_binary(msg).unwrap() {
             VFSQueryMsg::ResolvePath { path } => match path.as_str() {
@@ -210,7 +225,11 @@ impl MockAndromedaQuerier {
                 _ => SystemResult::Ok(ContractResult::Ok(to_binary(&path).unwrap())),
             },
             VFSQueryMsg::Paths { addr } => {
-                SystemResult::Ok(ContractResult::Ok(to_binary(&addr).unwrap()))
+                let paths = match addr.as_str() {
+                    UNWHITELISTED_ADDRESS => vec![addr.to_string()],
+                    _ => vec![format!("team/{addr}")],
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&paths).unwrap()))
             }
             VFSQueryMsg::GetUsername { address } => {
                 SystemResult::Ok(ContractResult::Ok(to_binary(&address).unwrap()))
@@ -218,6 +237,7 @@ impl MockAndromedaQuerier {
             VFSQueryMsg::GetLibrary { address } => {
                 SystemResult::Ok(ContractResult::Ok(to_binary(&address).unwrap()))
             }
+            _ => SystemResult::Ok(ContractResult::Err("Not implemented".to_string())),
         }
     }
 
@@ -236,6 +256,11 @@ impl MockAndromedaQuerier {
     /// Returns `"ADOType"` for `ADOType` queries with code ID 1 and an error otherwise.
     ///
     /// Returns an error for `CodeId` queries with key `FAKE_ADODB_KEY` and 1 otherwise.
+    ///
+    /// Returns an error for `ResolveVersion` queries with key `FAKE_ADODB_KEY` and version `0.1.0` with code ID 1 otherwise.
+    /// The version is deprecated for key `MOCK_DEPRECATED_ADODB_KEY` and yanked for key `MOCK_YANKED_ADODB_KEY`.
+    ///
+    /// Returns an error for `VerifyADO` queries with address `INVALID_CONTRACT` and `"ADOType"` with code ID 1 otherwise.
     fn handle_adodb_query(&self, msg: &Binary) -> QuerierResult {
         match from_binary(msg).unwrap() {
             ADODBQueryMsg::ADOType { code_id } => match code_id {
@@ -246,6 +271,39 @@ impl MockAndromedaQuerier {
                 FAKE_ADODB_KEY => SystemResult::Ok(ContractResult::Err("Invalid Key".to_string())),
                 _ => SystemResult::Ok(ContractResult::Ok(to_binary(&1).unwrap())),
             },
+            ADODBQueryMsg::ResolveVersion { key } => match key.as_str() {
+                FAKE_ADODB_KEY => SystemResult::Ok(ContractResult::Err("Invalid Key".to_string())),
+                _ => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&ResolvedVersionResponse {
+                        ado_type: ADOVersion::from_string(key.as_str()).get_type(),
+                        version: "0.1.0".to_string(),
+                        code_id: 1,
+                        status: match key.as_str() {
+                            MOCK_DEPRECATED_ADODB_KEY => ADOVersionStatus::Deprecated {
+                                reason: Some("Use a newer version".to_string()),
+                            },
+                            MOCK_YANKED_ADODB_KEY => ADOVersionStatus::Yanked { reason: None },
+                            _ => ADOVersionStatus::Active,
+                        },
+                    })
+                    .unwrap(),
+                )),
+            },
+            ADODBQueryMsg::VerifyADO { address } => match address.as_str() {
+                INVALID_CONTRACT => {
+                    SystemResult::Ok(ContractResult::Err("Unpublished ADO".to_string()))
+                }
+                _ => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&VerifyADOResponse {
+                        code_id: 1,
+                        ado_type: "ADOType".to_string(),
+                        version: "0.1.0".to_string(),
+                        publisher: MOCK_ADO_PUBLISHER.to_string(),
+                        status: ADOVersionStatus::Active,
+                    })
+                    .unwrap(),
+                )),
+            },
             _ => SystemResult::Ok(ContractResult::Err("Not implemented".to_string())),
         }
     }
@@ -304,11 +362,29 @@ impl MockAndromedaQuerier {
         }
     }
 
-    #[cfg(feature = "modules")]
     /// Handles all address list queries
     ///
-    /// Returns `true` for `OnExecute` queries for any address excluding `UNWHITELISTED_ADDRESS`.
+    /// Returns `true` for `IncludesAddress` queries for any address excluding `UNWHITELISTED_ADDRESS`.
     fn handle_address_list_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg) {
+            Ok(AddressListQueryMsg::IncludesAddress { address }) => {
+                let included = address != UNWHITELISTED_ADDRESS;
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&IncludesAddressResponse { included }).unwrap(),
+                ))
+            }
+            #[cfg(feature = "modules")]
+            Err(_) => self.handle_address_list_hook_query(msg),
+            #[cfg(not(feature = "modules"))]
+            Err(_) => panic!("Unsupported query for contract: {MOCK_ADDRESS_LIST_CONTRACT}"),
+        }
+    }
+
+    #[cfg(feature = "modules")]
+    /// Handles all address list hook queries
+    ///
+    /// Returns `true` for `OnExecute` queries for any address excluding `UNWHITELISTED_ADDRESS`.
+    fn handle_address_list_hook_query(&self, msg: &Binary) -> QuerierResult {
         use cosmwasm_std::Response;
 
         use crate::ado_base::hooks::{AndromedaHook, HookMsg, OnFundsTransferResponse};
@@ -362,6 +438,24 @@ impl MockAndromedaQuerier {
         }
     }
 
+    /// Handles all CW721 queries.
+    ///
+    /// Returns a single token for `Tokens` queries for any owner excluding `UNWHITELISTED_ADDRESS`.
+    fn handle_cw721_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg).unwrap() {
+            Cw721QueryMsg::Tokens { owner, .. } => {
+                let tokens = match owner.as_str() {
+                    UNWHITELISTED_ADDRESS => vec![],
+                    _ => vec!["token1".to_string()],
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&TokensResponse { tokens }).unwrap(),
+                ))
+            }
+            _ => SystemResult::Ok(ContractResult::Err("Not implemented".to_string())),
+        }
+    }
+
     /// Handles all CW20 queries.
     ///
     /// Returns a balance of 10 for any `Balance` query.
@@ -437,7 +531,19 @@ impl MockAndromedaQuerier {
             let key = split.last();
             match key {
                 Some(key) => {
-                    if key.contains("ADOTypeaction") {
+                    if key.contains(&format!("ADOType{MOCK_PERCENTAGE_ACTION}")) {
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(
+                                &ActionFee::new(
+                                    MOCK_PERCENTAGE_ACTION.to_string(),
+                                    "uusd".to_string(),
+                                    Uint128::from(10u128),
+                                )
+                                .with_percentage(Decimal::percent(5)),
+                            )
+                            .unwrap(),
+                        ))
+                    } else if key.contains("ADOTypeaction") {
                         SystemResult::Ok(ContractResult::Ok(
                             to_binary(&ActionFee::new(
                                 MOCK_ACTION.to_string(),
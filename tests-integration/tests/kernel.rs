@@ -1,17 +1,20 @@
-use andromeda_app_contract::mock::mock_andromeda_app;
+use andromeda_app::app::AppComponent;
+use andromeda_app_contract::mock::{mock_andromeda_app, mock_app_instantiate_msg};
 use andromeda_finance::splitter::AddressPercent;
 use andromeda_splitter::mock::{
     mock_andromeda_splitter, mock_splitter_instantiate_msg, mock_splitter_send_msg,
 };
 use andromeda_std::{
     amp::{messages::AMPMsg, AndrAddr, Recipient},
+    error::ContractError,
+    os::adodb::ExecuteMsg as ADODBExecuteMsg,
     os::kernel::ExecuteMsg as KernelExecuteMsg,
 };
 use andromeda_testing::{mock::MockAndromeda, mock_contract::MockContract};
 
 use cosmwasm_std::{coin, to_binary, Addr, Decimal};
 
-use cw_multi_test::App;
+use cw_multi_test::{App, Executor};
 
 fn mock_app() -> App {
     App::new(|router, _api, storage| {
@@ -108,3 +111,79 @@ fn kernel() {
 
     assert!(res.data.is_none());
 }
+
+#[test]
+fn kernel_yanked_version() {
+    let owner = Addr::unchecked("owner");
+
+    let mut router = mock_app();
+    let andr = mock_andromeda(&mut router, owner.clone());
+    andr.store_ado(&mut router, mock_andromeda_app(), "app");
+    andr.store_ado(&mut router, mock_andromeda_splitter(), "splitter");
+
+    router
+        .execute_contract(
+            owner.clone(),
+            andr.adodb_address.clone(),
+            &ADODBExecuteMsg::Yank {
+                ado_type: "splitter".to_string(),
+                version: "0.1.0".to_string(),
+                reason: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let splitter_msg = mock_splitter_instantiate_msg(
+        vec![AddressPercent::new(
+            Recipient::from_string(owner.to_string()),
+            Decimal::one(),
+        )],
+        andr.kernel_address.clone(),
+        None,
+        None,
+    );
+    let yanked = ContractError::ADOVersionYanked {
+        version: "splitter@0.1.0".to_string(),
+    };
+
+    // The kernel refuses to create an ADO from a yanked version
+    let err = router
+        .execute_contract(
+            owner.clone(),
+            andr.kernel_address.clone(),
+            &KernelExecuteMsg::Create {
+                ado_type: "splitter@0.1.0".to_string(),
+                msg: to_binary(&splitter_msg).unwrap(),
+                owner: None,
+                chain: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.downcast::<ContractError>().unwrap(), yanked);
+
+    // As does an App creating a component from a yanked version
+    let app_init_msg = mock_app_instantiate_msg(
+        "Yanked App",
+        vec![AppComponent::new(
+            "splitter",
+            "splitter@0.1.0",
+            to_binary(&splitter_msg).unwrap(),
+        )],
+        andr.kernel_address.to_string(),
+        None,
+    );
+    let app_code_id = andr.get_code_id(&mut router, "app");
+    let err = router
+        .instantiate_contract(
+            app_code_id,
+            owner.clone(),
+            &app_init_msg,
+            &[],
+            "Yanked App",
+            Some(owner.to_string()),
+        )
+        .unwrap_err();
+    assert_eq!(err.downcast::<ContractError>().unwrap(), yanked);
+}